@@ -1,16 +1,17 @@
 use owo_colors::OwoColorize;
 
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
 use poc_framework::{
     Environment,
-    localhost_client,
-    keypair, RemoteEnvironment,
     solana_program::{
         pubkey::Pubkey,
         instruction::{AccountMeta, Instruction},
     },
     solana_sdk::{
         system_program,
-        signature::{read_keypair_file, Signer},
+        signature::{read_keypair_file, Keypair, Signer},
         bpf_loader_upgradeable::UpgradeableLoaderState,
     },
 };
@@ -38,10 +39,11 @@ pub const WALLET_LEN: u64 = 32 + 32;
 pub fn main() {
     let programa_keypair = read_keypair_file("./target/so/level0-keypair.json").unwrap();
     let programa = programa_keypair.pubkey();
-    let cliente1 = localhost_client();
+    let cliente1 = dryrun::client();
     
-    let hacker = keypair(1);
-    let authority_info = keypair(2);
+    let mut actors = Actors::new();
+    let hacker = actors.get("hacker");
+    let authority_info = actors.get("victim");
 
     let (wallet_address, _ ) =
     Pubkey::find_program_address(&[&authority_info.pubkey().to_bytes()], &programa);
@@ -50,7 +52,8 @@ pub fn main() {
 
     /* First we create the accounts */
  
-    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, keypair(2), 10000000000);
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("victim"), 10000000000);
+    println!("{}", actors.legend().blue());
 
             env.execute_as_transaction(
                 &[Instruction {
@@ -143,7 +146,8 @@ pub fn main() {
             let mut hacker_wallet_data: Vec<u8> = vec![];
             hacker_wallet.serialize(&mut hacker_wallet_data).unwrap();
 
-            let fake_wallet = keypair(3);
+            let fake_wallet = Keypair::new();
+            actors.label(fake_wallet.pubkey(), "level0_fake_wallet");
             env.create_account_with_data(&fake_wallet, hacker_wallet_data);
 
             let steal_amount = env.get_account(vault_address).unwrap().lamports;
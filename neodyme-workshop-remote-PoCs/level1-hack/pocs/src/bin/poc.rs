@@ -1,9 +1,10 @@
 use owo_colors::OwoColorize;
 
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
 use poc_framework::{
     Environment,
-    localhost_client,
-    keypair, RemoteEnvironment,
     solana_program::{
         pubkey::Pubkey,
         instruction::{AccountMeta, Instruction},
@@ -37,10 +38,11 @@ pub const WALLET_LEN: u64 = 32;
 pub fn main() {
     let programa_keypair = read_keypair_file("./target/so/level1-keypair.json").unwrap();
     let programa = programa_keypair.pubkey();
-    let cliente1 = localhost_client();
+    let cliente1 = dryrun::client();
     
-    let hacker = keypair(1);
-    let authority_info = keypair(2);
+    let mut actors = Actors::new();
+    let hacker = actors.get("hacker");
+    let authority_info = actors.get("victim");
 
     /* Create the PDA */
     let (wallet_address, _) =
@@ -48,7 +50,8 @@ pub fn main() {
 
     /* First we create the accounts */
  
-    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, keypair(2), 10000000000);
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("victim"), 10000000000);
+    println!("{}", actors.legend().blue());
             env.airdrop(hacker.pubkey(), 10000000000);
             env.execute_as_transaction(
                 &[Instruction {
@@ -0,0 +1,157 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program, sysvar,
+};
+
+/// Mock constant-product AMM between SOL (the Pool lamports) and one token (the Reserve token account).
+/// No fees, no LP shares: liquidity is added by sending lamports to the Pool and tokens to the Reserve.
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum AmmInstruction {
+    /// Initialize a Pool for a mint
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Pool account
+    /// (2) Reserve token account (owned by the Pool)
+    /// (3) Mint
+    /// (4) Payer (must sign)
+    /// (5) Rent sysvar
+    /// (6) System program
+    InitializePool,
+    /// Sell lamports for tokens
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Pool account
+    /// (2) Reserve token account
+    /// (3) User (must sign)
+    /// (4) User token account
+    /// (5) Token program
+    /// (6) System program
+    SwapSolForToken { lamports: u64 },
+    /// Sell tokens for lamports
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Pool account
+    /// (2) Reserve token account
+    /// (3) User (must sign)
+    /// (4) User token account
+    /// (5) Token program
+    SwapTokenForSol { amount: u64 },
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Pool {
+    pub mint: Pubkey,
+    pub reserve: Pubkey,
+    pub seed: u8,
+}
+
+pub const POOL_LEN: u64 = 32 + 32 + 1;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_pool_address(mint: Pubkey, amm_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"POOL".as_bytes(), &mint.to_bytes()], &amm_program)
+}
+
+/// Output of a swap of `amount_in` against the given reserves, `x * y = k` without fee
+pub fn quote(amount_in: u64, reserve_in: u64, reserve_out: u64) -> Result<u64, ProgramError> {
+    // an empty reserve and nothing in: no price
+    Ok((reserve_out as u128 * amount_in as u128)
+        .checked_div(reserve_in as u128 + amount_in as u128)
+        .ok_or(ProgramError::InsufficientFunds)? as u64)
+}
+
+/// (lamports, tokens) currently in the Pool
+pub fn reserves(
+    pool_info: &AccountInfo,
+    reserve_info: &AccountInfo,
+    rent: &Rent,
+) -> Result<(u64, u64), ProgramError> {
+    let lamports = pool_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(POOL_LEN as usize));
+    let tokens = spl_token::state::Account::unpack(&reserve_info.data.borrow())?.amount;
+    Ok((lamports, tokens))
+}
+
+pub fn initialize_pool(
+    amm_program: Pubkey,
+    reserve: Pubkey,
+    mint: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    let (pool_address, _) = get_pool_address(mint, amm_program);
+    Instruction {
+        program_id: amm_program,
+        accounts: vec![
+            AccountMeta::new(pool_address, false),
+            AccountMeta::new_readonly(reserve, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: AmmInstruction::InitializePool.try_to_vec().unwrap(),
+    }
+}
+
+pub fn swap_sol_for_token(
+    amm_program: Pubkey,
+    mint: Pubkey,
+    reserve: Pubkey,
+    user: Pubkey,
+    user_token_account: Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let (pool_address, _) = get_pool_address(mint, amm_program);
+    Instruction {
+        program_id: amm_program,
+        accounts: vec![
+            AccountMeta::new(pool_address, false),
+            AccountMeta::new(reserve, false),
+            AccountMeta::new(user, true),
+            AccountMeta::new(user_token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: AmmInstruction::SwapSolForToken { lamports }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn swap_token_for_sol(
+    amm_program: Pubkey,
+    mint: Pubkey,
+    reserve: Pubkey,
+    user: Pubkey,
+    user_token_account: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (pool_address, _) = get_pool_address(mint, amm_program);
+    Instruction {
+        program_id: amm_program,
+        accounts: vec![
+            AccountMeta::new(pool_address, false),
+            AccountMeta::new(reserve, false),
+            AccountMeta::new(user, true),
+            AccountMeta::new(user_token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: AmmInstruction::SwapTokenForSol { amount }.try_to_vec().unwrap(),
+    }
+}
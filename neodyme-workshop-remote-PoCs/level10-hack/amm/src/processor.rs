@@ -0,0 +1,161 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{get_pool_address, quote, reserves, AmmInstruction, Pool, POOL_LEN};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match AmmInstruction::deserialize(&mut instruction_data)? {
+        AmmInstruction::InitializePool => initialize_pool(program_id, accounts),
+        AmmInstruction::SwapSolForToken { lamports } => {
+            swap_sol_for_token(program_id, accounts, lamports)
+        }
+        AmmInstruction::SwapTokenForSol { amount } => {
+            swap_token_for_sol(program_id, accounts, amount)
+        }
+    }
+}
+
+fn initialize_pool(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pool_info = next_account_info(account_info_iter)?;
+    let reserve_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (pool_address, pool_seed) = get_pool_address(*mint_info.key, *program_id);
+    let reserve = spl_token::state::Account::unpack(&reserve_info.data.borrow())?;
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*pool_info.key, pool_address);
+    assert!(pool_info.data_is_empty());
+    assert!(payer_info.is_signer, "payer must sign!");
+    assert_eq!(*reserve_info.owner, spl_token::id());
+    assert_eq!(reserve.mint, *mint_info.key);
+    assert_eq!(reserve.owner, pool_address);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &payer_info.key,
+            &pool_address,
+            rent.minimum_balance(POOL_LEN as usize),
+            POOL_LEN,
+            &program_id,
+        ),
+        &[payer_info.clone(), pool_info.clone()],
+        &[&[&"POOL".as_bytes(), &mint_info.key.to_bytes(), &[pool_seed]]],
+    )?;
+
+    let pool = Pool {
+        mint: *mint_info.key,
+        reserve: *reserve_info.key,
+        seed: pool_seed,
+    };
+
+    pool.serialize(&mut &mut (*pool_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn swap_sol_for_token(program_id: &Pubkey, accounts: &[AccountInfo], lamports: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pool_info = next_account_info(account_info_iter)?;
+    let reserve_info = next_account_info(account_info_iter)?;
+    let user_info = next_account_info(account_info_iter)?;
+    let user_token_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let pool = Pool::deserialize(&mut &(*pool_info.data).borrow_mut()[..])?;
+
+    assert_eq!(pool_info.owner, program_id);
+    assert_eq!(pool.reserve, *reserve_info.key);
+    assert_eq!(*token_program_info.key, spl_token::id());
+
+    let (sol_reserve, token_reserve) = reserves(pool_info, reserve_info, &Rent::get()?)?;
+    let amount_out = quote(lamports, sol_reserve, token_reserve)?;
+    if amount_out == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    msg!("swap {} lamports for {} tokens", lamports, amount_out);
+
+    invoke(
+        &system_instruction::transfer(&user_info.key, &pool_info.key, lamports),
+        &[user_info.clone(), pool_info.clone()],
+    )?;
+
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::id(),
+            &reserve_info.key,
+            &user_token_info.key,
+            &pool_info.key,
+            &[],
+            amount_out,
+        )?,
+        &[
+            reserve_info.clone(),
+            user_token_info.clone(),
+            pool_info.clone(),
+        ],
+        &[&[&"POOL".as_bytes(), &pool.mint.to_bytes(), &[pool.seed]]],
+    )?;
+
+    Ok(())
+}
+
+fn swap_token_for_sol(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pool_info = next_account_info(account_info_iter)?;
+    let reserve_info = next_account_info(account_info_iter)?;
+    let user_info = next_account_info(account_info_iter)?;
+    let user_token_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let pool = Pool::deserialize(&mut &(*pool_info.data).borrow_mut()[..])?;
+
+    assert_eq!(pool_info.owner, program_id);
+    assert_eq!(pool.reserve, *reserve_info.key);
+    assert_eq!(*token_program_info.key, spl_token::id());
+
+    let (sol_reserve, token_reserve) = reserves(pool_info, reserve_info, &Rent::get()?)?;
+    let lamports_out = quote(amount, token_reserve, sol_reserve)?;
+    if lamports_out == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    msg!("swap {} tokens for {} lamports", amount, lamports_out);
+
+    invoke(
+        &spl_token::instruction::transfer(
+            &spl_token::id(),
+            &user_token_info.key,
+            &reserve_info.key,
+            &user_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            user_token_info.clone(),
+            reserve_info.clone(),
+            user_info.clone(),
+        ],
+    )?;
+
+    **pool_info.lamports.borrow_mut() -= lamports_out;
+    **user_info.lamports.borrow_mut() += lamports_out;
+
+    Ok(())
+}
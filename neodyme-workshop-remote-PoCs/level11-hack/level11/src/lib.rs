@@ -0,0 +1,124 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum VaultInstruction {
+    /// Initialize a Balance account
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Balance account
+    /// (2) Owner (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    Initialize,
+    /// Deposit lamports into the Vault
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Balance account
+    /// (2) Vault account
+    /// (3) Owner (must sign)
+    /// (4) System program
+    Deposit { amount: u64 },
+    /// Withdraw lamports from the Vault, then optionally notify a callback program.
+    /// The callback receives every account but itself, in the same order, signed by the Vault
+    /// so it can tell the call comes from here, and the withdrawn amount (u64, little endian) as data
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Balance account
+    /// (2) Vault account
+    /// (3) Owner (must sign)
+    /// (4) System program
+    /// (5) Callback program (optional)
+    /// (6..) Accounts for the callback (optional)
+    Withdraw { amount: u64 },
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Balance {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+pub const BALANCE_LEN: u64 = 32 + 8;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_balance_address(owner: Pubkey, vault_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&owner.to_bytes()], &vault_program)
+}
+
+/// System account holding every deposit, only this program can sign for it
+pub fn get_vault_address(vault_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"VAULT".as_bytes()], &vault_program)
+}
+
+pub fn initialize(vault_program: Pubkey, owner: Pubkey) -> Instruction {
+    let (balance_address, _) = get_balance_address(owner, vault_program);
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(balance_address, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+pub fn deposit(vault_program: Pubkey, owner: Pubkey, amount: u64) -> Instruction {
+    let (balance_address, _) = get_balance_address(owner, vault_program);
+    let (vault_address, _) = get_vault_address(vault_program);
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(balance_address, false),
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Deposit { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn withdraw(vault_program: Pubkey, owner: Pubkey, amount: u64) -> Instruction {
+    let (balance_address, _) = get_balance_address(owner, vault_program);
+    let (vault_address, _) = get_vault_address(vault_program);
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(balance_address, false),
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Withdraw { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn withdraw_with_callback(
+    vault_program: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+    callback_program: Pubkey,
+    callback_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut instruction = withdraw(vault_program, owner, amount);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(callback_program, false));
+    instruction.accounts.extend_from_slice(callback_accounts);
+    instruction
+}
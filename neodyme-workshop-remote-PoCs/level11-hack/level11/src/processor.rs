@@ -0,0 +1,174 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{get_balance_address, get_vault_address, Balance, VaultInstruction, BALANCE_LEN};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match VaultInstruction::deserialize(&mut instruction_data)? {
+        VaultInstruction::Initialize => initialize(program_id, accounts),
+        VaultInstruction::Deposit { amount } => deposit(program_id, accounts, amount),
+        VaultInstruction::Withdraw { amount } => withdraw(program_id, accounts, amount),
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let balance_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (balance_address, balance_seed) = get_balance_address(*owner_info.key, *program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*balance_info.key, balance_address);
+    assert!(balance_info.data_is_empty());
+    assert!(owner_info.is_signer, "owner must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &owner_info.key,
+            &balance_address,
+            rent.minimum_balance(BALANCE_LEN as usize),
+            BALANCE_LEN,
+            &program_id,
+        ),
+        &[owner_info.clone(), balance_info.clone()],
+        &[&[&owner_info.key.to_bytes(), &[balance_seed]]],
+    )?;
+
+    let balance = Balance {
+        owner: *owner_info.key,
+        amount: 0,
+    };
+
+    balance
+        .serialize(&mut &mut (*balance_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn deposit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let balance_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let mut balance = Balance::deserialize(&mut &(*balance_info.data).borrow_mut()[..])?;
+    let (vault_address, _) = get_vault_address(*program_id);
+
+    assert_eq!(balance_info.owner, program_id);
+    assert_eq!(*vault_info.key, vault_address);
+    assert!(owner_info.is_signer, "owner must sign!");
+    assert_eq!(balance.owner, *owner_info.key);
+
+    invoke(
+        &system_instruction::transfer(&owner_info.key, &vault_info.key, amount),
+        &[owner_info.clone(), vault_info.clone()],
+    )?;
+
+    balance.amount = match balance.amount.checked_add(amount) {
+        Some(v) => v,
+        None => return Err(ProgramError::InvalidArgument),
+    };
+
+    balance
+        .serialize(&mut &mut (*balance_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let balance_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let mut balance = Balance::deserialize(&mut &(*balance_info.data).borrow_mut()[..])?;
+    let (vault_address, vault_seed) = get_vault_address(*program_id);
+
+    assert_eq!(balance_info.owner, program_id);
+    assert_eq!(*vault_info.key, vault_address);
+    assert!(owner_info.is_signer, "owner must sign!");
+    assert_eq!(balance.owner, *owner_info.key);
+
+    balance.amount = match balance.amount.checked_sub(amount) {
+        Some(v) => v,
+        None => return Err(ProgramError::InsufficientFunds),
+    };
+
+    invoke_signed(
+        &system_instruction::transfer(&vault_info.key, &owner_info.key, amount),
+        &[vault_info.clone(), owner_info.clone()],
+        &[&[&"VAULT".as_bytes(), &[vault_seed]]],
+    )?;
+
+    balance
+        .serialize(&mut &mut (*balance_info.data).borrow_mut()[..])
+        .unwrap();
+
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let callback_info = match next_account_info(account_info_iter) {
+        Ok(info) => info,
+        Err(_) => return Ok(()),
+    };
+
+    // Everything but the callback program goes to the callback, with the Vault as signer.
+    // Signer privileges carry over to the callee: it can do with the Vault anything we can,
+    // like transferring all its lamports.
+    let forwarded: Vec<AccountInfo> = accounts
+        .iter()
+        .filter(|info| info.key != callback_info.key)
+        .cloned()
+        .collect();
+    #[cfg(not(feature = "patched"))]
+    let metas = forwarded
+        .iter()
+        .map(|info| AccountMeta {
+            pubkey: *info.key,
+            is_signer: info.is_signer || *info.key == vault_address,
+            is_writable: info.is_writable,
+        })
+        .collect();
+    #[cfg(feature = "patched")]
+    let metas = forwarded
+        .iter()
+        .map(|info| AccountMeta {
+            pubkey: *info.key,
+            is_signer: false,
+            is_writable: info.is_writable,
+        })
+        .collect();
+
+    let callback = Instruction {
+        program_id: *callback_info.key,
+        accounts: metas,
+        data: amount.to_le_bytes().to_vec(),
+    };
+    let mut callback_accounts = forwarded;
+    callback_accounts.push(callback_info.clone());
+
+    #[cfg(not(feature = "patched"))]
+    invoke_signed(
+        &callback,
+        &callback_accounts,
+        &[&[&"VAULT".as_bytes(), &[vault_seed]]],
+    )?;
+    #[cfg(feature = "patched")]
+    invoke(&callback, &callback_accounts)?;
+
+    Ok(())
+}
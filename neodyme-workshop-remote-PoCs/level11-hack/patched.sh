@@ -0,0 +1,5 @@
+#!/bin/bash
+# Same PoC against the patched program: the callback can't sign for the Vault and the PoC panics
+cargo build-bpf --manifest-path=./level11/Cargo.toml --bpf-out-dir=./target/so --features patched
+solana program deploy ./target/so/level11.so
+RUST_BACKTRACE=1 cargo run --manifest-path=./pocs/Cargo.toml --target-dir=./target/
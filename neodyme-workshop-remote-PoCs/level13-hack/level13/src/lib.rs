@@ -0,0 +1,92 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum VaultInstruction {
+    /// Initialize a Vault. Like an spl-token account, the Vault is created beforehand
+    /// (VAULT_LEN bytes, owned by this program) in the same transaction
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Authority (must sign)
+    /// (3) Rent sysvar
+    Initialize,
+    /// Deposit
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Money Source (must sign)
+    /// (3) System program
+    Deposit { amount: u64 },
+    /// Withdraw from the Vault
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Authority (must sign)
+    /// (3) Destination
+    Withdraw { amount: u64 },
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Vault {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+}
+
+pub const VAULT_LEN: u64 = 1 + 32;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn initialize(vault_program: Pubkey, vault: Pubkey, authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: VaultInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+pub fn deposit(vault_program: Pubkey, vault: Pubkey, source: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new(source, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Deposit { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn withdraw(
+    vault_program: Pubkey,
+    vault: Pubkey,
+    authority: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(destination, false),
+        ],
+        data: VaultInstruction::Withdraw { amount }.try_to_vec().unwrap(),
+    }
+}
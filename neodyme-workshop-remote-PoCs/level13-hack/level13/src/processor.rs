@@ -0,0 +1,99 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{Vault, VaultInstruction, VAULT_LEN};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match VaultInstruction::deserialize(&mut instruction_data)? {
+        VaultInstruction::Initialize => initialize(program_id, accounts),
+        VaultInstruction::Deposit { amount } => deposit(program_id, accounts, amount),
+        VaultInstruction::Withdraw { amount } => withdraw(program_id, accounts, amount),
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(vault_info.owner, program_id);
+    assert_eq!(vault_info.data_len(), VAULT_LEN as usize);
+    assert!(authority_info.is_signer, "authority must sign!");
+    assert!(rent.is_exempt(vault_info.lamports(), vault_info.data_len()));
+
+    // Nothing stops a second Initialize on a Vault already in use: the account exists,
+    // has the right owner and size, so the new authority simply overwrites the old one
+    #[cfg(feature = "patched")]
+    {
+        let vault = Vault::deserialize(&mut &(*vault_info.data).borrow_mut()[..])?;
+        if vault.is_initialized {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+    }
+
+    let vault = Vault {
+        is_initialized: true,
+        authority: *authority_info.key,
+    };
+
+    vault
+        .serialize(&mut &mut (*vault_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn deposit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let source_info = next_account_info(account_info_iter)?;
+
+    assert_eq!(vault_info.owner, program_id);
+
+    invoke(
+        &system_instruction::transfer(&source_info.key, &vault_info.key, amount),
+        &[vault_info.clone(), source_info.clone()],
+    )?;
+
+    Ok(())
+}
+
+fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let vault = Vault::deserialize(&mut &(*vault_info.data).borrow_mut()[..])?;
+
+    assert_eq!(vault_info.owner, program_id);
+    assert!(authority_info.is_signer, "authority must sign!");
+    assert_eq!(vault.authority, *authority_info.key);
+
+    let available = vault_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(VAULT_LEN as usize));
+    if amount > available {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    **vault_info.lamports.borrow_mut() -= amount;
+    **destination_info.lamports.borrow_mut() += amount;
+
+    Ok(())
+}
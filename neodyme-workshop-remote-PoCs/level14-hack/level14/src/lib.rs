@@ -0,0 +1,101 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum FeeInstruction {
+    /// Set the Treasury the fees are swept to, creating the Config the first time.
+    /// Only the upgrade authority of this program can do it
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Config account
+    /// (2) Upgrade authority (must sign)
+    /// (3) ProgramData account of this program
+    /// (4) Rent sysvar
+    /// (5) System program
+    SetConfig { treasury: Pubkey },
+    /// Pay a fee
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Config account
+    /// (2) Payer (must sign)
+    /// (3) System program
+    Pay { amount: u64 },
+    /// Send the collected fees to the Treasury, anyone can crank it
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Config account
+    /// (2) Treasury
+    Sweep,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Config {
+    pub treasury: Pubkey,
+}
+
+pub const CONFIG_LEN: u64 = 32;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_config_address(fee_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"CONFIG".as_bytes()], &fee_program)
+}
+
+/// Where the upgradeable loader keeps the ProgramData (and the upgrade authority) of a program
+pub fn get_programdata_address(program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&program.to_bytes()], &bpf_loader_upgradeable::id())
+}
+
+pub fn set_config(fee_program: Pubkey, upgrade_authority: Pubkey, treasury: Pubkey) -> Instruction {
+    let (config_address, _) = get_config_address(fee_program);
+    let (programdata_address, _) = get_programdata_address(fee_program);
+    Instruction {
+        program_id: fee_program,
+        accounts: vec![
+            AccountMeta::new(config_address, false),
+            AccountMeta::new(upgrade_authority, true),
+            AccountMeta::new_readonly(programdata_address, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: FeeInstruction::SetConfig { treasury }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn pay(fee_program: Pubkey, payer: Pubkey, amount: u64) -> Instruction {
+    let (config_address, _) = get_config_address(fee_program);
+    Instruction {
+        program_id: fee_program,
+        accounts: vec![
+            AccountMeta::new(config_address, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: FeeInstruction::Pay { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn sweep(fee_program: Pubkey, treasury: Pubkey) -> Instruction {
+    let (config_address, _) = get_config_address(fee_program);
+    Instruction {
+        program_id: fee_program,
+        accounts: vec![
+            AccountMeta::new(config_address, false),
+            AccountMeta::new(treasury, false),
+        ],
+        data: FeeInstruction::Sweep.try_to_vec().unwrap(),
+    }
+}
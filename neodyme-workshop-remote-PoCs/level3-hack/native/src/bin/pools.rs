@@ -0,0 +1,70 @@
+/*
+Lists the Vaults and TipPools of a deployed level3 program using getProgramAccounts filters.
+
+cargo run --bin pools                        -> every vault and every pool
+cargo run --bin pools -- --vault <pubkey>    -> only the pools of that vault
+cargo run --bin pools -- --creator <pubkey>  -> only the vaults of that creator (and their pools)
+*/
+use std::str::FromStr;
+
+use solana_client::rpc_client::RpcClient;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::{
+        signature::read_keypair_file,
+        signer::Signer,
+        commitment_config::CommitmentConfig,
+    };
+
+use owo_colors::OwoColorize;
+
+use native::query;
+
+fn main() {
+
+    let programa_keypair = read_keypair_file("./target/so/level3-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+
+    let cliente1 = dryrun::url();
+    let env = RpcClient::new_with_commitment(cliente1, CommitmentConfig::confirmed());
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let flag = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .map(|v| Pubkey::from_str(v).expect("invalid pubkey"))
+    };
+    let vault_filter = flag("--vault");
+    let creator_filter = flag("--creator");
+
+    let vaults = match creator_filter {
+        Some(creator) => query::vaults_by_creator(&env, &programa, &creator).unwrap(),
+        None => query::vaults(&env, &programa).unwrap(),
+    };
+
+    println!("");
+    println!("{} {}", "Vaults of program".purple().bold(), programa);
+    println!("");
+    for (address, vault) in vaults.iter() {
+        println!("{} {} {} {:?}", "Vault:".bold().blue(), address, "data:".blue(), vault);
+    }
+
+    let pools = match (vault_filter, creator_filter) {
+        (Some(vault), _) => query::pools_by_vault(&env, &programa, &vault).unwrap(),
+        (None, Some(_)) => {
+            let mut pools = vec![];
+            for (address, _) in vaults.iter() {
+                pools.extend(query::pools_by_vault(&env, &programa, address).unwrap());
+            }
+            pools
+        }
+        (None, None) => query::pools(&env, &programa).unwrap(),
+    };
+
+    println!("");
+    println!("{}", "Pools".purple().bold());
+    println!("");
+    let rows = query::pool_rows(&env, pools).unwrap();
+    query::print_pool_table(&rows);
+    println!("");
+}
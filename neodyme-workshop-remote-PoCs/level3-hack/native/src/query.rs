@@ -0,0 +1,196 @@
+use borsh::BorshDeserialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+
+use owo_colors::OwoColorize;
+
+use level3::{TipPool, Vault, TIP_POOL_LEN, VAULT_LEN};
+
+// Field offsets inside the borsh layouts of level3
+pub const VAULT_CREATOR_OFFSET: usize = 0;
+pub const POOL_WITHDRAW_AUTHORITY_OFFSET: usize = 0;
+pub const POOL_VAULT_OFFSET: usize = 32 + 8;
+
+pub fn data_size(len: u64) -> RpcFilterType {
+    RpcFilterType::DataSize(len)
+}
+
+pub fn memcmp(offset: usize, key: &Pubkey) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(offset, key.as_ref()))
+}
+
+/// getProgramAccounts with the given filters, always asking for the full data base64 encoded
+pub fn program_accounts(
+    client: &RpcClient,
+    tip_program: &Pubkey,
+    filters: Vec<RpcFilterType>,
+) -> ClientResult<Vec<(Pubkey, Account)>> {
+    client.get_program_accounts_with_config(
+        tip_program,
+        RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        },
+    )
+}
+
+fn decode<T: BorshDeserialize>(accounts: Vec<(Pubkey, Account)>) -> Vec<(Pubkey, T)> {
+    accounts
+        .into_iter()
+        .filter_map(|(key, account)| {
+            T::deserialize(&mut &account.data[..])
+                .ok()
+                .map(|state| (key, state))
+        })
+        .collect()
+}
+
+/// Every account with the `Vault` size
+pub fn vaults(client: &RpcClient, tip_program: &Pubkey) -> ClientResult<Vec<(Pubkey, Vault)>> {
+    let accounts = program_accounts(client, tip_program, vec![data_size(VAULT_LEN)])?;
+    Ok(decode(accounts))
+}
+
+/// Vaults whose `creator` field equals `creator`
+pub fn vaults_by_creator(
+    client: &RpcClient,
+    tip_program: &Pubkey,
+    creator: &Pubkey,
+) -> ClientResult<Vec<(Pubkey, Vault)>> {
+    let accounts = program_accounts(
+        client,
+        tip_program,
+        vec![
+            data_size(VAULT_LEN),
+            memcmp(VAULT_CREATOR_OFFSET, creator),
+        ],
+    )?;
+    Ok(decode(accounts))
+}
+
+/// Every account with the `TipPool` size
+pub fn pools(client: &RpcClient, tip_program: &Pubkey) -> ClientResult<Vec<(Pubkey, TipPool)>> {
+    let accounts = program_accounts(client, tip_program, vec![data_size(TIP_POOL_LEN)])?;
+    Ok(decode(accounts))
+}
+
+/// Pools whose `vault` field equals `vault`
+pub fn pools_by_vault(
+    client: &RpcClient,
+    tip_program: &Pubkey,
+    vault: &Pubkey,
+) -> ClientResult<Vec<(Pubkey, TipPool)>> {
+    let accounts = program_accounts(
+        client,
+        tip_program,
+        vec![data_size(TIP_POOL_LEN), memcmp(POOL_VAULT_OFFSET, vault)],
+    )?;
+    Ok(decode(accounts))
+}
+
+/// Pools whose `withdraw_authority` field equals `authority`
+pub fn pools_by_authority(
+    client: &RpcClient,
+    tip_program: &Pubkey,
+    authority: &Pubkey,
+) -> ClientResult<Vec<(Pubkey, TipPool)>> {
+    let accounts = program_accounts(
+        client,
+        tip_program,
+        vec![
+            data_size(TIP_POOL_LEN),
+            memcmp(POOL_WITHDRAW_AUTHORITY_OFFSET, authority),
+        ],
+    )?;
+    Ok(decode(accounts))
+}
+
+#[derive(Clone, Debug)]
+pub struct PoolRow {
+    pub address: Pubkey,
+    pub pool: TipPool,
+    // None if the vault account doesn't exist (anymore)
+    pub vault_lamports: Option<u64>,
+    // Sum of the `value` of every listed pool pointing to the same vault
+    pub vault_claims: u64,
+}
+
+/// Attach the current vault balance to every pool
+pub fn pool_rows(
+    client: &RpcClient,
+    pools: Vec<(Pubkey, TipPool)>,
+) -> ClientResult<Vec<PoolRow>> {
+    let mut vault_keys: Vec<Pubkey> = pools.iter().map(|(_, pool)| pool.vault).collect();
+    vault_keys.sort();
+    vault_keys.dedup();
+
+    let mut balances = Vec::with_capacity(vault_keys.len());
+    // getMultipleAccounts is limited to 100 keys per request
+    for chunk in vault_keys.chunks(100) {
+        let accounts = client.get_multiple_accounts(chunk)?;
+        balances.extend(
+            chunk
+                .iter()
+                .zip(accounts)
+                .map(|(key, account)| (*key, account.map(|a| a.lamports))),
+        );
+    }
+
+    let rows = pools
+        .iter()
+        .map(|(address, pool)| PoolRow {
+            address: *address,
+            pool: *pool,
+            vault_lamports: balances
+                .iter()
+                .find(|(key, _)| *key == pool.vault)
+                .and_then(|(_, lamports)| *lamports),
+            vault_claims: pools
+                .iter()
+                .filter(|(_, other)| other.vault == pool.vault)
+                .fold(0u64, |acc, (_, other)| acc.saturating_add(other.value)),
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+pub fn print_pool_table(rows: &[PoolRow]) {
+    let header = format!(
+        "{:<44} {:<44} {:<44} {:>20} {:>20}  {}",
+        "Pool", "Vault", "Withdraw authority", "Value", "Vault lamports", "Status"
+    );
+    println!("{}", header.bold());
+    for row in rows {
+        let lamports = match row.vault_lamports {
+            Some(lamports) => lamports.to_string(),
+            None => String::from("-"),
+        };
+        let status = match row.vault_lamports {
+            None => "VAULT MISSING".red().to_string(),
+            Some(lamports) if row.pool.value > lamports => "VALUE > VAULT".red().to_string(),
+            Some(lamports) if row.vault_claims > lamports => "VAULT SHARED".yellow().to_string(),
+            Some(_) => "OK".green().to_string(),
+        };
+        println!(
+            "{:<44} {:<44} {:<44} {:>20} {:>20}  {}",
+            row.address.to_string(),
+            row.pool.vault.to_string(),
+            row.pool.withdraw_authority.to_string(),
+            row.pool.value,
+            lamports,
+            status
+        );
+    }
+}
@@ -1,16 +1,17 @@
 use owo_colors::OwoColorize;
 
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
 use poc_framework::{
     Environment,
-    localhost_client,
-    keypair, RemoteEnvironment,
     solana_program::{
         pubkey::Pubkey,
         instruction::{AccountMeta, Instruction},
     },
     solana_sdk::{
         system_program,
-        signature::{read_keypair_file, Signer},
+        signature::{read_keypair_file, Keypair, Signer},
     },
 };
 
@@ -51,19 +52,22 @@ pub const VAULT_LEN: u64 = 32 + 8 + 32 + 1;
 pub fn main() {
     let programa_keypair = read_keypair_file("./target/so/level3-keypair.json").unwrap();
     let programa = programa_keypair.pubkey();
-    let cliente1 = localhost_client();
+    let cliente1 = dryrun::client();
     
-    let hacker = keypair(1);
-    let withdraw_authority = keypair(2);
-    let pool_info = keypair(3);
-    let authority_info = keypair(4);
-    let tip_guy = keypair(5);
+    let mut actors = Actors::new();
+    let hacker = actors.get("hacker");
+    let withdraw_authority = actors.get("withdraw_authority");
+    let pool_info = Keypair::new();
+    actors.label(pool_info.pubkey(), "level3_pool");
+    let authority_info = actors.get("admin");
+    let tip_guy = actors.get("tipper");
 
     /* Create the PDA , if InvalidSeeds, change the value */
     let seed:u8 = 3;
     let vault_info = Pubkey::create_program_address(&[&[seed]], &programa).unwrap();
  
-    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, keypair(4), 10000000000);
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("admin"), 10000000000);
+    println!("{}", actors.legend().blue());
             env.execute_as_transaction(
                 &[Instruction {
                     program_id: programa,
@@ -0,0 +1,2 @@
+#!/bin/bash
+RUST_BACKTRACE=1 cargo run --manifest-path=./native/Cargo.toml --target-dir=./target/ --bin pools -- "$@"
@@ -9,5 +9,13 @@ solana program deploy ./target/so/level4.so
 solana program deploy ./target/so/myspl.so
 RUST_BACKTRACE=1 cargo run --bin tok
 #RUST_BACKTRACE=1 cargo run --bin poc
+#RUST_BACKTRACE=1 cargo run --bin spoofmint
 #IF error, replace the program ID in the poc.rs with the correct
 #solana program show --programs
+#To build level4 against spl-token 3.1.1 (program id check in transfer_checked):
+#cargo build-bpf --manifest-path=./program/Cargo.toml --bpf-out-dir=./target/so --no-default-features --features vendored-spl-token-3-1-1
+#To build level4 with the token program allowlist:
+#cargo build-bpf --manifest-path=./program/Cargo.toml --bpf-out-dir=./target/so --features patched
+#cargo test -p level4 --test spl_token_mitigation
+#CARGO_TARGET_DIR=target/spl-token-3.1.1 cargo test -p level4 --test spl_token_mitigation --no-default-features --features vendored-spl-token-3-1-1
+#CARGO_TARGET_DIR=target/patched cargo test -p level4 --test spl_token_mitigation --features patched
@@ -0,0 +1,368 @@
+/*
+Deposit and Withdraw used to read the decimals with `mint.data.borrow()[44]` on an unchecked account.
+Byte 44 of a real Mint is `decimals` (COption<Pubkey> mint_authority = 36 bytes, supply = 8 bytes),
+but nothing stopped us from passing an account we own with whatever we want at that offset:
+  - a copy of the real mint with a crafted byte 44 -> the program forwarded our decimals to transfer_checked
+  - an account shorter than 45 bytes             -> the program panicked (index out of bounds)
+Now the mint is unpacked as spl_token::state::Mint (before the wallet) and must be owned by the token
+program, so the copy is rejected for its owner and the short account, owned by the token program, for
+its size. Each deposit goes to the wallet derived from (owner, mint) for that mint, like level4::deposit.
+The old program is not deployed anymore, so what it did is only simulated afterwards: the transfer_checked
+it built for the copy is sent to the token program directly, and its byte 44 read on the short account.
+*/
+use solana_client::rpc_client::RpcClient;
+
+use solana_program::{
+    bpf_loader,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::rent::id as rent_id,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    native_token::LAMPORTS_PER_SOL,
+    signature::{read_keypair_file, Keypair, Signature},
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+};
+
+use spl_token::state::{Account, Mint};
+
+use actors::Actors;
+use borsh::{BorshDeserialize, BorshSerialize};
+use owo_colors::OwoColorize;
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum WalletInstruction {
+    Initialize,
+    Deposit { amount: u64 },
+    Withdraw { amount: u64 },
+}
+
+pub fn get_wallet_address(owner: &Pubkey, mint: &Pubkey, wallet_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&owner.to_bytes(), &mint.to_bytes()], wallet_program)
+}
+
+pub fn get_authority(wallet_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[], wallet_program)
+}
+
+// Offset of `decimals` inside spl_token::state::Mint
+const DECIMALS_OFFSET: usize = 36 + 8;
+
+fn send(env: &RpcClient, instructions: &[Instruction], payer: &Keypair, signers: &[&Keypair]) {
+    let recent_blockhash = env.get_latest_blockhash().unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        signers,
+        recent_blockhash,
+    );
+    dryrun::send(env, &tx);
+}
+
+fn deposit_with_mint(
+    env: &RpcClient,
+    programa: Pubkey,
+    source: Pubkey,
+    payer: &Keypair,
+    mint: Pubkey,
+    label: &str,
+) -> Result<Signature, String> {
+    let (wallet_pda, _) = get_wallet_address(&payer.pubkey(), &mint, &programa);
+    let tx_deposit = Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(wallet_pda, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: WalletInstruction::Deposit { amount: 10000 }
+            .try_to_vec()
+            .unwrap(),
+    };
+
+    let recent_blockhash = env.get_latest_blockhash().unwrap();
+    let tx_deposit = Transaction::new_signed_with_payer(
+        &[tx_deposit],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    println!();
+    println!(
+        "{} {}",
+        "Depositing with".purple().bold(),
+        label.purple().bold()
+    );
+    let result = dryrun::try_send(env, &tx_deposit);
+    match &result {
+        Ok(sig) => println!("{} {}", "Accepted:".green().bold(), sig),
+        Err(error) => println!("{} {}", "Rejected:".red().bold(), error),
+    };
+    result
+}
+
+fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level4-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+
+    let cliente1 = dryrun::url();
+
+    let mut actors = Actors::new();
+    let payer = actors.get("payer");
+    let mint_account = Keypair::new();
+    let source_account = Keypair::new();
+
+    let env = RpcClient::new_with_commitment(cliente1, CommitmentConfig::confirmed());
+    println!("{}", actors.legend().blue());
+
+    dryrun::airdrop(&env, &payer.pubkey(), LAMPORTS_PER_SOL * 100);
+
+    let (wallet_pda, _) = get_wallet_address(&payer.pubkey(), &mint_account.pubkey(), &programa);
+    let (program_pda, _) = get_authority(&programa);
+
+    println!("Creating mint, source token account and Wallet PDA");
+    let mint_rent = env
+        .get_minimum_balance_for_rent_exemption(Mint::LEN)
+        .unwrap();
+    let account_rent = env
+        .get_minimum_balance_for_rent_exemption(Account::LEN)
+        .unwrap();
+
+    send(
+        &env,
+        &[
+            solana_program::system_instruction::create_account(
+                &payer.pubkey(),
+                &mint_account.pubkey(),
+                mint_rent,
+                Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint_account.pubkey(),
+                &payer.pubkey(),
+                None,
+                9,
+            )
+            .unwrap(),
+            solana_program::system_instruction::create_account(
+                &payer.pubkey(),
+                &source_account.pubkey(),
+                account_rent,
+                Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &source_account.pubkey(),
+                &mint_account.pubkey(),
+                &payer.pubkey(),
+            )
+            .unwrap(),
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint_account.pubkey(),
+                &source_account.pubkey(),
+                &payer.pubkey(),
+                &[],
+                LAMPORTS_PER_SOL,
+            )
+            .unwrap(),
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(wallet_pda, false),
+                    AccountMeta::new_readonly(program_pda, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new(mint_account.pubkey(), false),
+                    AccountMeta::new_readonly(rent_id(), false),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: WalletInstruction::Initialize.try_to_vec().unwrap(),
+            },
+        ],
+        &payer,
+        &[&payer, &mint_account, &source_account],
+    );
+
+    let real_mint_data = dryrun::get_account(&env, &mint_account.pubkey())
+        .unwrap()
+        .data;
+    println!(
+        "{} {:?} {} {:?}",
+        "Real mint byte 44:".blue().bold(),
+        real_mint_data[DECIMALS_OFFSET],
+        "decimals:".blue().bold(),
+        Mint::unpack(&real_mint_data).unwrap().decimals
+    );
+
+    /*
+    Same bytes as the real mint but decimals = 0, owned by the bpf loader so we can write the data
+    (same trick as level0 native)
+    */
+    let mut spoof_data = real_mint_data.clone();
+    spoof_data[DECIMALS_OFFSET] = 0;
+
+    let spoof_mint = Keypair::new();
+    let short_spoof = Keypair::new();
+    let spoof_rent = env
+        .get_minimum_balance_for_rent_exemption(spoof_data.len())
+        .unwrap();
+    let short_rent = env.get_minimum_balance_for_rent_exemption(1).unwrap();
+
+    send(
+        &env,
+        &[
+            solana_program::system_instruction::create_account(
+                &payer.pubkey(),
+                &spoof_mint.pubkey(),
+                spoof_rent,
+                spoof_data.len() as u64,
+                &bpf_loader::ID,
+            ),
+            solana_program::loader_instruction::write(
+                &spoof_mint.pubkey(),
+                &bpf_loader::ID,
+                0,
+                spoof_data,
+            ),
+            // Anyone can create an account owned by the token program, it just stays zeroed
+            solana_program::system_instruction::create_account(
+                &payer.pubkey(),
+                &short_spoof.pubkey(),
+                short_rent,
+                1,
+                &spl_token::id(),
+            ),
+        ],
+        &payer,
+        &[&payer, &spoof_mint, &short_spoof],
+    );
+
+    let spoof_account = dryrun::get_account(&env, &spoof_mint.pubkey()).unwrap();
+    println!(
+        "{} {} {} {:?} {} {}",
+        "Spoofed mint:".yellow().bold(),
+        spoof_mint.pubkey(),
+        "byte 44:".yellow().bold(),
+        spoof_account.data[DECIMALS_OFFSET],
+        "owner:".yellow().bold(),
+        spoof_account.owner
+    );
+    let short_account = dryrun::get_account(&env, &short_spoof.pubkey()).unwrap();
+    println!(
+        "{} {} {} {:?} {} {}",
+        "Short mint:".yellow().bold(),
+        short_spoof.pubkey(),
+        "bytes:".yellow().bold(),
+        short_account.data.len(),
+        "owner:".yellow().bold(),
+        short_account.owner
+    );
+
+    deposit_with_mint(
+        &env,
+        programa,
+        source_account.pubkey(),
+        &payer,
+        mint_account.pubkey(),
+        "the real mint",
+    )
+    .expect("the real mint must be accepted");
+    // rejected by the owner check of token::unpack_mint
+    deposit_with_mint(
+        &env,
+        programa,
+        source_account.pubkey(),
+        &payer,
+        spoof_mint.pubkey(),
+        "a copy of the mint with byte 44 = 0",
+    )
+    .expect_err("the copy must be rejected");
+    // owned by the token program, so rejected by Mint::unpack for its size
+    deposit_with_mint(
+        &env,
+        programa,
+        source_account.pubkey(),
+        &payer,
+        short_spoof.pubkey(),
+        "a 1 byte account as mint",
+    )
+    .expect_err("the short account must be rejected");
+
+    println!();
+    println!(
+        "{}",
+        "What the old deposit did with them, simulated"
+            .purple()
+            .bold()
+    );
+    /*
+    Not the old program: the CPI it built for the copy (its key in the mint slot and its byte 44 as
+    decimals), sent directly, so only the checks of the token program itself are left
+    */
+    let old_cpi = spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        &source_account.pubkey(),
+        &spoof_mint.pubkey(),
+        &wallet_pda,
+        &payer.pubkey(),
+        &[],
+        10000,
+        spoof_account.data[DECIMALS_OFFSET],
+    )
+    .unwrap();
+    let recent_blockhash = env.get_latest_blockhash().unwrap();
+    let old_cpi = Transaction::new_signed_with_payer(
+        &[old_cpi],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    match dryrun::try_send(&env, &old_cpi) {
+        Ok(sig) => println!(
+            "{} {} {}",
+            "copy:".yellow().bold(),
+            "direct transfer_checked went through".red().bold(),
+            sig
+        ),
+        Err(error) => println!(
+            "{} {} {}",
+            "copy:".yellow().bold(),
+            "direct transfer_checked rejected by the token program:"
+                .green()
+                .bold(),
+            error
+        ),
+    };
+    // `mint.data.borrow()[44]` on 1 byte, done here
+    match short_account.data.get(DECIMALS_OFFSET) {
+            Some(decimals) => println!("{} {} {:?}", "short:".yellow().bold(), "byte 44".red().bold(), decimals),
+            None => println!("{} {}", "short:".yellow().bold(),
+                format!("index out of bounds: the len is {} but the index is {}, the old program would panic",
+                short_account.data.len(), DECIMALS_OFFSET).green().bold()),
+        };
+
+    let wallet_amount = Account::unpack(&dryrun::get_account(&env, &wallet_pda).unwrap().data)
+        .unwrap()
+        .amount;
+    assert_eq!(wallet_amount, 10000);
+    println!();
+    println!(
+        "{} {:?}",
+        "Wallet amount (only the real mint deposit counts):"
+            .blue()
+            .bold(),
+        wallet_amount.blue()
+    );
+    println!();
+}
@@ -20,12 +20,17 @@ use solana_sdk::{
 use spl_token::{
         state::{Account, Mint}, instruction::initialize_account,
     };
+use spl_token_2022::{
+        extension::{transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType, StateWithExtensions},
+        state::{Account as Account2022, Mint as Mint2022},
+    };
 use spl_associated_token_account:: {
     instruction::create_associated_token_account,
     get_associated_token_address,
 };
 
 use owo_colors::OwoColorize;
+use actors::Actors;
 use borsh::{BorshDeserialize, BorshSerialize};
 
 #[derive(Debug, BorshDeserialize, BorshSerialize)]
@@ -35,14 +40,152 @@ use borsh::{BorshDeserialize, BorshSerialize};
         Withdraw { amount: u64 },
     }
 
-    pub fn get_wallet_address(owner: &Pubkey, wallet_program: &Pubkey) -> (Pubkey, u8) {
-        Pubkey::find_program_address(&[&owner.to_bytes()], wallet_program)
+    pub fn get_wallet_address(owner: &Pubkey, mint: &Pubkey, wallet_program: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[&owner.to_bytes(), &mint.to_bytes()], wallet_program)
     }
 
     pub fn get_authority(wallet_program: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&[], wallet_program)
     }
 
+fn token_2022_amount(env: &RpcClient, account: &Pubkey) -> u64 {
+    let data = dryrun::get_account(env, account).unwrap().data;
+    StateWithExtensions::<Account2022>::unpack(&data).unwrap().base.amount
+}
+
+/*
+Same wallet program, but with a Token-2022 mint that charges a 1% transfer fee.
+The wallet PDA is sized for the account extensions the mint requires (TransferFeeAmount)
+and deposit / withdraw use transfer_checked_with_fee, so the fee is explicit.
+*/
+fn token_2022_round_trip(env: &RpcClient, programa: Pubkey, payer: &Keypair) {
+        println!("");
+        println!("{}", "Token-2022 mint with a transfer fee".purple().bold());
+
+        let mint_2022 = Keypair::new();
+        let source_2022 = Keypair::new();
+        let (wallet_2022, _) = get_wallet_address(&payer.pubkey(), &mint_2022.pubkey(), &programa);
+        let (program_pda, _) = get_authority(&programa);
+
+        let mint_len = ExtensionType::get_account_len::<Mint2022>(&[ExtensionType::TransferFeeConfig]);
+        let account_len = ExtensionType::get_account_len::<Account2022>(&[ExtensionType::TransferFeeAmount]);
+
+        let instructions = vec! [
+            solana_program::system_instruction::create_account(
+            &payer.pubkey(),
+            &mint_2022.pubkey(),
+            env.get_minimum_balance_for_rent_exemption(mint_len).unwrap(),
+            mint_len as u64,
+            &spl_token_2022::id(),
+        ),
+        initialize_transfer_fee_config(
+            &spl_token_2022::id(),
+            &mint_2022.pubkey(),
+            Some(&payer.pubkey()),
+            Some(&payer.pubkey()),
+            100, // 1%
+            u64::MAX,
+        )
+        .unwrap(),
+        spl_token_2022::instruction::initialize_mint(
+            &spl_token_2022::id(),
+            &mint_2022.pubkey(),
+            &payer.pubkey(),
+            None,
+            6,
+        )
+        .unwrap(),
+            solana_program::system_instruction::create_account(
+            &payer.pubkey(),
+            &source_2022.pubkey(),
+            env.get_minimum_balance_for_rent_exemption(account_len).unwrap(),
+            account_len as u64,
+            &spl_token_2022::id(),
+        ),
+        spl_token_2022::instruction::initialize_account(
+            &spl_token_2022::id(),
+            &source_2022.pubkey(),
+            &mint_2022.pubkey(),
+            &payer.pubkey(),
+        )
+        .unwrap(),
+        spl_token_2022::instruction::mint_to(
+            &spl_token_2022::id(),
+            &mint_2022.pubkey(),
+            &source_2022.pubkey(),
+            &payer.pubkey(),
+            &[],
+            1_000_000,
+        )
+        .unwrap(),
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(wallet_2022, false),
+                    AccountMeta::new_readonly(program_pda, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new(mint_2022.pubkey(), false),
+                    AccountMeta::new_readonly(rent_id(), false),
+                    AccountMeta::new_readonly(spl_token_2022::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: WalletInstruction::Initialize.try_to_vec().unwrap(),
+            },
+        ];
+
+        let recent_blockhash = env.get_latest_blockhash().unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer, &mint_2022, &source_2022],
+            recent_blockhash,
+        );
+
+        dryrun::send(env, &tx);
+
+        println!("Depositing 10000 (100 withheld as fee)");
+        let tx_deposit = Instruction {
+            program_id: programa,
+            accounts: vec![
+                AccountMeta::new(wallet_2022, false),
+                AccountMeta::new(source_2022.pubkey(), false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+                AccountMeta::new_readonly(mint_2022.pubkey(), false),
+                AccountMeta::new_readonly(spl_token_2022::id(), false),
+            ],
+            data: WalletInstruction::Deposit { amount: 10000 }.try_to_vec().unwrap(),
+        };
+
+        println!("Withdrawing 5000 (50 withheld as fee)");
+        let tx_withdraw = Instruction {
+            program_id: programa,
+            accounts: vec![
+                AccountMeta::new(wallet_2022, false),
+                AccountMeta::new_readonly(program_pda, false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+                AccountMeta::new(source_2022.pubkey(), false),
+                AccountMeta::new_readonly(mint_2022.pubkey(), false),
+                AccountMeta::new_readonly(spl_token_2022::id(), false),
+            ],
+            data: WalletInstruction::Withdraw { amount: 5000 }.try_to_vec().unwrap(),
+        };
+
+        let recent_blockhash = env.get_latest_blockhash().unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[tx_deposit, tx_withdraw],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        );
+
+        dryrun::send(env, &tx);
+
+        println!("{} {:?}", "Token-2022 wallet amount:".blue().bold(), token_2022_amount(env, &wallet_2022));
+        println!("{} {:?}", "Token-2022 source amount:".blue().bold(), token_2022_amount(env, &source_2022.pubkey()));
+}
+
 
 fn main() {
 
@@ -50,29 +193,22 @@ fn main() {
         let programa_keypair = read_keypair_file("./target/so/level4-keypair.json").unwrap();
         let programa = programa_keypair.pubkey();
 
-        let cliente1 = String::from("http://localhost:8899");
+        let cliente1 = dryrun::url();
         
-        let payer = Keypair::new();
+        let mut actors = Actors::new();
+        let payer = actors.get("payer");
+        let hacker = actors.get("hacker");
         let mint_account = Keypair::new();
 
         println!("Creating the RpcClient and airdropping");
         
     
         let env = RpcClient::new_with_commitment(cliente1, CommitmentConfig::confirmed());
+        println!("{}", actors.legend().blue());
 
-        match env.request_airdrop(&payer.pubkey(), LAMPORTS_PER_SOL * 100) {
-            Ok(sig) => loop {
-                if let Ok(confirmed) = env.confirm_transaction(&sig) {
-                    if confirmed {
-                        println!("Transaction: {} Status: {}", sig, confirmed);
-                        break;
-                    }
-                }
-            },
-            Err(_) => println!("Error requesting airdrop"),
-        };
+        dryrun::airdrop(&env, &payer.pubkey(), LAMPORTS_PER_SOL * 100);
 
-        let (wallet_pda, _) = get_wallet_address(&payer.pubkey(), &programa);
+        let (wallet_pda, _) = get_wallet_address(&payer.pubkey(), &mint_account.pubkey(), &programa);
         let (program_pda, _) = get_authority(&programa);
 
         let rent_exemption_amount :u64 = env.get_minimum_balance_for_rent_exemption(Mint::LEN).unwrap();
@@ -108,7 +244,7 @@ fn main() {
             recent_blockhash,
         );
     
-        env.send_and_confirm_transaction(&tx).unwrap();   
+        dryrun::send(&env, &tx);   
         
         println!("Initializing Wallet PDA");
 
@@ -136,7 +272,57 @@ fn main() {
             recent_blockhash,
         );
             
-        env.send_and_confirm_transaction(&tx_init).unwrap();
+        dryrun::send(&env, &tx_init);
+
+        // Wallets are derived from (owner, mint), so the same owner can hold a second token type
+        println!("Initializing a second Wallet PDA for another mint");
+        let second_mint = Keypair::new();
+        let (second_wallet_pda, _) = get_wallet_address(&payer.pubkey(), &second_mint.pubkey(), &programa);
+
+        let instructions  = vec! [
+            solana_program::system_instruction::create_account(
+            &payer.pubkey(),
+            &second_mint.pubkey(),
+            rent_exemption_amount,
+            Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            &second_mint.pubkey(),
+            &payer.pubkey(),
+            None,
+            6,
+        )
+        .unwrap(),
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(second_wallet_pda, false),
+                    AccountMeta::new_readonly(program_pda, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new(second_mint.pubkey(), false),
+                    AccountMeta::new_readonly(rent_id(), false),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: WalletInstruction::Initialize.try_to_vec().unwrap(),
+            },
+        ];
+
+        let recent_blockhash = env.get_latest_blockhash().unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &second_mint],
+            recent_blockhash,
+        );
+
+        dryrun::send(&env, &tx);
+
+        println!("{} {} {} {}", "Wallet for mint".green(), mint_account.pubkey(), "->".green(), wallet_pda);
+        println!("{} {} {} {}", "Wallet for mint".green(), second_mint.pubkey(), "->".green(), second_wallet_pda);
 
 
         // Creating ATA owner and ATA wallet
@@ -173,10 +359,10 @@ fn main() {
             recent_blockhash,
         );
 
-        env.send_and_confirm_transaction(&ata_tx).unwrap();
+        dryrun::send(&env, &ata_tx);
 
 
-        env.request_airdrop(&ata_account.pubkey(), LAMPORTS_PER_SOL * 3);
+        dryrun::airdrop(&env, &ata_account.pubkey(), LAMPORTS_PER_SOL * 3);
         let ata_token = get_associated_token_address(
             &ata_account.pubkey(),
             &mint_account.pubkey(),
@@ -201,9 +387,9 @@ fn main() {
             recent_blockhash,
         );
     
-        env.send_and_confirm_transaction(&mint_to_tx).unwrap();
+        dryrun::send(&env, &mint_to_tx);
 
-        let ata_tok_data = env.get_account(&ata_token).unwrap().data;
+        let ata_tok_data = dryrun::get_account(&env, &ata_token).unwrap().data;
 
         println!("Depositing 10000");
 
@@ -229,28 +415,16 @@ fn main() {
             recent_blockhash,
         );
             
-        env.send_and_confirm_transaction(&tx_create).unwrap();
+        dryrun::send(&env, &tx_create);
 
-        let prog_pda_data = env.get_account(&wallet_pda).unwrap().data;
+        let prog_pda_data = dryrun::get_account(&env, &wallet_pda).unwrap().data;
         let wall_amount_orig = Account::unpack(&prog_pda_data).unwrap().amount;
     
-        let hacker = Keypair::new();
-
-        match env.request_airdrop(&hacker.pubkey(), LAMPORTS_PER_SOL * 100) {
-            Ok(sig) => loop {
-                if let Ok(confirmed) = env.confirm_transaction(&sig) {
-                    if confirmed {
-                        println!("Transaction: {} Status: {}", sig, confirmed);
-                        break;
-                    }
-                }
-            },
-            Err(_) => println!("Error requesting airdrop"),
-        };
+        dryrun::airdrop(&env, &hacker.pubkey(), LAMPORTS_PER_SOL * 100);
 
-        let (hack_wallet, _) = get_wallet_address(&hacker.pubkey(), &programa);
+        let (hack_wallet, _) = get_wallet_address(&hacker.pubkey(), &mint_account.pubkey(), &programa);
 
-        println!("Stealing lamports");
+        println!("Trying the myspl substitution");
 
         let hack_init = Instruction {
             program_id: programa,
@@ -276,9 +450,9 @@ fn main() {
             recent_blockhash,
         );
     
-        env.send_and_confirm_transaction(&hack_init).unwrap();
+        dryrun::send(&env, &hack_init);
 
-        let hack_amount_1 = env.get_account(&hack_wallet).unwrap().data;
+        let hack_amount_1 = dryrun::get_account(&env, &hack_wallet).unwrap().data;
         let hack_amount_orig= Account::unpack(&hack_amount_1).unwrap().amount;
 
         //Deploy the malicious contract
@@ -310,16 +484,24 @@ fn main() {
             recent_blockhash,
         );
         
-        env.send_and_confirm_transaction(&hack).unwrap();
-
-        let prog_pda_data_2 = env.get_account(&wallet_pda).unwrap().data;
+        // The workshop exploit: the real token program travels in the mint slot so myspl can CPI into it,
+        // a callee can only invoke a program that is one of its own accounts. Wallets are derived from
+        // (owner, mint) now, hack_wallet was derived from the real mint and a token program in the mint
+        // slot isn't a mint either, so withdraw rejects it before any token program is called, with or
+        // without `patched`. With a mint in that slot, the accounts myspl gets (wallet, mint, destination,
+        // authority) leave no room for both spl-token and the victim wallet
+        let error = dryrun::try_send(&env, &hack).expect_err("the myspl substitution must be rejected");
+        println!("{} {}", "Withdraw rejected:".green().bold(), error);
+
+        let prog_pda_data_2 = dryrun::get_account(&env, &wallet_pda).unwrap().data;
         let wall_amount_final= Account::unpack(&prog_pda_data_2).unwrap().amount;
-        let hack_amount_1 = env.get_account(&hack_wallet).unwrap().data;
+        let hack_amount_1 = dryrun::get_account(&env, &hack_wallet).unwrap().data;
         let hack_amount_final= Account::unpack(&hack_amount_1).unwrap().amount;
 
-        if wall_amount_orig > wall_amount_final && hack_amount_final > hack_amount_orig {
-            println!("{} {:?}", "[*Hax*] stealed amount:".blue().bold(), hack_amount_final.blue().on_yellow()); } 
-            else { println!("Something went wroing! :(") }
-    
+        assert_eq!(wall_amount_final, wall_amount_orig);
+        assert_eq!(hack_amount_final, hack_amount_orig);
+        println!("{} {:?}", "[*Rejected*] victim wallet amount:".blue().bold(), wall_amount_final.blue());
+
+        token_2022_round_trip(&env, programa, &payer);
 }
 
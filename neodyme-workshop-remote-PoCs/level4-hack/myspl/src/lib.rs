@@ -3,44 +3,10 @@ Exactly the same code from https://workshop.neodyme.io/level4-solution.html
 You can try to create another kind of approach :)
  */
 
-use solana_program::{
-    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, program::invoke,
-    pubkey::Pubkey,
-};
+// In its own file so the level4 tests can build it against the spl-token they pick
+pub mod processor;
 
-entrypoint!(process_instruction);
+pub use processor::process_instruction;
 
-pub fn process_instruction(
-    _program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> ProgramResult {
-    match spl_token::instruction::TokenInstruction::unpack(instruction_data).unwrap() {
-        spl_token::instruction::TokenInstruction::TransferChecked { amount, .. } => {
-            let source = &accounts[0];
-            let mint = &accounts[1];
-            let destination = &accounts[2];
-            let authority = &accounts[3];
-            invoke(
-                &spl_token::instruction::transfer(
-                    mint.key,
-                    destination.key,
-                    source.key,
-                    authority.key,
-                    &[],
-                    amount,
-                )
-                .unwrap(),
-                &[
-                    source.clone(),
-                    mint.clone(),
-                    destination.clone(),
-                    authority.clone(),
-                ],
-            )
-        }
-        _ => {
-            panic!("wrong ix")
-        }
-    }
-}
\ No newline at end of file
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
@@ -1,20 +1,27 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
-    entrypoint,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program, sysvar,
 };
 
 // There's a mitigation for this bug in spl-token 3.1.1
-// vendored_spl_token is an exact copy of spl-token 3.1.0, which doesn't have the mitigation yet
-//use vendored_spl_token as spl_token;
-use spl_token;
+// vendored_spl_token_3_1_0 is an exact copy of spl-token 3.1.0, which doesn't have the mitigation yet
+#[cfg(all(
+    feature = "vendored-spl-token-3-1-0",
+    feature = "vendored-spl-token-3-1-1"
+))]
+compile_error!("enable only one of vendored-spl-token-3-1-0 and vendored-spl-token-3-1-1");
+#[cfg(feature = "vendored-spl-token-3-1-0")]
+pub use vendored_spl_token_3_1_0 as spl_token;
+#[cfg(feature = "vendored-spl-token-3-1-1")]
+pub use vendored_spl_token_3_1_1 as spl_token;
 
 #[derive(Debug, BorshDeserialize, BorshSerialize)]
 
 pub enum WalletInstruction {
-    /// Initialize a Personal Savings Wallet
+    /// Initialize a Personal Savings Wallet for one mint,
+    /// an owner can hold one wallet per mint
     ///
     /// Passed accounts:
     ///
@@ -23,7 +30,7 @@ pub enum WalletInstruction {
     /// (3) Owner
     /// (4) Mint
     /// (5) Rent sysvar
-    /// (6) SPL-Token program
+    /// (6) Token program (spl-token or Token-2022)
     /// (7) System program
     Initialize,
     /// Deposit
@@ -34,7 +41,7 @@ pub enum WalletInstruction {
     /// (2) Money Source
     /// (3) Source Authority
     /// (4) Mint
-    /// (5) SPL-Token program
+    /// (5) Token program (spl-token or Token-2022)
     Deposit { amount: u64 },
     /// Withdraw from Wallet
     ///
@@ -45,24 +52,32 @@ pub enum WalletInstruction {
     /// (3) Owner
     /// (4) Destination
     /// (5) Mint
-    /// (6) SPL-Token program
+    /// (6) Token program (spl-token or Token-2022)
     Withdraw { amount: u64 },
 }
 
 pub mod processor;
+pub mod token;
+#[cfg(not(feature = "no-entrypoint"))]
 use processor::process_instruction;
-entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
 
-pub fn get_wallet_address(owner: &Pubkey, wallet_program: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[&owner.to_bytes()], wallet_program)
+pub fn get_wallet_address(owner: &Pubkey, mint: &Pubkey, wallet_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&owner.to_bytes(), &mint.to_bytes()], wallet_program)
 }
 
 pub fn get_authority(wallet_program: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[], wallet_program)
 }
 
-pub fn initialize(wallet_program: Pubkey, owner_address: Pubkey, mint: Pubkey) -> Instruction {
-    let wallet_address = get_wallet_address(&owner_address, &wallet_program).0;
+pub fn initialize(
+    wallet_program: Pubkey,
+    owner_address: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
+    let wallet_address = get_wallet_address(&owner_address, &mint, &wallet_program).0;
     let authority_address = get_authority(&wallet_program).0;
     Instruction {
         program_id: wallet_program,
@@ -72,7 +87,7 @@ pub fn initialize(wallet_program: Pubkey, owner_address: Pubkey, mint: Pubkey) -
             AccountMeta::new(owner_address, true),
             AccountMeta::new(mint, false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_program, false),
             AccountMeta::new_readonly(system_program::id(), false),
         ],
         data: WalletInstruction::Initialize.try_to_vec().unwrap(),
@@ -85,9 +100,10 @@ pub fn deposit(
     source: Pubkey,
     source_authority: Pubkey,
     mint: Pubkey,
+    token_program: Pubkey,
     amount: u64,
 ) -> Instruction {
-    let wallet_address = get_wallet_address(&owner_address, &wallet_program).0;
+    let wallet_address = get_wallet_address(&owner_address, &mint, &wallet_program).0;
     Instruction {
         program_id: wallet_program,
         accounts: vec![
@@ -95,7 +111,7 @@ pub fn deposit(
             AccountMeta::new(source, false),
             AccountMeta::new_readonly(source_authority, true),
             AccountMeta::new_readonly(mint, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_program, false),
         ],
         data: WalletInstruction::Deposit { amount }.try_to_vec().unwrap(),
     }
@@ -106,9 +122,10 @@ pub fn withdraw(
     owner_address: Pubkey,
     destination: Pubkey,
     mint: Pubkey,
+    token_program: Pubkey,
     amount: u64,
 ) -> Instruction {
-    let wallet_address = get_wallet_address(&owner_address, &wallet_program).0;
+    let wallet_address = get_wallet_address(&owner_address, &mint, &wallet_program).0;
     let authority_address = get_authority(&wallet_program).0;
     Instruction {
         program_id: wallet_program,
@@ -118,7 +135,7 @@ pub fn withdraw(
             AccountMeta::new_readonly(owner_address, true),
             AccountMeta::new(destination, false),
             AccountMeta::new_readonly(mint, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_program, false),
         ],
         data: WalletInstruction::Withdraw { amount }.try_to_vec().unwrap(),
     }
@@ -4,19 +4,13 @@ use solana_program::{
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
-    program_pack::Pack,
     pubkey::Pubkey,
     rent::Rent,
     system_instruction,
     sysvar::Sysvar,
 };
 
-use crate::{get_authority, get_wallet_address, WalletInstruction};
-
-// There's a mitigation for this bug in spl-token 3.1.1
-// vendored_spl_token is an exact copy of spl-token 3.1.0, which doesn't have the mitigation yet
-//use vendored_spl_token as spl_token
-use spl_token;
+use crate::{get_authority, get_wallet_address, token, WalletInstruction};
 
 pub fn process_instruction(
     program_id: &Pubkey,
@@ -40,13 +34,18 @@ fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let rent_info = next_account_info(account_info_iter)?;
     let spl_token = next_account_info(account_info_iter)?;
 
-    let (wallet_address, wallet_seed) = get_wallet_address(owner.key, program_id);
+    let (wallet_address, wallet_seed) = get_wallet_address(owner.key, mint.key, program_id);
     let (authority_address, _) = get_authority(program_id);
     let rent = Rent::from_account_info(rent_info)?;
 
     assert_eq!(wallet_info.key, &wallet_address);
     assert_eq!(authority_info.key, &authority_address);
     assert!(owner.is_signer, "owner must sign!");
+    #[cfg(feature = "patched")]
+    token::check_token_program(spl_token)?;
+
+    let (_, mint_extensions, _) = token::unpack_mint(mint, spl_token.key)?;
+    let wallet_len = token::wallet_len(&mint_extensions);
 
     msg!("WALL: {:?}", wallet_info);
     msg!("WALL: {:?}", authority_info);
@@ -56,22 +55,16 @@ fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         &system_instruction::create_account(
             &owner.key,
             &wallet_address,
-            rent.minimum_balance(spl_token::state::Account::LEN),
-            spl_token::state::Account::LEN as u64,
+            rent.minimum_balance(wallet_len),
+            wallet_len as u64,
             &spl_token.key,
         ),
         &[owner.clone(), wallet_info.clone()],
-        &[&[&owner.key.to_bytes(), &[wallet_seed]]],
+        &[&[&owner.key.to_bytes(), &mint.key.to_bytes(), &[wallet_seed]]],
     )?;
 
     invoke(
-        &spl_token::instruction::initialize_account(
-            &spl_token.key,
-            &wallet_address,
-            mint.key,
-            &authority_address,
-        )
-        .unwrap(),
+        &token::initialize_account(&spl_token.key, &wallet_address, mint.key, &authority_address)?,
         &[
             authority_info.clone(),
             wallet_info.clone(),
@@ -92,20 +85,29 @@ fn deposit(_program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> Progr
     let mint = next_account_info(account_info_iter)?;
     let spl_token = next_account_info(account_info_iter)?;
 
-    let decimals = mint.data.borrow()[44];
+    #[cfg(feature = "patched")]
+    token::check_token_program(spl_token)?;
+    #[cfg(feature = "patched")]
+    token::check_wallet_owner(wallet_info, spl_token.key)?;
+    // The mint first, so a spoofed mint is reported as such and not as a wallet of another mint
+    let (mint_state, _, transfer_fee_config) = token::unpack_mint(mint, spl_token.key)?;
+    token::unpack_wallet(wallet_info, mint.key)?;
+    let fee = token::transfer_fee(&transfer_fee_config, amount)?;
+    if fee > 0 {
+        msg!("transfer fee {}, {} arrives", fee, amount - fee);
+    }
 
     invoke(
-        &spl_token::instruction::transfer_checked(
+        &token::transfer_checked(
             &spl_token.key,
             &source_info.key,
             mint.key,
             wallet_info.key,
             user_authority_info.key,
-            &[],
             amount,
-            decimals,
-        )
-        .unwrap(),
+            mint_state.decimals,
+            fee,
+        )?,
         &[
             wallet_info.clone(),
             source_info.clone(),
@@ -127,27 +129,38 @@ fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> Progr
     let mint = next_account_info(account_info_iter)?;
     let spl_token = next_account_info(account_info_iter)?;
 
-    let (wallet_address, _) = get_wallet_address(owner_info.key, program_id);
+    let (wallet_address, _) = get_wallet_address(owner_info.key, mint.key, program_id);
     let (authority_address, authority_seed) = get_authority(program_id);
 
     assert_eq!(wallet_info.key, &wallet_address);
     assert_eq!(authority_info.key, &authority_address);
     assert!(owner_info.is_signer, "owner must sign!");
 
-    let decimals = mint.data.borrow()[44];
+    // The mint is only checked against the token program that is passed (and the wallet, only with
+    // `patched`), and whichever program that is gets the authority signature below. spl-token 3.1.1
+    // refuses to build the instruction for another program id, 3.1.0 doesn't.
+    #[cfg(feature = "patched")]
+    token::check_token_program(spl_token)?;
+    #[cfg(feature = "patched")]
+    token::check_wallet_owner(wallet_info, spl_token.key)?;
+    let (mint_state, _, transfer_fee_config) = token::unpack_mint(mint, spl_token.key)?;
+    token::unpack_wallet(wallet_info, mint.key)?;
+    let fee = token::transfer_fee(&transfer_fee_config, amount)?;
+    if fee > 0 {
+        msg!("transfer fee {}, {} arrives", fee, amount - fee);
+    }
 
     invoke_signed(
-        &spl_token::instruction::transfer_checked(
+        &token::transfer_checked(
             &spl_token.key,
             &wallet_info.key,
             mint.key,
             destination_info.key,
             authority_info.key,
-            &[],
             amount,
-            decimals,
-        )
-        .unwrap(),
+            mint_state.decimals,
+            fee,
+        )?,
         &[
             wallet_info.clone(),
             destination_info.clone(),
@@ -0,0 +1,140 @@
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, instruction::Instruction,
+    msg, program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+// spl-token 3.1.0 or 3.1.1, picked by the vendored-spl-token-* features
+use crate::spl_token;
+use spl_token_2022::{
+    extension::{
+        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    state::{Account, Mint},
+};
+
+/// Token programs the wallet accepts and CPIs into: the legacy program and Token-2022
+pub fn token_program_allowlist() -> [Pubkey; 2] {
+    [spl_token::id(), spl_token_2022::id()]
+}
+
+/// The passed token program must be one of the allowlisted programs,
+/// a caller-controlled program id never receives the authority signature (`patched` feature)
+pub fn check_token_program(token_program: &AccountInfo) -> ProgramResult {
+    if !token_program_allowlist().contains(token_program.key) {
+        msg!("{} is not an allowed token program", token_program.key);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// The wallet must be owned by the token program that gets the authority signature (`patched` feature)
+pub fn check_wallet_owner(wallet_info: &AccountInfo, token_program: &Pubkey) -> ProgramResult {
+    if wallet_info.owner != token_program {
+        msg!("wallet {} is not owned by {}", wallet_info.key, token_program);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Parses the passed mint instead of trusting `mint.data[44]`
+///
+/// The account must be owned by the passed token program and hold an initialized `Mint`,
+/// Token-2022 mints may carry extensions after the base state.
+pub fn unpack_mint(
+    mint_info: &AccountInfo,
+    token_program: &Pubkey,
+) -> Result<(Mint, Vec<ExtensionType>, Option<TransferFeeConfig>), ProgramError> {
+    if mint_info.owner != token_program {
+        msg!("mint {} is not owned by {}", mint_info.key, token_program);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = mint_info.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+    let extensions = mint.get_extension_types()?;
+    let transfer_fee = mint.get_extension::<TransferFeeConfig>().ok().copied();
+    Ok((mint.base, extensions, transfer_fee))
+}
+
+/// Parses a wallet (token account) and checks it holds `mint`
+pub fn unpack_wallet(wallet_info: &AccountInfo, mint: &Pubkey) -> Result<Account, ProgramError> {
+    let data = wallet_info.data.borrow();
+    let wallet = StateWithExtensions::<Account>::unpack(&data)?.base;
+    if &wallet.mint != mint {
+        msg!("wallet {} holds mint {}, not {}", wallet_info.key, wallet.mint, mint);
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(wallet)
+}
+
+/// Space of a wallet for `mint`, including the account extensions the mint extensions require
+/// (e.g. TransferFeeAmount for a mint with a TransferFeeConfig)
+pub fn wallet_len(mint_extensions: &[ExtensionType]) -> usize {
+    let account_extensions = ExtensionType::get_required_init_account_extensions(mint_extensions);
+    ExtensionType::get_account_len::<Account>(&account_extensions)
+}
+
+/// Fee withheld by Token-2022 when moving `amount` in the current epoch
+pub fn transfer_fee(
+    transfer_fee_config: &Option<TransferFeeConfig>,
+    amount: u64,
+) -> Result<u64, ProgramError> {
+    match transfer_fee_config {
+        Some(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ProgramError::InvalidArgument),
+        None => Ok(0),
+    }
+}
+
+pub fn initialize_account(
+    token_program: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    if *token_program == spl_token_2022::id() {
+        spl_token_2022::instruction::initialize_account(token_program, account, mint, owner)
+    } else {
+        spl_token::instruction::initialize_account(token_program, account, mint, owner)
+    }
+}
+
+/// `transfer_checked` for the legacy program, `transfer_checked_with_fee` for Token-2022
+/// so the fee we accounted for is the fee the token program withholds
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+) -> Result<Instruction, ProgramError> {
+    if *token_program == spl_token_2022::id() {
+        spl_token_2022::extension::transfer_fee::instruction::transfer_checked_with_fee(
+            token_program,
+            source,
+            mint,
+            destination,
+            authority,
+            &[],
+            amount,
+            decimals,
+            fee,
+        )
+    } else {
+        spl_token::instruction::transfer_checked(
+            token_program,
+            source,
+            mint,
+            destination,
+            authority,
+            &[],
+            amount,
+            decimals,
+        )
+    }
+}
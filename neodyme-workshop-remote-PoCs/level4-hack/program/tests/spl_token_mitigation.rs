@@ -0,0 +1,408 @@
+/*
+The level4 exploit swaps the token program for `myspl`: withdraw builds a transfer_checked for whatever
+program id is passed and invoke_signed hands it the authority PDA signature.
+spl-token 3.1.0 builds that instruction for any program id, 3.1.1 refuses it (check_program_account),
+and the `patched` feature rejects any program outside the allowlist before that.
+
+The tests run withdraw through the processor, and the CPIs through spl-token and myspl themselves,
+with the checks of the runtime: a callee only gets the accounts of its caller, and only signers
+(or PDAs of its caller) sign. The victim Wallet is a real spl-token Wallet of the authority PDA.
+
+cargo test --test spl_token_mitigation
+CARGO_TARGET_DIR=target/spl-token-3.1.1 cargo test --test spl_token_mitigation --no-default-features --features vendored-spl-token-3-1-1
+CARGO_TARGET_DIR=target/patched cargo test --test spl_token_mitigation --features patched
+
+One target dir per build: spl-token and level4 are cdylibs too, so their rlibs aren't hashed
+and a build with other features overwrites them without the tests being relinked.
+*/
+use std::{cell::RefCell, sync::Once};
+
+use borsh::BorshSerialize;
+use level4::{get_authority, get_wallet_address, processor, spl_token, token, WalletInstruction};
+use solana_program::{
+    account_info::AccountInfo,
+    bpf_loader,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_token_2022::state::{Account, AccountState, Mint};
+
+/// myspl against the spl-token of the build: as a dependency it would bring spl-token 3.1.0
+/// into the 3.1.1 build, and both write the same rlib
+mod myspl {
+    use level4::spl_token;
+
+    include!("../../myspl/src/processor.rs");
+}
+
+/// The program id myspl is deployed at
+const MYSPL: Pubkey = Pubkey::new_from_array([0x31; 32]);
+
+/// A CPI of the processor or of a program it invoked: the instruction, the seeds it was signed
+/// with and what it returned
+struct Cpi {
+    instruction: Instruction,
+    signers_seeds: Vec<Vec<Vec<u8>>>,
+    result: ProgramResult,
+}
+
+/// A running program and the accounts of its instruction
+struct Frame {
+    program_id: Pubkey,
+    accounts: Vec<Pubkey>,
+}
+
+thread_local! {
+    static CPIS: RefCell<Vec<Cpi>> = const { RefCell::new(vec![]) };
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(vec![]) };
+}
+
+/// Runs the CPIs of the thread with spl-token and myspl, and records them
+struct RunCpis;
+
+impl SyscallStubs for RunCpis {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        // in the order they are made, the CPIs of the callee after its own
+        let index = CPIS.with(|cpis| {
+            let mut cpis = cpis.borrow_mut();
+            cpis.push(Cpi {
+                instruction: instruction.clone(),
+                signers_seeds: signers_seeds
+                    .iter()
+                    .map(|seeds| seeds.iter().map(|seed| seed.to_vec()).collect())
+                    .collect(),
+                result: Ok(()),
+            });
+            cpis.len() - 1
+        });
+        let result = invoke(instruction, account_infos, signers_seeds);
+        CPIS.with(|cpis| cpis.borrow_mut()[index].result = result.clone());
+        result
+    }
+}
+
+fn invoke(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let (caller, caller_accounts) = FRAMES.with(|frames| {
+        let frames = frames.borrow();
+        let frame = frames.last().unwrap();
+        (frame.program_id, frame.accounts.clone())
+    });
+    // The runtime fails the transaction with MissingAccount ("Unknown program") when the callee or
+    // one of its accounts is not an account of the caller
+    if !caller_accounts.contains(&instruction.program_id)
+        || instruction
+            .accounts
+            .iter()
+            .any(|meta| !caller_accounts.contains(&meta.pubkey))
+    {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let process: fn(&Pubkey, &[AccountInfo], &[u8]) -> ProgramResult = match instruction.program_id
+    {
+        id if id == spl_token::id() => spl_token::processor::Processor::process,
+        MYSPL => myspl::process_instruction,
+        // not a program, AccountNotExecutable
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+
+    let signers: Vec<Pubkey> = signers_seeds
+        .iter()
+        .filter_map(|seeds| Pubkey::create_program_address(seeds, &caller).ok())
+        .collect();
+    let mut accounts = vec![];
+    for meta in &instruction.accounts {
+        let mut account = account_infos
+            .iter()
+            .find(|account| *account.key == meta.pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
+            .clone();
+        if meta.is_signer && !account.is_signer && !signers.contains(account.key) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        account.is_signer = meta.is_signer;
+        account.is_writable = meta.is_writable;
+        accounts.push(account);
+    }
+
+    FRAMES.with(|frames| {
+        frames.borrow_mut().push(Frame {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| meta.pubkey)
+                .collect(),
+        })
+    });
+    let result = process(&instruction.program_id, &accounts, &instruction.data);
+    FRAMES.with(|frames| frames.borrow_mut().pop());
+    result
+}
+
+struct Withdrawal {
+    wallet_program: Pubkey,
+    authority: Pubkey,
+    victim_wallet: Pubkey,
+    result: ProgramResult,
+    cpis: Vec<Cpi>,
+    /// What the hacker's and the victim's Wallets hold afterwards
+    hacker_amount: u64,
+    victim_amount: u64,
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0; Account::LEN];
+    Account {
+        mint,
+        owner,
+        amount,
+        state: AccountState::Initialized,
+        ..Account::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+/// The hacker withdraws 10000 from its own Wallet into the victim's, through `token_program`.
+/// The hacker's Wallet and the mint are owned by `token_program`, which is all withdraw checks them
+/// against: a fake token program can own and fill them in. The victim's Wallet is a real spl-token
+/// Wallet of the authority PDA, holding 10000 of another mint
+fn withdraw(token_program: Pubkey) -> Withdrawal {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(RunCpis));
+    });
+    CPIS.with(|cpis| cpis.borrow_mut().clear());
+
+    let wallet_program = Pubkey::new_unique();
+    let hacker = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let victim_mint = match token_program == spl_token::id() {
+        true => mint,
+        false => Pubkey::new_unique(),
+    };
+    let victim_wallet = Pubkey::new_unique();
+    let (wallet, _) = get_wallet_address(&hacker, &mint, &wallet_program);
+    let (authority, _) = get_authority(&wallet_program);
+
+    let mut wallet_data = token_account(mint, authority, 10000);
+    let mut victim_data = token_account(victim_mint, authority, 10000);
+    let mut mint_data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::None,
+        supply: 20000,
+        decimals: 9,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut mint_data);
+
+    let (mut wallet_lamports, mut authority_lamports, mut hacker_lamports) = (1, 1, 1);
+    let (mut victim_lamports, mut mint_lamports, mut token_program_lamports) = (1, 1, 1);
+    let (mut authority_data, mut hacker_data, mut token_program_data) = (vec![], vec![], vec![]);
+    let (system_program, real_token_program, loader) =
+        (system_program::id(), spl_token::id(), bpf_loader::id());
+    let accounts = [
+        AccountInfo::new(
+            &wallet,
+            false,
+            true,
+            &mut wallet_lamports,
+            &mut wallet_data,
+            &token_program,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &authority,
+            false,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &hacker,
+            true,
+            false,
+            &mut hacker_lamports,
+            &mut hacker_data,
+            &system_program,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &victim_wallet,
+            false,
+            true,
+            &mut victim_lamports,
+            &mut victim_data,
+            &real_token_program,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &mint,
+            false,
+            false,
+            &mut mint_lamports,
+            &mut mint_data,
+            &token_program,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &token_program,
+            false,
+            false,
+            &mut token_program_lamports,
+            &mut token_program_data,
+            &loader,
+            true,
+            0,
+        ),
+    ];
+    let data = WalletInstruction::Withdraw { amount: 10000 }
+        .try_to_vec()
+        .unwrap();
+
+    FRAMES.with(|frames| {
+        *frames.borrow_mut() = vec![Frame {
+            program_id: wallet_program,
+            accounts: accounts.iter().map(|account| *account.key).collect(),
+        }]
+    });
+    let result = processor::process_instruction(&wallet_program, &accounts, &data);
+    let amount = |account: &AccountInfo| Account::unpack(&account.data.borrow()).unwrap().amount;
+    Withdrawal {
+        wallet_program,
+        authority,
+        victim_wallet,
+        result,
+        cpis: CPIS.with(|cpis| cpis.borrow_mut().drain(..).collect()),
+        hacker_amount: amount(&accounts[0]),
+        victim_amount: amount(&accounts[3]),
+    }
+}
+
+/// The first CPI went to `program_id` with the authority PDA as signer and the victim's Wallet
+fn assert_signed_for(withdrawal: &Withdrawal, program_id: Pubkey) {
+    let cpi = &withdrawal.cpis[0];
+    assert_eq!(cpi.instruction.program_id, program_id);
+    assert_eq!(
+        cpi.instruction.accounts[2],
+        AccountMeta::new(withdrawal.victim_wallet, false)
+    );
+    assert_eq!(
+        cpi.instruction.accounts[3],
+        AccountMeta::new_readonly(withdrawal.authority, true)
+    );
+    let seeds: Vec<&[u8]> = cpi.signers_seeds[0]
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect();
+    assert_eq!(
+        Pubkey::create_program_address(&seeds, &withdrawal.wallet_program),
+        Ok(withdrawal.authority)
+    );
+}
+
+#[test]
+fn real_token_program_moves_the_tokens() {
+    let withdrawal = withdraw(spl_token::id());
+    assert_eq!(withdrawal.result, Ok(()));
+    assert_eq!(withdrawal.cpis.len(), 1);
+    assert_eq!(withdrawal.cpis[0].result, Ok(()));
+    assert_signed_for(&withdrawal, spl_token::id());
+    assert_eq!(withdrawal.hacker_amount, 0);
+    assert_eq!(withdrawal.victim_amount, 20000);
+}
+
+#[cfg(all(feature = "vendored-spl-token-3-1-0", not(feature = "patched")))]
+#[test]
+fn spl_token_3_1_0_hands_myspl_the_victim_wallet_signed() {
+    let withdrawal = withdraw(MYSPL);
+    assert_signed_for(&withdrawal, MYSPL);
+
+    // myspl gets the authority signature over the victim's spl-token Wallet. It forwards it to the
+    // program in the mint slot, which is a mint now: spl-token is none of the 4 accounts of the
+    // transfer, so myspl can't reach it and the victim keeps its tokens
+    assert_eq!(withdrawal.cpis.len(), 2);
+    let myspl_cpi = &withdrawal.cpis[0];
+    assert_eq!(
+        myspl_cpi.instruction.accounts[1].pubkey,
+        withdrawal.cpis[1].instruction.program_id
+    );
+    assert!(!myspl_cpi
+        .instruction
+        .accounts
+        .iter()
+        .any(|meta| meta.pubkey == spl_token::id()));
+    // the mint is not a program
+    assert_eq!(
+        withdrawal.cpis[1].result,
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(withdrawal.result, Err(ProgramError::InvalidAccountData));
+    assert_eq!(withdrawal.hacker_amount, 10000);
+    assert_eq!(withdrawal.victim_amount, 10000);
+}
+
+#[cfg(all(feature = "vendored-spl-token-3-1-1", not(feature = "patched")))]
+#[test]
+fn spl_token_3_1_1_refuses_the_cpi_for_myspl() {
+    let withdrawal = withdraw(MYSPL);
+    assert_eq!(withdrawal.result, Err(ProgramError::IncorrectProgramId));
+    assert!(withdrawal.cpis.is_empty());
+    assert_eq!(withdrawal.victim_amount, 10000);
+}
+
+#[cfg(feature = "patched")]
+#[test]
+fn allowlist_refuses_myspl() {
+    let withdrawal = withdraw(MYSPL);
+    assert_eq!(withdrawal.result, Err(ProgramError::IncorrectProgramId));
+    assert!(withdrawal.cpis.is_empty());
+    assert_eq!(withdrawal.victim_amount, 10000);
+}
+
+#[test]
+fn allowlist_rejects_myspl_with_either_version() {
+    let check = |key: Pubkey| {
+        let mut lamports = 0;
+        let mut data = vec![];
+        let loader = Pubkey::new_unique();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &loader,
+            true,
+            0,
+        );
+        token::check_token_program(&info)
+    };
+
+    assert_eq!(check(MYSPL), Err(ProgramError::IncorrectProgramId));
+    assert_eq!(check(spl_token::id()), Ok(()));
+    assert_eq!(check(spl_token_2022::id()), Ok(()));
+}
@@ -0,0 +1,145 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum WalletInstruction {
+    /// Initialize a Wallet, and the shared Vault if it doesn't exist yet
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Wallet account
+    /// (2) Vault account
+    /// (3) Authority (must sign)
+    /// (4) Rent sysvar
+    /// (5) System program
+    Initialize,
+    /// Deposit
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Wallet account
+    /// (2) Vault account
+    /// (3) Money Source (must sign)
+    /// (4) System program
+    Deposit { amount: u64 },
+    /// Withdraw from Wallet
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Wallet account
+    /// (2) Vault account
+    /// (3) Authority (must sign)
+    /// (4) Destination
+    Withdraw { amount: u64 },
+    /// Move balance between two Wallets, the lamports stay in the Vault
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Source Wallet account
+    /// (2) Destination Wallet account
+    /// (3) Source Authority (must sign)
+    Transfer { amount: u64 },
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Wallet {
+    pub authority: Pubkey,
+    pub balance: u64,
+}
+
+pub const WALLET_LEN: u64 = 32 + 8;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_wallet_address(authority: Pubkey, wallet_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&authority.to_bytes()], &wallet_program)
+}
+
+/// Every Wallet keeps its lamports in the same Vault
+pub fn get_vault_address(wallet_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"VAULT".as_bytes()], &wallet_program)
+}
+
+pub fn initialize(wallet_program: Pubkey, authority_address: Pubkey) -> Instruction {
+    let (wallet_address, _) = get_wallet_address(authority_address, wallet_program);
+    let (vault_address, _) = get_vault_address(wallet_program);
+    Instruction {
+        program_id: wallet_program,
+        accounts: vec![
+            AccountMeta::new(wallet_address, false),
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(authority_address, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: WalletInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+pub fn deposit(
+    wallet_program: Pubkey,
+    authority_address: Pubkey,
+    source: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (wallet_address, _) = get_wallet_address(authority_address, wallet_program);
+    let (vault_address, _) = get_vault_address(wallet_program);
+    Instruction {
+        program_id: wallet_program,
+        accounts: vec![
+            AccountMeta::new(wallet_address, false),
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(source, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: WalletInstruction::Deposit { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn withdraw(
+    wallet_program: Pubkey,
+    authority_address: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (wallet_address, _) = get_wallet_address(authority_address, wallet_program);
+    let (vault_address, _) = get_vault_address(wallet_program);
+    Instruction {
+        program_id: wallet_program,
+        accounts: vec![
+            AccountMeta::new(wallet_address, false),
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(authority_address, true),
+            AccountMeta::new(destination, false),
+        ],
+        data: WalletInstruction::Withdraw { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn transfer(
+    wallet_program: Pubkey,
+    authority_address: Pubkey,
+    destination_authority: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (source_address, _) = get_wallet_address(authority_address, wallet_program);
+    let (destination_address, _) = get_wallet_address(destination_authority, wallet_program);
+    Instruction {
+        program_id: wallet_program,
+        accounts: vec![
+            AccountMeta::new(source_address, false),
+            AccountMeta::new(destination_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+        ],
+        data: WalletInstruction::Transfer { amount }.try_to_vec().unwrap(),
+    }
+}
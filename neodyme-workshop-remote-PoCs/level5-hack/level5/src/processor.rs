@@ -0,0 +1,183 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{get_vault_address, get_wallet_address, Wallet, WalletInstruction, WALLET_LEN};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match WalletInstruction::deserialize(&mut instruction_data)? {
+        WalletInstruction::Initialize => initialize(program_id, accounts),
+        WalletInstruction::Deposit { amount } => deposit(program_id, accounts, amount),
+        WalletInstruction::Withdraw { amount } => withdraw(program_id, accounts, amount),
+        WalletInstruction::Transfer { amount } => transfer(program_id, accounts, amount),
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let wallet_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (wallet_address, wallet_seed) = get_wallet_address(*authority_info.key, *program_id);
+    let (vault_address, vault_seed) = get_vault_address(*program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*wallet_info.key, wallet_address);
+    assert_eq!(*vault_info.key, vault_address);
+    assert!(wallet_info.data_is_empty());
+    assert!(authority_info.is_signer, "authority must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &authority_info.key,
+            &wallet_address,
+            rent.minimum_balance(WALLET_LEN as usize),
+            WALLET_LEN,
+            &program_id,
+        ),
+        &[authority_info.clone(), wallet_info.clone()],
+        &[&[&authority_info.key.to_bytes(), &[wallet_seed]]],
+    )?;
+
+    // the first wallet creates the shared vault
+    if vault_info.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                &authority_info.key,
+                &vault_address,
+                rent.minimum_balance(0),
+                0,
+                &program_id,
+            ),
+            &[authority_info.clone(), vault_info.clone()],
+            &[&[&"VAULT".as_bytes(), &[vault_seed]]],
+        )?;
+    }
+
+    let wallet = Wallet {
+        authority: *authority_info.key,
+        balance: 0,
+    };
+
+    wallet
+        .serialize(&mut &mut (*wallet_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn deposit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let wallet_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let source_info = next_account_info(account_info_iter)?;
+    let mut wallet = Wallet::deserialize(&mut &(*wallet_info.data).borrow_mut()[..])?;
+    let (vault_address, _) = get_vault_address(*program_id);
+
+    assert_eq!(wallet_info.owner, program_id);
+    assert_eq!(*vault_info.key, vault_address);
+
+    invoke(
+        &system_instruction::transfer(&source_info.key, &vault_info.key, amount),
+        &[vault_info.clone(), source_info.clone()],
+    )?;
+
+    wallet.balance = match wallet.balance.checked_add(amount) {
+        Some(v) => v,
+        None => return Err(ProgramError::InvalidArgument),
+    };
+
+    wallet
+        .serialize(&mut &mut (*wallet_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let wallet_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let mut wallet = Wallet::deserialize(&mut &(*wallet_info.data).borrow_mut()[..])?;
+    let (vault_address, _) = get_vault_address(*program_id);
+
+    assert_eq!(wallet_info.owner, program_id);
+    assert_eq!(*vault_info.key, vault_address);
+    assert!(authority_info.is_signer, "authority must sign!");
+    assert_eq!(wallet.authority, *authority_info.key);
+
+    wallet.balance = match wallet.balance.checked_sub(amount) {
+        Some(v) => v,
+        None => return Err(ProgramError::InsufficientFunds),
+    };
+
+    if amount > **vault_info.lamports.borrow_mut() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    **vault_info.lamports.borrow_mut() -= amount;
+    **destination_info.lamports.borrow_mut() += amount;
+
+    wallet
+        .serialize(&mut &mut (*wallet_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn transfer(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let source_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_eq!(source_info.owner, program_id);
+    assert_eq!(destination_info.owner, program_id);
+    assert!(authority_info.is_signer, "authority must sign!");
+
+    // Both wallets are read before either is written back. When the same account is passed
+    // twice, `destination` is a stale copy of `source` and its write wins: the balance grows by `amount`.
+    #[cfg(feature = "patched")]
+    if source_info.key == destination_info.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut source = Wallet::deserialize(&mut &(*source_info.data).borrow_mut()[..])?;
+    let mut destination = Wallet::deserialize(&mut &(*destination_info.data).borrow_mut()[..])?;
+
+    assert_eq!(source.authority, *authority_info.key);
+
+    source.balance = match source.balance.checked_sub(amount) {
+        Some(v) => v,
+        None => return Err(ProgramError::InsufficientFunds),
+    };
+    destination.balance = match destination.balance.checked_add(amount) {
+        Some(v) => v,
+        None => return Err(ProgramError::InvalidArgument),
+    };
+
+    source
+        .serialize(&mut &mut (*source_info.data).borrow_mut()[..])
+        .unwrap();
+    destination
+        .serialize(&mut &mut (*destination_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
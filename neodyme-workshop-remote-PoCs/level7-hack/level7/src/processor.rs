@@ -0,0 +1,139 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{get_vault_address, Vault, Voucher, VoucherInstruction, VAULT_LEN};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match VoucherInstruction::deserialize(&mut instruction_data)? {
+        VoucherInstruction::Initialize => initialize(program_id, accounts),
+        VoucherInstruction::Issue { amount } => issue(program_id, accounts, amount),
+        VoucherInstruction::Close => close(program_id, accounts),
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (vault_address, vault_seed) = get_vault_address(*program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*vault_info.key, vault_address);
+    assert!(vault_info.data_is_empty());
+    assert!(admin_info.is_signer, "admin must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &admin_info.key,
+            &vault_address,
+            rent.minimum_balance(VAULT_LEN as usize),
+            VAULT_LEN,
+            &program_id,
+        ),
+        &[admin_info.clone(), vault_info.clone()],
+        &[&[&"VAULT".as_bytes(), &[vault_seed]]],
+    )?;
+
+    let vault = Vault {
+        admin: *admin_info.key,
+    };
+
+    vault
+        .serialize(&mut &mut (*vault_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn issue(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let voucher_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let holder_info = next_account_info(account_info_iter)?;
+    let vault = Vault::deserialize(&mut &(*vault_info.data).borrow_mut()[..])?;
+    let (vault_address, _) = get_vault_address(*program_id);
+
+    assert_eq!(*vault_info.key, vault_address);
+    assert_eq!(voucher_info.owner, program_id);
+    assert!(admin_info.is_signer, "admin must sign!");
+    assert_eq!(vault.admin, *admin_info.key);
+    // check that account is uninitialized
+    if voucher_info.data.borrow_mut().iter().any(|b| *b != 0) {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    invoke(
+        &system_instruction::transfer(&admin_info.key, &vault_info.key, amount),
+        &[vault_info.clone(), admin_info.clone()],
+    )?;
+
+    let voucher = Voucher {
+        vault: *vault_info.key,
+        holder: *holder_info.key,
+        amount,
+    };
+
+    voucher
+        .serialize(&mut &mut (*voucher_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn close(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let voucher_info = next_account_info(account_info_iter)?;
+    let holder_info = next_account_info(account_info_iter)?;
+
+    #[cfg(feature = "patched")]
+    if voucher_info.data.borrow()[..8] == crate::CLOSED_ACCOUNT_DISCRIMINATOR {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let voucher = Voucher::deserialize(&mut &(*voucher_info.data).borrow_mut()[..])?;
+
+    assert_eq!(vault_info.owner, program_id);
+    assert_eq!(voucher_info.owner, program_id);
+    assert_eq!(voucher.vault, *vault_info.key);
+    assert!(holder_info.is_signer, "holder must sign!");
+    assert_eq!(voucher.holder, *holder_info.key);
+
+    if voucher.amount > **vault_info.lamports.borrow_mut() {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    **vault_info.lamports.borrow_mut() -= voucher.amount;
+    **holder_info.lamports.borrow_mut() += voucher.amount;
+
+    // Only the lamports go: the runtime drops the account at the end of the transaction,
+    // unless a later instruction of the same transaction refunds its rent. The data is still a valid Voucher.
+    let voucher_lamports = voucher_info.lamports();
+    **voucher_info.lamports.borrow_mut() = 0;
+    **holder_info.lamports.borrow_mut() += voucher_lamports;
+
+    #[cfg(feature = "patched")]
+    {
+        let mut data = voucher_info.data.borrow_mut();
+        data.fill(0);
+        data[..8].copy_from_slice(&crate::CLOSED_ACCOUNT_DISCRIMINATOR);
+    }
+
+    Ok(())
+}
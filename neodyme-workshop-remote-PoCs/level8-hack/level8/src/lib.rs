@@ -0,0 +1,87 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum BridgeInstruction {
+    /// Initialize the Bridge, anyone can fund it with a system transfer afterwards
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Bridge account
+    /// (2) Admin (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    Initialize { guardian: Pubkey },
+    /// Pay out a transfer approved by the guardian
+    ///
+    /// The previous instruction of the transaction must be an ed25519 program instruction
+    /// where the guardian signs `claim_message(recipient, amount, bridge.nonce)`
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Bridge account
+    /// (2) Recipient
+    /// (3) Instructions sysvar
+    Claim { amount: u64 },
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Bridge {
+    pub admin: Pubkey,
+    pub guardian: Pubkey,
+    // bumped by every Claim, a guardian signature is only good once
+    pub nonce: u64,
+}
+
+pub const BRIDGE_LEN: u64 = 32 + 32 + 8;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_bridge_address(bridge_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"BRIDGE".as_bytes()], &bridge_program)
+}
+
+/// Message the guardian signs to approve a Claim
+pub fn claim_message(recipient: &Pubkey, amount: u64, nonce: u64) -> Vec<u8> {
+    let mut message = recipient.to_bytes().to_vec();
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+pub fn initialize(bridge_program: Pubkey, admin: Pubkey, guardian: Pubkey) -> Instruction {
+    let (bridge_address, _) = get_bridge_address(bridge_program);
+    Instruction {
+        program_id: bridge_program,
+        accounts: vec![
+            AccountMeta::new(bridge_address, false),
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: BridgeInstruction::Initialize { guardian }.try_to_vec().unwrap(),
+    }
+}
+
+/// Goes right after the guardian's ed25519 instruction
+pub fn claim(bridge_program: Pubkey, recipient: Pubkey, amount: u64) -> Instruction {
+    let (bridge_address, _) = get_bridge_address(bridge_program);
+    Instruction {
+        program_id: bridge_program,
+        accounts: vec![
+            AccountMeta::new(bridge_address, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+        data: BridgeInstruction::Claim { amount }.try_to_vec().unwrap(),
+    }
+}
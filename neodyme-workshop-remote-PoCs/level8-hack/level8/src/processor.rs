@@ -0,0 +1,181 @@
+use std::convert::TryInto;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    ed25519_program,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::{instructions, Sysvar},
+};
+
+use crate::{claim_message, get_bridge_address, Bridge, BridgeInstruction, BRIDGE_LEN};
+
+// The ed25519 program instruction data is a 2 byte header followed by one
+// Ed25519SignatureOffsets (7 u16) per signature
+const SIGNATURE_OFFSETS_START: usize = 2;
+// instruction index meaning "the data of the ed25519 instruction itself"
+const THIS_INSTRUCTION: u16 = u16::MAX;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match BridgeInstruction::deserialize(&mut instruction_data)? {
+        BridgeInstruction::Initialize { guardian } => initialize(program_id, accounts, guardian),
+        BridgeInstruction::Claim { amount } => claim(program_id, accounts, amount),
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo], guardian: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let bridge_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (bridge_address, bridge_seed) = get_bridge_address(*program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*bridge_info.key, bridge_address);
+    assert!(bridge_info.data_is_empty());
+    assert!(admin_info.is_signer, "admin must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &admin_info.key,
+            &bridge_address,
+            rent.minimum_balance(BRIDGE_LEN as usize),
+            BRIDGE_LEN,
+            &program_id,
+        ),
+        &[admin_info.clone(), bridge_info.clone()],
+        &[&[&"BRIDGE".as_bytes(), &[bridge_seed]]],
+    )?;
+
+    let bridge = Bridge {
+        admin: *admin_info.key,
+        guardian,
+        nonce: 0,
+    };
+
+    bridge
+        .serialize(&mut &mut (*bridge_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn claim(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let bridge_info = next_account_info(account_info_iter)?;
+    let recipient_info = next_account_info(account_info_iter)?;
+    let instructions_info = next_account_info(account_info_iter)?;
+    let mut bridge = Bridge::deserialize(&mut &(*bridge_info.data).borrow_mut()[..])?;
+
+    assert_eq!(bridge_info.owner, program_id);
+
+    let ed25519_instruction = previous_instruction(instructions_info)?;
+    let message = verified_message(&ed25519_instruction, &bridge.guardian)?;
+    if message != claim_message(recipient_info.key, amount, bridge.nonce) {
+        msg!("guardian didn't approve this claim");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let rent = Rent::get()?;
+    let available = bridge_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(BRIDGE_LEN as usize));
+    if amount > available {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    **bridge_info.lamports.borrow_mut() -= amount;
+    **recipient_info.lamports.borrow_mut() += amount;
+
+    bridge.nonce += 1;
+    bridge
+        .serialize(&mut &mut (*bridge_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+/// The instruction right before the current one
+#[cfg(not(feature = "patched"))]
+fn previous_instruction(instructions_info: &AccountInfo) -> Result<Instruction, ProgramError> {
+    // Nothing checks that this is the Instructions sysvar, any account with
+    // the same layout is parsed as the instructions of this transaction
+    let data = instructions_info.data.borrow();
+    #[allow(deprecated)]
+    let current_index = instructions::load_current_index(&data);
+    if current_index == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    #[allow(deprecated)]
+    instructions::load_instruction_at(current_index as usize - 1, &data)
+        .map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+/// The instruction right before the current one
+#[cfg(feature = "patched")]
+fn previous_instruction(instructions_info: &AccountInfo) -> Result<Instruction, ProgramError> {
+    let current_index = instructions::load_current_index_checked(instructions_info)?;
+    if current_index == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    instructions::load_instruction_at_checked(current_index as usize - 1, instructions_info)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// Message of a single-signature ed25519 program instruction signed by `guardian`
+///
+/// The ed25519 program already failed the transaction if the signature is wrong,
+/// we only have to make sure it checked the right key and message.
+fn verified_message(
+    instruction: &Instruction,
+    guardian: &Pubkey,
+) -> Result<Vec<u8>, ProgramError> {
+    let data = &instruction.data;
+    if instruction.program_id != ed25519_program::id() || data.first() != Some(&1) {
+        msg!("previous instruction is not a single ed25519 signature");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_instruction_index = read_u16(data, offsets + 2)?;
+    let public_key_offset = read_u16(data, offsets + 4)? as usize;
+    let public_key_instruction_index = read_u16(data, offsets + 6)?;
+    let message_data_offset = read_u16(data, offsets + 8)? as usize;
+    let message_data_size = read_u16(data, offsets + 10)? as usize;
+    let message_instruction_index = read_u16(data, offsets + 12)?;
+    if signature_instruction_index != THIS_INSTRUCTION
+        || public_key_instruction_index != THIS_INSTRUCTION
+        || message_instruction_index != THIS_INSTRUCTION
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if public_key != guardian.as_ref() {
+        msg!("not signed by the guardian");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    data.get(message_data_offset..message_data_offset + message_data_size)
+        .map(|message| message.to_vec())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
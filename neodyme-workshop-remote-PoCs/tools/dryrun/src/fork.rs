@@ -0,0 +1,234 @@
+//! A local bank standing in for the validator under `--dry-run`, so every step is simulated on top
+//! of the ones before it. poc_framework's bank (solana 1.11) with `fork`, a bank of solana-runtime
+//! 1.18 with `fork-1-18`
+use std::collections::HashSet;
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    pubkey::Pubkey,
+    system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+use crate::Simulation;
+
+#[cfg(all(feature = "fork", feature = "fork-1-18"))]
+compile_error!("enable only one of fork and fork-1-18");
+
+/// What the bank says about a transaction
+struct Outcome {
+    result: Result<(), TransactionError>,
+    logs: Vec<String>,
+    /// The accounts of the transaction after it, in its order
+    post_accounts: Vec<(Pubkey, AccountSharedData)>,
+    units_consumed: u64,
+}
+
+#[cfg(feature = "fork")]
+mod bank {
+    use poc_framework::LocalEnvironment;
+    use solana_client::rpc_client::RpcClient;
+    use solana_sdk::{
+        account::AccountSharedData,
+        pubkey::Pubkey,
+        transaction::{SanitizedTransaction, Transaction},
+    };
+
+    use super::Outcome;
+
+    /// poc_framework's bank
+    pub struct Bank(LocalEnvironment);
+
+    impl Bank {
+        pub fn new(_client: &RpcClient) -> Result<Self, String> {
+            Ok(Bank(LocalEnvironment::builder().build()))
+        }
+
+        pub fn slot(&mut self) -> u64 {
+            self.0.bank().slot()
+        }
+
+        pub fn get_account(&mut self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+            self.0.bank().get_account(pubkey)
+        }
+
+        pub fn store_account(&mut self, pubkey: &Pubkey, account: &AccountSharedData) {
+            self.0.bank().store_account(pubkey, account)
+        }
+
+        /// Runs the transaction without keeping anything
+        pub fn simulate(&mut self, tx: &Transaction) -> Result<Outcome, String> {
+            let bank = self.0.bank();
+            // The blockhash is one of the validator
+            bank.register_recent_blockhash(&tx.message.recent_blockhash);
+            let sanitized = SanitizedTransaction::try_from_legacy_transaction(tx.clone()).map_err(|e| e.to_string())?;
+            let result = bank.simulate_transaction_unchecked(sanitized);
+            Ok(Outcome {
+                result: result.result,
+                logs: result.logs,
+                post_accounts: result.post_simulation_accounts,
+                units_consumed: result.units_consumed,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "fork-1-18")]
+mod bank {
+    use std::sync::{Arc, RwLock};
+
+    use solana_client::rpc_client::RpcClient;
+    use solana_runtime::{bank::Bank as RuntimeBank, bank_forks::BankForks, genesis_utils::create_genesis_config};
+    use solana_sdk::{
+        account::AccountSharedData,
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+        transaction::{SanitizedTransaction, Transaction},
+    };
+
+    use super::Outcome;
+
+    /// A bank of solana-runtime 1.18, a slot after the one of the validator: a program is only
+    /// visible after the slot it was deployed in
+    pub struct Bank {
+        bank: Arc<RuntimeBank>,
+        /// The program cache of the bank needs them
+        _forks: Arc<RwLock<BankForks>>,
+    }
+
+    impl Bank {
+        pub fn new(client: &RpcClient) -> Result<Self, String> {
+            let slot = client.get_slot().map_err(|e| e.to_string())?;
+            let genesis = create_genesis_config(1_000_000 * LAMPORTS_PER_SOL).genesis_config;
+            let (root, forks) = RuntimeBank::new_with_bank_forks_for_tests(&genesis);
+            let bank = RuntimeBank::new_from_parent(root, &Pubkey::default(), slot + 1);
+            let bank = forks.write().unwrap().insert(bank).clone_without_scheduler();
+            Ok(Bank { bank, _forks: forks })
+        }
+
+        pub fn slot(&mut self) -> u64 {
+            self.bank.slot()
+        }
+
+        pub fn get_account(&mut self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+            self.bank.get_account(pubkey)
+        }
+
+        pub fn store_account(&mut self, pubkey: &Pubkey, account: &AccountSharedData) {
+            self.bank.store_account(pubkey, account)
+        }
+
+        /// Runs the transaction without keeping anything
+        pub fn simulate(&mut self, tx: &Transaction) -> Result<Outcome, String> {
+            // The blockhash is one of the validator. 1.18 only registers one at the end of a block,
+            // so the bank ticks to the next one
+            let blockhash = tx.message.recent_blockhash;
+            while self.bank.last_blockhash() != blockhash {
+                self.bank.register_tick_for_test(&blockhash);
+            }
+            let sanitized = SanitizedTransaction::try_from_legacy_transaction(tx.clone()).map_err(|e| e.to_string())?;
+            let result = self.bank.simulate_transaction_unchecked(&sanitized, false);
+            Ok(Outcome {
+                result: result.result,
+                logs: result.logs,
+                post_accounts: result.post_simulation_accounts,
+                units_consumed: result.units_consumed,
+            })
+        }
+    }
+}
+
+/// The accounts of the validator, copied into a local bank the first time a step or a read needs
+/// them. The bank then keeps what the simulated steps and airdrops did to them
+pub struct Fork {
+    bank: bank::Bank,
+    /// The accounts copied (or found missing) so far
+    loaded: HashSet<Pubkey>,
+}
+
+impl Fork {
+    pub fn new(client: &RpcClient) -> Result<Self, String> {
+        Ok(Fork { bank: bank::Bank::new(client)?, loaded: HashSet::new() })
+    }
+
+    /// Copies the accounts the bank has not seen yet, with the ProgramData of upgradeable programs.
+    /// The builtins and sysvars of the bank are kept
+    fn load(&mut self, client: &RpcClient, keys: &[Pubkey]) -> Result<(), String> {
+        let missing: Vec<Pubkey> = keys
+            .iter()
+            .filter(|key| !self.loaded.contains(key) && self.bank.get_account(key).is_none())
+            .copied()
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let accounts = client.get_multiple_accounts(&missing).map_err(|e| e.to_string())?;
+        let mut program_data = vec![];
+        for (key, account) in missing.iter().zip(accounts) {
+            self.loaded.insert(*key);
+            let account = match account {
+                Some(account) => account,
+                None => continue,
+            };
+            if account.owner == bpf_loader_upgradeable::id() {
+                if let Ok(UpgradeableLoaderState::Program { programdata_address }) = account.deserialize_data() {
+                    program_data.push(programdata_address);
+                }
+            }
+            self.bank.store_account(key, &AccountSharedData::from(account));
+        }
+        self.load(client, &program_data)
+    }
+
+    /// The account as the steps so far left it
+    pub fn get_account(&mut self, client: &RpcClient, pubkey: &Pubkey) -> Result<Option<Account>, String> {
+        self.load(client, &[*pubkey])?;
+        Ok(self.bank.get_account(pubkey).map(Account::from))
+    }
+
+    /// Adds the lamports to the account, creating it when it does not exist
+    pub fn credit(&mut self, client: &RpcClient, pubkey: &Pubkey, lamports: u64) -> Result<(), String> {
+        let mut account =
+            self.get_account(client, pubkey)?.unwrap_or_else(|| Account::new(0, 0, &system_program::id()));
+        account.lamports += lamports;
+        self.bank.store_account(pubkey, &AccountSharedData::from(account));
+        Ok(())
+    }
+
+    /// Runs the transaction (signatures checked) in the bank, and keeps what it does when it succeeds
+    pub fn simulate(&mut self, client: &RpcClient, tx: &Transaction) -> Result<Simulation, String> {
+        tx.verify().map_err(|e| e.to_string())?;
+        let keys = &tx.message.account_keys;
+        self.load(client, keys)?;
+        let before: Vec<Option<Account>> =
+            keys.iter().map(|key| self.bank.get_account(key).map(Account::from)).collect();
+
+        let outcome = self.bank.simulate(tx)?;
+        // Only the writable accounts can change, the read-only programs come back as placeholders
+        let after = match outcome.result {
+            Ok(()) => outcome
+                .post_accounts
+                .iter()
+                .zip(&before)
+                .enumerate()
+                .map(|(i, ((key, account), before))| {
+                    if !tx.message.is_writable(i) {
+                        return before.clone();
+                    }
+                    self.bank.store_account(key, account);
+                    Some(Account::from(account.clone())).filter(|account| account.lamports > 0)
+                })
+                .collect(),
+            Err(_) => before.clone(),
+        };
+        Ok(Simulation {
+            slot: self.bank.slot(),
+            err: outcome.result.err(),
+            logs: outcome.logs,
+            units_consumed: Some(outcome.units_consumed),
+            accounts: keys.iter().copied().zip(before).zip(after).map(|((key, b), a)| (key, b, a)).collect(),
+        })
+    }
+}
@@ -0,0 +1,379 @@
+//! `--dry-run` for the PoCs and natives: every transaction is run through simulateTransaction
+//! instead of being sent, and its logs, compute units and account changes are printed, so an
+//! exploit can be tried against a shared validator without touching it.
+//!
+//! With the `fork` feature (`fork-1-18` for level4, on solana 1.18) the steps run in a local bank
+//! instead, holding the accounts of the validator as the earlier steps and airdrops left them: a
+//! deposit into the Wallet the previous step initializes goes through. Without it every step is
+//! simulated by the validator against its own state, only `get_account`, `get_balance` and the
+//! airdrops see the earlier steps. The run stops at the first step that would fail.
+//!
+//! It also knows which validator they talk to: `$RPC_URL`, set by the `validator` harness, or the
+//! local one.
+#[cfg(not(any(feature = "fork", feature = "fork-1-18")))]
+use std::collections::BTreeMap;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex, MutexGuard,
+};
+
+use owo_colors::OwoColorize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_client::RpcClient,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    native_token::lamports_to_sol,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::{Transaction, TransactionError},
+};
+
+use actors::Actors;
+
+#[cfg(any(feature = "fork", feature = "fork-1-18"))]
+mod fork;
+#[cfg(feature = "poc")]
+mod poc;
+#[cfg(any(feature = "fork", feature = "fork-1-18"))]
+use fork::Fork;
+#[cfg(feature = "poc")]
+pub use poc::RemoteEnvironment;
+
+/// The steps simulated so far, to number them
+static STEPS: AtomicUsize = AtomicUsize::new(0);
+
+/// The actors, to name the accounts in the output. Read by the first step that prints
+static ACTORS: Mutex<Option<Actors>> = Mutex::new(None);
+
+/// The validator as the simulated steps left it, created by the first step or read
+#[cfg(any(feature = "fork", feature = "fork-1-18"))]
+static FORK: Mutex<Option<Fork>> = Mutex::new(None);
+
+/// The accounts as the simulated steps and airdrops left them (None once closed)
+#[cfg(not(any(feature = "fork", feature = "fork-1-18")))]
+static CHANGED: Mutex<BTreeMap<Pubkey, Option<Account>>> = Mutex::new(BTreeMap::new());
+
+/// The RPC URL of the validator: `$RPC_URL`, or `http://localhost:8899/`
+pub fn url() -> String {
+    std::env::var("RPC_URL").unwrap_or_else(|_| "http://localhost:8899/".to_string())
+}
+
+/// A client of the validator at `url()`, like poc_framework's `localhost_client()`
+pub fn client() -> RpcClient {
+    RpcClient::new_with_commitment(url(), CommitmentConfig::confirmed())
+}
+
+/// `--dry-run` is one of the arguments of the program
+pub fn requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--dry-run")
+}
+
+/// What a transaction would do
+pub struct Simulation {
+    pub slot: u64,
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    /// Every account of the transaction, before and after it (None when it doesn't exist). The
+    /// after side is only known when the simulation succeeds
+    pub accounts: Vec<(Pubkey, Option<Account>, Option<Account>)>,
+}
+
+impl Simulation {
+    /// The accounts the transaction changes
+    pub fn changes(&self) -> impl Iterator<Item = &(Pubkey, Option<Account>, Option<Account>)> {
+        self.accounts.iter().filter(|(_, before, after)| before != after)
+    }
+
+    pub fn print(&self, step: usize, actors: &Actors) {
+        let units = match self.units_consumed {
+            Some(units) => format!(", {} compute units", units),
+            None => String::new(),
+        };
+        match &self.err {
+            None => println!("{} step {}: {}{}", "[dry run]".cyan().bold(), step, "ok".green().bold(), units),
+            Some(err) => println!(
+                "{} step {}: {}{}",
+                "[dry run]".cyan().bold(),
+                step,
+                format!("would fail, {}", err).red().bold(),
+                units
+            ),
+        }
+        for log in &self.logs {
+            println!("    {}", actors.relabel(log).dimmed());
+        }
+        for (pubkey, before, after) in self.changes() {
+            println!("  {} {}", actors.tag(pubkey), change(before.as_ref(), after.as_ref()));
+        }
+    }
+}
+
+/// `lamports 1 -> 0.5 SOL (-0.5), 12 of 64 bytes changed`
+fn change(before: Option<&Account>, after: Option<&Account>) -> String {
+    let (before, after) = match (before, after) {
+        (None, Some(after)) => {
+            return format!(
+                "{} {} SOL, {} bytes, owner {}",
+                "created:".green(),
+                lamports_to_sol(after.lamports),
+                after.data.len(),
+                after.owner
+            )
+        }
+        (Some(before), None) => {
+            return format!("{} -{} SOL", "closed:".red(), lamports_to_sol(before.lamports))
+        }
+        (Some(before), Some(after)) => (before, after),
+        (None, None) => return String::new(),
+    };
+    let mut parts = vec![];
+    if before.lamports != after.lamports {
+        let sign = if after.lamports > before.lamports { "+" } else { "-" };
+        parts.push(format!(
+            "lamports {} -> {} SOL ({}{})",
+            lamports_to_sol(before.lamports),
+            lamports_to_sol(after.lamports),
+            sign,
+            lamports_to_sol(before.lamports.max(after.lamports) - before.lamports.min(after.lamports))
+        ));
+    }
+    if before.data.len() != after.data.len() {
+        parts.push(format!("resized {} -> {} bytes", before.data.len(), after.data.len()));
+    } else {
+        let changed = before.data.iter().zip(&after.data).filter(|(b, a)| b != a).count();
+        if changed > 0 {
+            parts.push(format!("{} of {} bytes changed", changed, after.data.len()));
+        }
+    }
+    if before.owner != after.owner {
+        parts.push(format!("owner {} -> {}", before.owner, after.owner));
+    }
+    parts.join(", ")
+}
+
+/// Simulates the transaction (signatures checked) against the current state of the validator
+pub fn simulate(client: &RpcClient, tx: &Transaction) -> Result<Simulation, String> {
+    let keys = &tx.message.account_keys;
+    let before = client.get_multiple_accounts(keys).map_err(|e| e.to_string())?;
+    let response = client
+        .simulate_transaction_with_config(
+            tx,
+            RpcSimulateTransactionConfig {
+                sig_verify: true,
+                commitment: Some(client.commitment()),
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: keys.iter().map(|key| key.to_string()).collect(),
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )
+        .map_err(|e| e.to_string())?;
+    let result = response.value;
+    let after: Vec<Option<Account>> = match (&result.err, result.accounts) {
+        (None, Some(accounts)) => accounts.iter().map(|account| account.as_ref().and_then(|a| a.decode())).collect(),
+        _ => before.clone(),
+    };
+    Ok(Simulation {
+        slot: response.context.slot,
+        err: result.err,
+        logs: result.logs.unwrap_or_default(),
+        units_consumed: result.units_consumed,
+        accounts: keys.iter().copied().zip(before).zip(after).map(|((key, b), a)| (key, b, a)).collect(),
+    })
+}
+
+/// The actors of the registry, loaded once
+fn actors() -> MutexGuard<'static, Option<Actors>> {
+    let mut actors = ACTORS.lock().unwrap();
+    if actors.is_none() {
+        let mut loaded = Actors::new();
+        loaded.load_all();
+        *actors = Some(loaded);
+    }
+    actors
+}
+
+/// Runs `f` on the fork, created by the first step or read
+#[cfg(any(feature = "fork", feature = "fork-1-18"))]
+fn with_fork<T>(client: &RpcClient, f: impl FnOnce(&mut Fork) -> Result<T, String>) -> Result<T, String> {
+    let mut fork = FORK.lock().unwrap();
+    if fork.is_none() {
+        *fork = Some(Fork::new(client)?);
+    }
+    f(fork.as_mut().unwrap())
+}
+
+/// Simulates the transaction on top of the steps before it
+#[cfg(any(feature = "fork", feature = "fork-1-18"))]
+fn simulate_step(client: &RpcClient, tx: &Transaction) -> Result<Simulation, String> {
+    with_fork(client, |fork| fork.simulate(client, tx))
+}
+
+/// Simulates the transaction against the validator, and keeps the accounts it changes for the
+/// reads after it
+#[cfg(not(any(feature = "fork", feature = "fork-1-18")))]
+fn simulate_step(client: &RpcClient, tx: &Transaction) -> Result<Simulation, String> {
+    let simulation = simulate(client, tx)?;
+    if simulation.err.is_none() {
+        let mut changed = CHANGED.lock().unwrap();
+        for (pubkey, _, after) in simulation.changes() {
+            changed.insert(*pubkey, after.clone());
+        }
+    }
+    Ok(simulation)
+}
+
+/// Simulates the transaction as the next step of the run, and prints what it would do
+fn step(client: &RpcClient, tx: &Transaction) -> Simulation {
+    let step = STEPS.fetch_add(1, Ordering::SeqCst) + 1;
+    match simulate_step(client, tx) {
+        Ok(simulation) => {
+            simulation.print(step, actors().as_ref().unwrap());
+            simulation
+        }
+        Err(e) => {
+            println!("{} step {}: {}", "[dry run]".cyan().bold(), step, e.red().bold());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Simulates the transaction and prints what it would do. Exits when it would fail, the steps
+/// after it building on it
+pub fn dry_run(client: &RpcClient, tx: &Transaction) -> Simulation {
+    let simulation = step(client, tx);
+    if simulation.err.is_some() {
+        println!("{}", "[dry run] stopping here, nothing was sent".cyan().bold());
+        std::process::exit(1);
+    }
+    simulation
+}
+
+/// `send_and_confirm_transaction`, or only a simulation under `--dry-run`. A failed transaction
+/// prints the error with the logs of the preflight and exits, instead of panicking in `unwrap()`
+pub fn send(client: &RpcClient, tx: &Transaction) -> Signature {
+    if requested() {
+        dry_run(client, tx);
+        return tx.signatures[0];
+    }
+    match try_send(client, tx) {
+        Ok(signature) => signature,
+        Err(e) => {
+            println!("{} {}", "transaction failed:".red().bold(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `send`, for a transaction that is meant to be rejected: the failure (with the logs) is returned
+/// instead of ending the run, under `--dry-run` too
+pub fn try_send(client: &RpcClient, tx: &Transaction) -> Result<Signature, String> {
+    if requested() {
+        return match step(client, tx).err {
+            Some(err) => Err(err.to_string()),
+            None => Ok(tx.signatures[0]),
+        };
+    }
+    client.send_and_confirm_transaction(tx).map_err(|e| {
+        let actors = actors();
+        let actors = actors.as_ref().unwrap();
+        let mut message = e.to_string();
+        for log in preflight_logs(&e) {
+            message.push_str(&format!("\n    {}", actors.relabel(log).dimmed()));
+        }
+        message
+    })
+}
+
+/// The logs of a transaction the preflight simulation of the validator rejected
+fn preflight_logs(e: &ClientError) -> &[String] {
+    match e.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) => result.logs.as_deref().unwrap_or_default(),
+        _ => &[],
+    }
+}
+
+/// The account, as the simulated steps left it under `--dry-run`. Exits when the RPC fails
+pub fn get_account(client: &RpcClient, pubkey: &Pubkey) -> Option<Account> {
+    let account = match requested() {
+        true => simulated_account(client, pubkey),
+        false => client
+            .get_account_with_commitment(pubkey, client.commitment())
+            .map(|response| response.value)
+            .map_err(|e| e.to_string()),
+    };
+    account.unwrap_or_else(|e| {
+        println!("{} {}: {}", "getAccountInfo failed:".red().bold(), pubkey, e);
+        std::process::exit(1);
+    })
+}
+
+/// The lamports of the account, 0 when it does not exist
+pub fn get_balance(client: &RpcClient, pubkey: &Pubkey) -> u64 {
+    get_account(client, pubkey).map_or(0, |account| account.lamports)
+}
+
+#[cfg(any(feature = "fork", feature = "fork-1-18"))]
+fn simulated_account(client: &RpcClient, pubkey: &Pubkey) -> Result<Option<Account>, String> {
+    with_fork(client, |fork| fork.get_account(client, pubkey))
+}
+
+#[cfg(not(any(feature = "fork", feature = "fork-1-18")))]
+fn simulated_account(client: &RpcClient, pubkey: &Pubkey) -> Result<Option<Account>, String> {
+    if let Some(account) = CHANGED.lock().unwrap().get(pubkey) {
+        return Ok(account.clone());
+    }
+    client
+        .get_account_with_commitment(pubkey, client.commitment())
+        .map(|response| response.value)
+        .map_err(|e| e.to_string())
+}
+
+/// Adds the airdrop to the simulated account
+#[cfg(any(feature = "fork", feature = "fork-1-18"))]
+fn credit(client: &RpcClient, to: &Pubkey, lamports: u64) -> Result<(), String> {
+    with_fork(client, |fork| fork.credit(client, to, lamports))
+}
+
+#[cfg(not(any(feature = "fork", feature = "fork-1-18")))]
+fn credit(client: &RpcClient, to: &Pubkey, lamports: u64) -> Result<(), String> {
+    let mut account = simulated_account(client, to)?.unwrap_or_else(|| Account::new(0, 0, &solana_sdk::system_program::id()));
+    account.lamports += lamports;
+    CHANGED.lock().unwrap().insert(*to, Some(account));
+    Ok(())
+}
+
+/// Airdrops the lamports and waits for the confirmation. Under `--dry-run` they are only credited
+/// to the simulated account
+pub fn airdrop(client: &RpcClient, to: &Pubkey, lamports: u64) {
+    if requested() {
+        println!("{} airdrop of {} SOL to {}, simulated", "[dry run]".cyan().bold(), lamports_to_sol(lamports), to);
+        if let Err(e) = credit(client, to, lamports) {
+            println!("{} {}", "[dry run]".cyan().bold(), e.red().bold());
+            std::process::exit(1);
+        }
+        return;
+    }
+    match client.request_airdrop(to, lamports) {
+        Ok(sig) => loop {
+            if let Ok(confirmed) = client.confirm_transaction(&sig) {
+                if confirmed {
+                    println!("Transaction: {} Status: {}", sig, confirmed);
+                    break;
+                }
+            }
+        },
+        Err(_) => println!("Error requesting airdrop"),
+    };
+}
@@ -0,0 +1,134 @@
+//! The `RemoteEnvironment` of the PoCs, with `--dry-run`
+use poc_framework::{
+    bincode, clone_keypair,
+    solana_client::{rpc_client::RpcClient, rpc_config::RpcTransactionConfig},
+    solana_program::{hash::Hash, pubkey::Pubkey},
+    solana_sdk::{
+        account::Account,
+        commitment_config::CommitmentConfig,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+    solana_transaction_status::{
+        EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, EncodedTransactionWithStatusMeta,
+        UiTransactionEncoding, UiTransactionStatusMeta,
+    },
+    Environment,
+};
+
+use crate::{airdrop, dry_run, get_account, get_balance, requested, send};
+
+/// poc_framework's `RemoteEnvironment`, which only simulates the transactions under `--dry-run`.
+/// `get_account` then answers with the accounts as the simulations left them, so the PoC can go
+/// on printing them (the validator still has the old ones)
+pub struct RemoteEnvironment {
+    client: RpcClient,
+    payer: Keypair,
+    dry_run: bool,
+}
+
+impl RemoteEnvironment {
+    /// The payer keypair is expected to have enough funds to fund all transactions
+    pub fn new(client: RpcClient, payer: Keypair) -> Self {
+        RemoteEnvironment {
+            client,
+            payer,
+            dry_run: requested(),
+        }
+    }
+
+    /// Airdrops lamports up to the given balance to the payer first
+    pub fn new_with_airdrop(client: RpcClient, payer: Keypair, lamports: u64) -> Self {
+        let env = Self::new(client, payer);
+        env.airdrop(env.payer.pubkey(), lamports);
+        env
+    }
+
+    /// Airdrops lamports up to the given balance to the account, only to the simulated one under
+    /// `--dry-run`
+    pub fn airdrop(&self, account: Pubkey, lamports: u64) {
+        if get_balance(&self.client, &account) >= lamports {
+            return;
+        }
+        if self.dry_run {
+            airdrop(&self.client, &account, lamports);
+            return;
+        }
+        println!("Requesting airdrop...");
+        let blockhash = self.client.get_latest_blockhash().unwrap();
+        let sig = self
+            .client
+            .request_airdrop_with_blockhash(&account, lamports, &blockhash)
+            .unwrap();
+        self.client
+            .confirm_transaction_with_spinner(&sig, &blockhash, CommitmentConfig::confirmed())
+            .unwrap();
+    }
+
+    /// What the PoC gets back from a simulated transaction: the meta of the simulation, as if
+    /// it had landed in its slot
+    fn simulated_transaction(&self, tx: &Transaction) -> EncodedConfirmedTransactionWithStatusMeta {
+        let simulation = dry_run(&self.client, tx);
+        let balance = |account: &Option<Account>| account.as_ref().map_or(0, |account| account.lamports);
+        let meta = UiTransactionStatusMeta {
+            err: None,
+            status: Ok(()),
+            fee: self.client.get_fee_for_message(&tx.message).unwrap_or(0),
+            pre_balances: simulation.accounts.iter().map(|(_, before, _)| balance(before)).collect(),
+            post_balances: simulation.accounts.iter().map(|(_, _, after)| balance(after)).collect(),
+            inner_instructions: None,
+            log_messages: Some(simulation.logs.clone()),
+            pre_token_balances: None,
+            post_token_balances: None,
+            rewards: None,
+            loaded_addresses: None,
+            return_data: None,
+        };
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: simulation.slot,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::LegacyBinary(
+                    bs58::encode(bincode::serialize(tx).unwrap()).into_string(),
+                ),
+                meta: Some(meta),
+                version: None,
+            },
+            block_time: None,
+        }
+    }
+}
+
+impl Environment for RemoteEnvironment {
+    fn payer(&self) -> Keypair {
+        clone_keypair(&self.payer)
+    }
+
+    fn execute_transaction(&mut self, tx: Transaction) -> EncodedConfirmedTransactionWithStatusMeta {
+        if self.dry_run {
+            return self.simulated_transaction(&tx);
+        }
+        let sig = send(&self.client, &tx);
+        self.client
+            .get_transaction_with_config(
+                &sig,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Binary),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    ..RpcTransactionConfig::default()
+                },
+            )
+            .unwrap()
+    }
+
+    fn get_latest_blockhash(&self) -> Hash {
+        self.client.get_latest_blockhash().unwrap()
+    }
+
+    fn get_rent_excemption(&self, data: usize) -> u64 {
+        self.client.get_minimum_balance_for_rent_exemption(data).unwrap()
+    }
+
+    fn get_account(&self, pubkey: Pubkey) -> Option<Account> {
+        get_account(&self.client, &pubkey)
+    }
+}
@@ -0,0 +1,27 @@
+//! Generates the IDL of every level from its sources, see `src/parse.rs`
+use std::{env, fs, path::Path};
+
+#[path = "src/types.rs"]
+#[allow(dead_code)]
+mod types;
+
+#[path = "src/parse.rs"]
+#[allow(dead_code)]
+mod parse;
+
+#[path = "src/sources.rs"]
+mod sources;
+
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("../..");
+    let mut idls = vec![];
+    for (name, dir) in sources::SOURCES {
+        let source_path = format!("{}/src/lib.rs", dir);
+        let path = root.join(&source_path);
+        println!("cargo:rerun-if-changed={}", path.display());
+        let source = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        idls.push(parse::parse_idl(name, &source_path, &source).unwrap());
+    }
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("idls.json");
+    fs::write(out, serde_json::to_string(&idls).unwrap()).unwrap();
+}
@@ -0,0 +1,191 @@
+use std::{path::PathBuf, str::FromStr};
+
+use serde_json::{Map, Value};
+use solana_program::pubkey::Pubkey;
+
+use idl::{check, Idl};
+
+const USAGE: &str = "usage: idl list
+       idl show <PROGRAM>
+       idl dump [DIR]                                          (target/idl by default)
+       idl encode <PROGRAM> <INSTRUCTION> [ARG=VALUE]... [ACCOUNT_PUBKEY...]
+       idl decode <PROGRAM> <HEX_DATA>
+       idl account <PROGRAM> <LAYOUT> <HEX_DATA>
+       idl check [--root <REPO_ROOT>] [PROGRAM]...";
+
+fn program(name: Option<String>) -> Result<Idl, String> {
+    let name = name.ok_or(USAGE)?;
+    idl::find(&name).ok_or(format!("unknown program {}, see `idl list`", name))
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unhex(s: Option<String>) -> Result<Vec<u8>, String> {
+    let s = s.ok_or(USAGE)?;
+    let s = s.trim_start_matches("0x");
+    if s.len() % 2 != 0 {
+        return Err(format!("{} is not hex", s));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| format!("{} is not hex", s)))
+        .collect()
+}
+
+fn flags(writable: Option<bool>, signer: Option<bool>) -> String {
+    let flag = |flag: Option<bool>, c: char| match flag {
+        Some(true) => c,
+        Some(false) => '-',
+        None => '?',
+    };
+    format!("{}{}", flag(writable, 'w'), flag(signer, 's'))
+}
+
+fn show(idl: &Idl) {
+    println!("{} ({}, enum {})", idl.name, idl.source, idl.enum_name);
+    for ix in &idl.instructions {
+        let args: Vec<String> = ix.args.iter().map(|a| format!("{}: {}", a.name, a.ty)).collect();
+        println!(
+            "\n  [{}] {} {{ {} }}  {}",
+            ix.discriminant,
+            ix.name,
+            args.join(", "),
+            ix.docs
+        );
+        match &ix.builder {
+            Some(builder) => println!("      builder: {}()", builder),
+            None => println!("      no builder"),
+        }
+        for (i, account) in ix.accounts.iter().enumerate() {
+            println!(
+                "      ({}) {} {:<24} {}",
+                i + 1,
+                flags(account.writable, account.signer),
+                account.name,
+                account.builder.as_deref().unwrap_or("")
+            );
+        }
+        if ix.remaining_accounts {
+            println!("      ... remaining accounts");
+        }
+    }
+    for layout in &idl.layouts {
+        let size = layout
+            .size
+            .map_or("variable size".to_string(), |s| format!("{} bytes", s));
+        println!("\n  {} ({})", layout.name, size);
+        for field in &layout.fields {
+            let offset = field.offset.map_or("?".to_string(), |o| o.to_string());
+            println!("      {:>4} {}: {}", offset, field.name, field.ty);
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("list") => {
+            for idl in idl::idls() {
+                let names: Vec<&str> = idl.instructions.iter().map(|ix| ix.name.as_str()).collect();
+                println!("{:<8} {}", idl.name, names.join(", "));
+            }
+        }
+        Some("show") => show(&program(args.next())?),
+        Some("dump") => {
+            let dir = PathBuf::from(args.next().unwrap_or_else(|| "target/idl".to_string()));
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            for idl in idl::idls() {
+                let path = dir.join(format!("{}.json", idl.name));
+                let json = serde_json::to_string_pretty(&idl).unwrap();
+                std::fs::write(&path, json).map_err(|e| e.to_string())?;
+                println!("{}", path.display());
+            }
+        }
+        Some("encode") => {
+            let idl = program(args.next())?;
+            let name = args.next().ok_or(USAGE)?;
+            let mut values = Map::new();
+            let mut accounts = vec![];
+            for arg in args {
+                match arg.split_once('=') {
+                    // Numbers, booleans and lists as JSON, anything else (pubkeys) as a string
+                    Some((key, value)) => {
+                        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::from(value));
+                        values.insert(key.to_string(), value);
+                    }
+                    None => {
+                        accounts.push(Pubkey::from_str(&arg).map_err(|_| format!("{} is not a pubkey", arg))?)
+                    }
+                }
+            }
+            if accounts.is_empty() {
+                println!("{}", hex(&idl.encode(&name, &values)?));
+                return Ok(());
+            }
+            let ix = idl.instruction(Pubkey::default(), &name, &values, &accounts)?;
+            println!("{}", hex(&ix.data));
+            for meta in &ix.accounts {
+                println!(
+                    "  {} {}",
+                    flags(Some(meta.is_writable), Some(meta.is_signer)),
+                    meta.pubkey
+                );
+            }
+        }
+        Some("decode") => {
+            let (name, values) = program(args.next()).and_then(|idl| idl.decode(&unhex(args.next())?))?;
+            println!("{} {}", name, serde_json::to_string_pretty(&values).unwrap());
+        }
+        Some("account") => {
+            let idl = program(args.next())?;
+            let layout = args.next().ok_or(USAGE)?;
+            let values = idl.decode_account(&layout, &unhex(args.next())?)?;
+            println!("{}", serde_json::to_string_pretty(&values).unwrap());
+        }
+        Some("check") => {
+            let mut root = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../.."));
+            let mut programs = vec![];
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--root" => root = PathBuf::from(args.next().ok_or(USAGE)?),
+                    _ => programs.push(arg),
+                }
+            }
+            let findings = match programs.is_empty() {
+                true => check::check_all(&root)?,
+                false => {
+                    let mut findings = vec![];
+                    for program in &programs {
+                        findings.extend(check::check(&root, program)?);
+                    }
+                    findings
+                }
+            };
+            let mut last = None;
+            for finding in &findings {
+                let instruction = (&finding.program, &finding.instruction);
+                if last != Some(instruction) {
+                    println!("{} {}", finding.program, finding.instruction);
+                    last = Some(instruction);
+                }
+                println!("  [{}] {}", finding.kind, finding.message);
+            }
+            println!("{} mismatches", findings.len());
+            if !findings.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Some("-h") | Some("--help") => println!("{}", USAGE),
+        _ => return Err(USAGE.to_string()),
+    }
+    Ok(())
+}
+
+pub fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    }
+}
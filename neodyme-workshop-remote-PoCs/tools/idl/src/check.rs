@@ -0,0 +1,331 @@
+//! Compares the three account lists of each instruction: the "Passed accounts" docs of the enum,
+//! the AccountMetas of the builder and the `next_account_info` order of the processor
+use std::{fmt, fs, path::Path};
+
+use crate::{
+    parse::{self, BuilderAccount, DocAccount},
+    processor::{self, Handler, ProcessorAccount},
+    sources::SOURCES,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    Count,
+    Order,
+    Writable,
+    Signer,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self {
+            Kind::Count => "count",
+            Kind::Order => "order",
+            Kind::Writable => "writable",
+            Kind::Signer => "signer",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub program: String,
+    pub instruction: String,
+    pub kind: Kind,
+    pub message: String,
+}
+
+/// The three lists of one instruction, each can be missing
+struct Lists<'a> {
+    docs: &'a [DocAccount],
+    builder: Option<(&'a str, &'a [BuilderAccount], bool)>,
+    processor: Option<&'a Handler>,
+}
+
+/// Checks every program of SOURCES under `root`, the root of the repo
+pub fn check_all(root: &Path) -> Result<Vec<Finding>, String> {
+    let mut findings = vec![];
+    for (name, _) in SOURCES {
+        findings.extend(check(root, name)?);
+    }
+    Ok(findings)
+}
+
+pub fn check(root: &Path, program: &str) -> Result<Vec<Finding>, String> {
+    let (_, dir) = SOURCES
+        .iter()
+        .find(|(name, _)| *name == program)
+        .ok_or(format!("unknown program {}", program))?;
+    let read = |file: &str| {
+        let path = root.join(dir).join("src").join(file);
+        fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))
+    };
+    let lib = read("lib.rs")?;
+    let file = syn::parse_file(&lib).map_err(|e| format!("{}/src/lib.rs: {}", dir, e))?;
+    let ix_enum = parse::instruction_enum(&file).ok_or(format!("{}/src/lib.rs: no instruction enum", dir))?;
+    let enum_name = ix_enum.ident.to_string();
+    let builders = parse::builders(&file, &enum_name);
+    let handlers = processor::handlers(&read("processor.rs")?, &enum_name)
+        .map_err(|e| format!("{}/src/processor.rs: {}", dir, e))?;
+
+    let mut findings = vec![];
+    for variant in &ix_enum.variants {
+        let instruction = variant.ident.to_string();
+        let docs = parse::passed_accounts(&parse::doc_lines(&variant.attrs));
+        let builder = builders.iter().find(|b| b.variant == instruction);
+        let lists = Lists {
+            docs: &docs,
+            builder: builder.map(|b| (b.name.as_str(), b.accounts.as_slice(), b.remaining_accounts)),
+            processor: handlers.iter().find(|h| h.variant == instruction),
+        };
+        let mut report = |kind: Kind, message: String| {
+            findings.push(Finding {
+                program: program.to_string(),
+                instruction: instruction.clone(),
+                kind,
+                message,
+            })
+        };
+        check_counts(&lists, &mut report);
+        check_order(&lists, &mut report);
+        check_flags(&lists, &mut report);
+    }
+    Ok(findings)
+}
+
+fn check_counts(lists: &Lists, report: &mut impl FnMut(Kind, String)) {
+    // Accounts documented as optional may be left out
+    let docs_required = lists
+        .docs
+        .iter()
+        .filter(|d| !d.text.to_lowercase().contains("optional"))
+        .count();
+    if let Some((builder, accounts, _)) = lists.builder {
+        if !lists.docs.is_empty() && (accounts.len() < docs_required || accounts.len() > lists.docs.len()) {
+            report(
+                Kind::Count,
+                format!(
+                    "the docs list {} accounts, {}() passes {}",
+                    lists.docs.len(),
+                    builder,
+                    accounts.len()
+                ),
+            );
+        }
+    }
+    let processor = match lists.processor {
+        Some(processor) => processor,
+        None => return,
+    };
+    let required = processor.accounts.iter().filter(|a| !a.optional).count();
+    // The program a CPI calls has to be passed, even if the processor never takes it
+    let unread = |name: &str| !(processor.invokes && parse::snake_case(name).ends_with("program"));
+    match lists.builder {
+        Some((builder, accounts, _)) => {
+            if accounts.len() < required {
+                report(
+                    Kind::Count,
+                    format!(
+                        "{}() passes {} accounts, the processor takes {}",
+                        builder,
+                        accounts.len(),
+                        required
+                    ),
+                );
+            }
+            if !processor.remaining_accounts {
+                for (i, account) in accounts.iter().enumerate().skip(processor.accounts.len()) {
+                    if unread(&parse::expr_name(&account.expr)) {
+                        report(
+                            Kind::Count,
+                            format!(
+                                "{}() passes ({}) {}, the processor never reads it",
+                                builder,
+                                i + 1,
+                                account.expr
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+        // Without a builder, compare the docs with the processor directly
+        None => {
+            if lists.docs.len() < required {
+                report(
+                    Kind::Count,
+                    format!(
+                        "the docs list {} accounts, the processor takes {}",
+                        lists.docs.len(),
+                        required
+                    ),
+                );
+            }
+            if !processor.remaining_accounts {
+                for (i, doc) in lists.docs.iter().enumerate().skip(processor.accounts.len()) {
+                    if unread(&doc.text) && !doc.text.to_lowercase().contains("optional") {
+                        report(
+                            Kind::Count,
+                            format!(
+                                "the docs list ({}) {}, the processor never reads it",
+                                i + 1,
+                                doc.text
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_order(lists: &Lists, report: &mut impl FnMut(Kind, String)) {
+    let docs: Vec<String> = lists
+        .docs
+        .iter()
+        .map(|d| d.text.split('(').next().unwrap().trim().to_string())
+        .collect();
+    let builder: Option<Vec<String>> = lists
+        .builder
+        .map(|(_, accounts, _)| accounts.iter().map(|a| parse::expr_name(&a.expr)).collect());
+    let processor: Option<Vec<String>> = lists
+        .processor
+        .map(|p| p.accounts.iter().map(|a| a.name.clone()).collect());
+
+    let mut compare = |a_name: &str, a: &[String], b_name: &str, b: &[String]| {
+        for (i, name) in a.iter().enumerate() {
+            if b.get(i).is_some_and(|other| same_account(name, other)) {
+                continue;
+            }
+            // Only an account that is somewhere else in `b`, and not where it belongs in `a` too:
+            // `Target Wallet account` is not misplaced because `wallet_info` is at (1)
+            let misplaced = b.iter().enumerate().position(|(j, other)| {
+                same_account(name, other) && !a.get(j).is_some_and(|at_j| same_account(at_j, other))
+            });
+            if let Some(j) = misplaced {
+                report(
+                    Kind::Order,
+                    format!(
+                        "{} has {} at ({}), {} at ({})",
+                        a_name,
+                        name,
+                        i + 1,
+                        b_name,
+                        j + 1
+                    ),
+                );
+            }
+        }
+    };
+    if let Some(builder) = &builder {
+        compare("the docs", &docs, "the builder", builder);
+    }
+    match (&builder, &processor) {
+        (Some(builder), Some(processor)) => compare("the builder", builder, "the processor", processor),
+        (None, Some(processor)) => compare("the docs", &docs, "the processor", processor),
+        _ => {}
+    }
+}
+
+fn check_flags(lists: &Lists, report: &mut impl FnMut(Kind, String)) {
+    let (builder, accounts) = match lists.builder {
+        Some((builder, accounts, _)) => (builder, accounts),
+        None => return,
+    };
+    let processor: &[ProcessorAccount] = lists.processor.map_or(&[], |p| p.accounts.as_slice());
+
+    for (i, account) in accounts.iter().enumerate() {
+        let position = format!("({}) {}", i + 1, account.expr);
+        let doc = lists.docs.get(i);
+        let used = processor.get(i).filter(|p| p.name != "_");
+
+        if let Some(used) = used {
+            if used.writes && !account.writable {
+                report(
+                    Kind::Writable,
+                    format!(
+                        "{} is written by the processor, {}() passes it read-only",
+                        position, builder
+                    ),
+                );
+            }
+            if account.writable && !used.writes && !used.passed_on {
+                report(
+                    Kind::Writable,
+                    format!("{} is passed writable, the processor never writes it", position),
+                );
+            }
+        }
+
+        let signer = account.signer.unwrap_or(false);
+        if let Some(used) = used {
+            if used.checks_signer && !signer {
+                report(
+                    Kind::Signer,
+                    format!(
+                        "the processor checks that {} signs, {}() does not sign it",
+                        position, builder
+                    ),
+                );
+            }
+            if signer && !used.checks_signer && !used.passed_on {
+                report(
+                    Kind::Signer,
+                    format!("{} signs, nothing checks or uses its signature", position),
+                );
+            }
+        }
+        if let Some(doc) = doc {
+            if doc.signer && !signer {
+                report(
+                    Kind::Signer,
+                    format!(
+                        "the docs say {} must sign, {}() does not sign it",
+                        position, builder
+                    ),
+                );
+            }
+            if signer && !doc.signer {
+                report(
+                    Kind::Signer,
+                    format!("{} signs, the docs don't say it must", position),
+                );
+            }
+            if let (true, Some(used)) = (doc.signer, used) {
+                if !used.checks_signer && !used.passed_on {
+                    report(
+                        Kind::Signer,
+                        format!(
+                            "the docs say {} must sign, the processor never checks it",
+                            position
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Whether two names (doc text, builder expression, processor binding) are the same account:
+/// the words of one are all in the other, leaving out `account`, `info`, `address`...
+/// (`Money Source` and `source_info`, `Rent sysvar` and `sysvar::rent::id()`)
+fn same_account(a: &str, b: &str) -> bool {
+    let words = |name: &str| -> Vec<String> {
+        parse::snake_case(name)
+            .split('_')
+            .filter(|w| {
+                ![
+                    "account", "accounts", "info", "address", "sysvar", "id", "the", "of", "this", "a",
+                ]
+                .contains(w)
+            })
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    a.iter().all(|w| b.contains(w)) || b.iter().all(|w| a.contains(w))
+}
@@ -0,0 +1,265 @@
+//! JSON IDLs of the levels, generated at build time from the instruction enums, their
+//! "Passed accounts" docs and the instruction builders of each crate (see `build.rs`).
+//!
+//! The IDL is enough to build and decode instructions and accounts without the level crate:
+//! `idl::find("level0")?.instruction(program_id, "Withdraw", &args, &accounts)`.
+use std::{convert::TryInto, str::FromStr};
+
+use serde_json::{Map, Value};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+pub mod check;
+pub mod parse;
+pub mod processor;
+pub mod sources;
+pub mod types;
+
+pub use types::{Idl, IdlAccount, IdlField, IdlInstruction, IdlLayout, IdlType};
+
+const IDLS: &str = include_str!(concat!(env!("OUT_DIR"), "/idls.json"));
+
+/// The IDLs of all levels, in level order
+pub fn idls() -> Vec<Idl> {
+    serde_json::from_str(IDLS).expect("generated IDLs")
+}
+
+pub fn find(name: &str) -> Option<Idl> {
+    idls().into_iter().find(|idl| idl.name == name)
+}
+
+impl Idl {
+    pub fn instruction_named(&self, name: &str) -> Result<&IdlInstruction, String> {
+        self.instructions
+            .iter()
+            .find(|ix| ix.name.eq_ignore_ascii_case(name))
+            .ok_or(format!("{} has no instruction {}", self.name, name))
+    }
+
+    pub fn layout(&self, name: &str) -> Result<&IdlLayout, String> {
+        self.layouts
+            .iter()
+            .find(|l| l.name.eq_ignore_ascii_case(name))
+            .ok_or(format!("{} has no layout {}", self.name, name))
+    }
+
+    /// The instruction data: the discriminant, then the borsh encoded args
+    pub fn encode(&self, name: &str, args: &Map<String, Value>) -> Result<Vec<u8>, String> {
+        let ix = self.instruction_named(name)?;
+        let mut data = vec![ix.discriminant];
+        for arg in &ix.args {
+            let value = args
+                .get(&arg.name)
+                .ok_or(format!("missing argument {}", arg.name))?;
+            encode_value(&arg.ty, value, &mut data).map_err(|e| format!("{}: {}", arg.name, e))?;
+        }
+        Ok(data)
+    }
+
+    /// The name and args of an instruction data
+    pub fn decode(&self, data: &[u8]) -> Result<(String, Map<String, Value>), String> {
+        let (discriminant, mut rest) = data.split_first().ok_or("empty instruction data")?;
+        let ix = self
+            .instructions
+            .iter()
+            .find(|ix| ix.discriminant == *discriminant)
+            .ok_or(format!("{} has no instruction {}", self.name, discriminant))?;
+        let args = decode_fields(&ix.args, &mut rest)?;
+        if !rest.is_empty() {
+            return Err(format!("{} trailing bytes after {}", rest.len(), ix.name));
+        }
+        Ok((ix.name.clone(), args))
+    }
+
+    /// The instruction, with the accounts in order and flagged as the builder of the crate does.
+    /// Accounts after the listed ones (`remaining_accounts`) are writable
+    pub fn instruction(
+        &self,
+        program_id: Pubkey,
+        name: &str,
+        args: &Map<String, Value>,
+        accounts: &[Pubkey],
+    ) -> Result<Instruction, String> {
+        let ix = self.instruction_named(name)?;
+        if accounts.len() < ix.accounts.len()
+            || (accounts.len() > ix.accounts.len() && !ix.remaining_accounts)
+        {
+            return Err(format!(
+                "{} takes {} accounts, got {}",
+                ix.name,
+                ix.accounts.len(),
+                accounts.len()
+            ));
+        }
+        let metas = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, pubkey)| {
+                let account = ix.accounts.get(i);
+                let writable = account.is_none_or(|a| a.writable.unwrap_or(true));
+                let signer = account.and_then(|a| a.signer).unwrap_or(false);
+                match writable {
+                    true => AccountMeta::new(*pubkey, signer),
+                    false => AccountMeta::new_readonly(*pubkey, signer),
+                }
+            })
+            .collect();
+        Ok(Instruction {
+            program_id,
+            accounts: metas,
+            data: self.encode(name, args)?,
+        })
+    }
+
+    /// The fields of an account. Trailing bytes (i.e. the rest of an oversized account) are ignored
+    pub fn decode_account(&self, layout: &str, data: &[u8]) -> Result<Map<String, Value>, String> {
+        self.read_account(layout, data).map(|(fields, _)| fields)
+    }
+
+    /// The fields of an account and the number of bytes they take, which is what `deserialize`
+    /// (unlike `try_from_slice`) accepts: a TipPool reads the first 72 bytes of a 73 byte Vault
+    pub fn read_account(&self, layout: &str, data: &[u8]) -> Result<(Map<String, Value>, usize), String> {
+        let layout = self.layout(layout)?;
+        let mut rest = data;
+        let fields = decode_fields(&layout.fields, &mut rest)?;
+        Ok((fields, data.len() - rest.len()))
+    }
+}
+
+fn encode_value(ty: &IdlType, value: &Value, data: &mut Vec<u8>) -> Result<(), String> {
+    match ty {
+        IdlType::Named(name) => match name.as_str() {
+            "bool" => data.push(value.as_bool().ok_or(format!("{} is not a bool", value))? as u8),
+            "f64" => data.extend(
+                value
+                    .as_f64()
+                    .ok_or(format!("{} is not a number", value))?
+                    .to_le_bytes(),
+            ),
+            "publicKey" => {
+                let pubkey = value.as_str().and_then(|s| Pubkey::from_str(s).ok());
+                data.extend(pubkey.ok_or(format!("{} is not a pubkey", value))?.to_bytes())
+            }
+            "string" => {
+                let s = value.as_str().ok_or(format!("{} is not a string", value))?;
+                data.extend((s.len() as u32).to_le_bytes());
+                data.extend(s.as_bytes());
+            }
+            int => {
+                let size = ty.size().ok_or(format!("unsupported type {}", int))?;
+                let v = value
+                    .as_i64()
+                    .map(|v| v as i128)
+                    .or_else(|| value.as_u64().map(|v| v as i128))
+                    .ok_or(format!("{} is not an integer", value))?;
+                let signed = int.starts_with('i');
+                let (min, max) = match (signed, size) {
+                    (true, _) => (-(1i128 << (size * 8 - 1)), (1i128 << (size * 8 - 1)) - 1),
+                    (false, 16) => (0, i128::MAX),
+                    (false, _) => (0, (1i128 << (size * 8)) - 1),
+                };
+                if v < min || v > max {
+                    return Err(format!("{} is not a {}", v, int));
+                }
+                data.extend(&v.to_le_bytes()[..size]);
+            }
+        },
+        IdlType::Vec { vec } => {
+            let items = value.as_array().ok_or(format!("{} is not a list", value))?;
+            data.extend((items.len() as u32).to_le_bytes());
+            for item in items {
+                encode_value(vec, item, data)?;
+            }
+        }
+        IdlType::Array { array: (ty, len) } => {
+            let items = value.as_array().ok_or(format!("{} is not a list", value))?;
+            if items.len() != *len {
+                return Err(format!("expected {} items, got {}", len, items.len()));
+            }
+            for item in items {
+                encode_value(ty, item, data)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn decode_fields(fields: &[IdlField], data: &mut &[u8]) -> Result<Map<String, Value>, String> {
+    let mut values = Map::new();
+    for field in fields {
+        let value = decode_value(&field.ty, data).map_err(|e| format!("{}: {}", field.name, e))?;
+        values.insert(field.name.clone(), value);
+    }
+    Ok(values)
+}
+
+fn take<'a>(data: &mut &'a [u8], n: usize) -> Result<&'a [u8], String> {
+    if data.len() < n {
+        return Err(format!("needs {} bytes, {} left", n, data.len()));
+    }
+    let (bytes, rest) = data.split_at(n);
+    *data = rest;
+    Ok(bytes)
+}
+
+fn decode_value(ty: &IdlType, data: &mut &[u8]) -> Result<Value, String> {
+    Ok(match ty {
+        IdlType::Named(name) => match name.as_str() {
+            "bool" => match take(data, 1)?[0] {
+                0 => Value::from(false),
+                1 => Value::from(true),
+                b => return Err(format!("{} is not a bool", b)),
+            },
+            "f64" => Value::from(f64::from_le_bytes(take(data, 8)?.try_into().unwrap())),
+            "publicKey" => Value::from(Pubkey::new(take(data, 32)?).to_string()),
+            "string" => {
+                let len = u32::from_le_bytes(take(data, 4)?.try_into().unwrap()) as usize;
+                Value::from(String::from_utf8_lossy(take(data, len)?).into_owned())
+            }
+            int => {
+                let size = ty.size().ok_or(format!("unsupported type {}", int))?;
+                let mut bytes = [0u8; 16];
+                bytes[..size].copy_from_slice(take(data, size)?);
+                let signed = int.starts_with('i');
+                if signed && bytes[size - 1] & 0x80 != 0 {
+                    bytes[size..].fill(0xff);
+                }
+                let v = i128::from_le_bytes(bytes);
+                match (signed, size) {
+                    (false, 16) => Value::from((v as u128).to_string()),
+                    (true, 16) => Value::from(v.to_string()),
+                    (false, _) => Value::from(v as u64),
+                    (true, _) => Value::from(v as i64),
+                }
+            }
+        },
+        IdlType::Vec { vec } => {
+            let len = u32::from_le_bytes(take(data, 4)?.try_into().unwrap()) as usize;
+            if len > data.len() {
+                return Err(format!("vec of {} items in {} bytes", len, data.len()));
+            }
+            Value::from(
+                (0..len)
+                    .map(|_| decode_value(vec, data))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+        }
+        IdlType::Array { array: (ty, len) } => Value::from(
+            (0..*len)
+                .map(|_| decode_value(ty, data))
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+    })
+}
+
+impl std::fmt::Display for IdlType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            IdlType::Named(name) => write!(f, "{}", name),
+            IdlType::Vec { vec } => write!(f, "Vec<{}>", vec),
+            IdlType::Array { array: (ty, len) } => write!(f, "[{}; {}]", ty, len),
+        }
+    }
+}
@@ -0,0 +1,371 @@
+//! Reads an IDL out of the `lib.rs` of a level: the instruction enum with its "Passed accounts"
+//! docs, the builder functions and the borsh structs. Shared with build.rs
+use quote::ToTokens;
+use syn::{
+    visit::{self, Visit},
+    Attribute, Expr, Fields, File, GenericArgument, Item, ItemEnum, ItemFn, Lit, Meta, PathArguments, Type,
+};
+
+use crate::types::{Idl, IdlAccount, IdlField, IdlInstruction, IdlLayout, IdlType};
+
+/// An account of the "Passed accounts" list of a variant
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocAccount {
+    pub position: usize,
+    pub text: String,
+    pub signer: bool,
+}
+
+/// An AccountMeta of a builder, in order
+#[derive(Clone, Debug, PartialEq)]
+pub struct BuilderAccount {
+    pub expr: String,
+    pub writable: bool,
+    /// None when the signer flag is not a literal
+    pub signer: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Builder {
+    pub name: String,
+    pub variant: String,
+    pub accounts: Vec<BuilderAccount>,
+    pub remaining_accounts: bool,
+}
+
+pub fn parse_idl(name: &str, source_path: &str, source: &str) -> Result<Idl, String> {
+    let file: File = syn::parse_file(source).map_err(|e| format!("{}: {}", source_path, e))?;
+    let ix_enum = instruction_enum(&file).ok_or(format!("{}: no instruction enum", source_path))?;
+    let builders = builders(&file, &ix_enum.ident.to_string());
+
+    let mut instructions = vec![];
+    for (discriminant, variant) in ix_enum.variants.iter().enumerate() {
+        let variant_name = variant.ident.to_string();
+        let docs = doc_lines(&variant.attrs);
+        let doc_accounts = passed_accounts(&docs);
+        let builder = builders.iter().find(|b| b.variant == variant_name);
+        let args = match &variant.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|f| IdlField {
+                    name: f.ident.as_ref().unwrap().to_string(),
+                    ty: idl_type(&f.ty),
+                    offset: None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        let builder_accounts = builder.map(|b| b.accounts.clone()).unwrap_or_default();
+        let mut accounts = vec![];
+        for i in 0..doc_accounts.len().max(builder_accounts.len()) {
+            let doc = doc_accounts.get(i);
+            let meta = builder_accounts.get(i);
+            let name = match (doc, meta) {
+                (Some(doc), _) => snake_case(doc.text.split(['(', ',']).next().unwrap()),
+                (None, Some(meta)) => snake_case(&expr_name(&meta.expr)),
+                (None, None) => unreachable!(),
+            };
+            accounts.push(IdlAccount {
+                name: unique(&accounts, name),
+                writable: meta.map(|m| m.writable),
+                signer: match (meta.and_then(|m| m.signer), doc) {
+                    (Some(signer), _) => Some(signer),
+                    (None, Some(doc)) if doc.signer => Some(true),
+                    _ => None,
+                },
+                docs: doc.map(|d| d.text.clone()),
+                builder: meta.map(|m| m.expr.clone()),
+            });
+        }
+
+        instructions.push(IdlInstruction {
+            name: variant_name,
+            discriminant: discriminant as u8,
+            docs: docs
+                .iter()
+                .take_while(|line| !line.starts_with("Passed accounts"))
+                .filter(|line| !line.is_empty())
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" "),
+            args,
+            accounts,
+            builder: builder.map(|b| b.name.clone()),
+            remaining_accounts: builder.is_some_and(|b| b.remaining_accounts),
+        });
+    }
+
+    Ok(Idl {
+        name: name.to_string(),
+        source: source_path.to_string(),
+        enum_name: ix_enum.ident.to_string(),
+        instructions,
+        layouts: layouts(&file),
+    })
+}
+
+fn derives_borsh(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|a| a.path.is_ident("derive") && a.tokens.to_string().contains("BorshSerialize"))
+}
+
+/// The borsh enum whose name ends with `Instruction`
+pub fn instruction_enum(file: &File) -> Option<&ItemEnum> {
+    file.items.iter().find_map(|item| match item {
+        Item::Enum(e) if derives_borsh(&e.attrs) && e.ident.to_string().ends_with("Instruction") => Some(e),
+        _ => None,
+    })
+}
+
+pub fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|a| match a.parse_meta() {
+            Ok(Meta::NameValue(nv)) if nv.path.is_ident("doc") => match nv.lit {
+                Lit::Str(s) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// `(1) Wallet account`, `(2) Authority (must sign)` ... after "Passed accounts:"
+pub fn passed_accounts(docs: &[String]) -> Vec<DocAccount> {
+    docs.iter()
+        .skip_while(|line| !line.starts_with("Passed accounts"))
+        .filter_map(|line| {
+            let rest = line.strip_prefix('(')?;
+            let (position, text) = rest.split_once(')')?;
+            let position = position.trim().parse().ok()?;
+            let signer = text.contains("(must sign)");
+            let text = text.replace("(must sign)", "").trim().to_string();
+            Some(DocAccount {
+                position,
+                text,
+                signer,
+            })
+        })
+        .collect()
+}
+
+/// The `pub fn`s returning an Instruction built with `<enum_name>::<Variant>`
+pub fn builders(file: &File, enum_name: &str) -> Vec<Builder> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(f) => builder(f, enum_name),
+            _ => None,
+        })
+        .collect()
+}
+
+fn builder(f: &ItemFn, enum_name: &str) -> Option<Builder> {
+    let returns_instruction = match &f.sig.output {
+        syn::ReturnType::Type(_, ty) => ty.to_token_stream().to_string() == "Instruction",
+        _ => false,
+    };
+    if !returns_instruction {
+        return None;
+    }
+    let mut visitor = BuilderVisitor {
+        enum_name,
+        variant: None,
+        accounts: vec![],
+        remaining_accounts: false,
+        closures: 0,
+    };
+    visitor.visit_block(&f.block);
+    Some(Builder {
+        name: f.sig.ident.to_string(),
+        variant: visitor.variant?,
+        accounts: visitor.accounts,
+        remaining_accounts: visitor.remaining_accounts,
+    })
+}
+
+struct BuilderVisitor<'a> {
+    enum_name: &'a str,
+    variant: Option<String>,
+    accounts: Vec<BuilderAccount>,
+    remaining_accounts: bool,
+    /// AccountMetas made in a closure (`recipients.iter().map(|r| AccountMeta::new(..))`) are not fixed
+    closures: usize,
+}
+
+impl<'a> BuilderVisitor<'a> {
+    fn variant_of(&mut self, path: &syn::Path) {
+        let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        if self.variant.is_none() && segments.len() == 2 && segments[0] == self.enum_name {
+            self.variant = Some(segments[1].clone());
+        }
+    }
+}
+
+impl<'a, 'ast> Visit<'ast> for BuilderVisitor<'a> {
+    fn visit_expr_path(&mut self, e: &'ast syn::ExprPath) {
+        self.variant_of(&e.path);
+        visit::visit_expr_path(self, e);
+    }
+
+    fn visit_expr_struct(&mut self, e: &'ast syn::ExprStruct) {
+        self.variant_of(&e.path);
+        visit::visit_expr_struct(self, e);
+    }
+
+    fn visit_expr_closure(&mut self, e: &'ast syn::ExprClosure) {
+        self.closures += 1;
+        visit::visit_expr_closure(self, e);
+        self.closures -= 1;
+    }
+
+    /// `vec![AccountMeta::new(..), ..]`, syn leaves macro bodies as tokens
+    fn visit_macro(&mut self, m: &'ast syn::Macro) {
+        if m.path.is_ident("vec") {
+            let parser = syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated;
+            if let Ok(exprs) = m.parse_body_with(parser) {
+                for expr in &exprs {
+                    self.visit_expr(expr);
+                }
+            }
+        }
+        visit::visit_macro(self, m);
+    }
+
+    fn visit_expr_method_call(&mut self, e: &'ast syn::ExprMethodCall) {
+        let method = e.method.to_string();
+        if ["extend", "extend_from_slice", "push", "append"].contains(&method.as_str()) {
+            self.remaining_accounts = true;
+        }
+        visit::visit_expr_method_call(self, e);
+    }
+
+    fn visit_expr_call(&mut self, e: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = &*e.func {
+            let path = path.path.to_token_stream().to_string().replace(' ', "");
+            let writable = match path.as_str() {
+                "AccountMeta::new" => Some(true),
+                "AccountMeta::new_readonly" => Some(false),
+                _ => None,
+            };
+            if let (Some(writable), 0) = (writable, self.closures) {
+                let mut args = e.args.iter();
+                let expr = args
+                    .next()
+                    .map(|a| a.to_token_stream().to_string())
+                    .unwrap_or_default();
+                let signer = match args.next() {
+                    Some(Expr::Lit(syn::ExprLit {
+                        lit: Lit::Bool(b), ..
+                    })) => Some(b.value),
+                    _ => None,
+                };
+                self.accounts.push(BuilderAccount {
+                    expr: expr.replace(' ', ""),
+                    writable,
+                    signer,
+                });
+            }
+        }
+        visit::visit_expr_call(self, e);
+    }
+}
+
+/// The borsh structs of the file, with their field offsets
+pub fn layouts(file: &File) -> Vec<IdlLayout> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(s) if derives_borsh(&s.attrs) => {
+                let mut offset = Some(0);
+                let mut fields = vec![];
+                if let Fields::Named(named) = &s.fields {
+                    for f in &named.named {
+                        let ty = idl_type(&f.ty);
+                        fields.push(IdlField {
+                            name: f.ident.as_ref().unwrap().to_string(),
+                            offset,
+                            ty: ty.clone(),
+                        });
+                        offset = match (offset, ty.size()) {
+                            (Some(offset), Some(size)) => Some(offset + size),
+                            _ => None,
+                        };
+                    }
+                }
+                Some(IdlLayout {
+                    name: s.ident.to_string(),
+                    size: offset,
+                    fields,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn idl_type(ty: &Type) -> IdlType {
+    match ty {
+        Type::Path(p) => {
+            let last = p.path.segments.last().unwrap();
+            let name = last.ident.to_string();
+            match (name.as_str(), &last.arguments) {
+                ("Pubkey", _) => IdlType::Named("publicKey".to_string()),
+                ("Vec", PathArguments::AngleBracketed(args)) => match args.args.first() {
+                    Some(GenericArgument::Type(inner)) => IdlType::Vec {
+                        vec: Box::new(idl_type(inner)),
+                    },
+                    _ => IdlType::Named(name),
+                },
+                ("String", _) => IdlType::Named("string".to_string()),
+                _ => IdlType::Named(name),
+            }
+        }
+        Type::Array(a) => {
+            let len = match &a.len {
+                Expr::Lit(syn::ExprLit { lit: Lit::Int(i), .. }) => i.base10_parse().unwrap_or(0),
+                _ => 0,
+            };
+            IdlType::Array {
+                array: (Box::new(idl_type(&a.elem)), len),
+            }
+        }
+        _ => IdlType::Named(ty.to_token_stream().to_string()),
+    }
+}
+
+/// `wallet_address` for `wallet_address`, `system_program` for `system_program::id()`,
+/// `rent` for `sysvar::rent::id()`
+pub fn expr_name(expr: &str) -> String {
+    let expr = expr
+        .trim_start_matches('*')
+        .trim_end_matches("::id()")
+        .trim_end_matches("()");
+    let expr = expr.trim_end_matches(".pubkey");
+    expr.rsplit("::").next().unwrap_or(expr).to_string()
+}
+
+pub fn snake_case(text: &str) -> String {
+    let mut name = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            name.push(c.to_ascii_lowercase());
+        } else if !name.ends_with('_') && !name.is_empty() {
+            name.push('_');
+        }
+    }
+    name.trim_end_matches('_').to_string()
+}
+
+fn unique(accounts: &[IdlAccount], name: String) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while accounts.iter().any(|a| a.name == candidate) {
+        candidate = format!("{}_{}", name, n);
+        n += 1;
+    }
+    candidate
+}
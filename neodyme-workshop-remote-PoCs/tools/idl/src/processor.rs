@@ -0,0 +1,248 @@
+//! Reads what the processor of a level does with its accounts: the `next_account_info` order of
+//! each instruction handler, which accounts it checks `is_signer` on and which ones it writes
+use std::collections::HashSet;
+
+use quote::ToTokens;
+use syn::{
+    visit::{self, Visit},
+    Expr, File, Item, ItemFn, Pat,
+};
+
+/// An account taken with `next_account_info`, in order
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProcessorAccount {
+    /// The binding, i.e. `wallet_info`, or `_` when the result is not bound
+    pub name: String,
+    /// Taken without `?`, i.e. `match next_account_info(..) { Err(_) => return Ok(()), .. }`
+    pub optional: bool,
+    pub checks_signer: bool,
+    /// Lamports or data written in place (`**info.lamports.borrow_mut() -= ..`, `serialize` into the data)
+    pub writes: bool,
+    /// Cloned or passed to another function (a CPI, a helper), which may write it or need its signature
+    pub passed_on: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Handler {
+    pub variant: String,
+    /// The function the `process_instruction` arm calls, or None when the arm does the work itself
+    pub function: Option<String>,
+    pub accounts: Vec<ProcessorAccount>,
+    /// Reads `&accounts[n..]` or the rest of the iterator
+    pub remaining_accounts: bool,
+    /// Makes a CPI, which needs the called program among the accounts without reading it
+    pub invokes: bool,
+}
+
+/// The handlers of the `process_instruction` match on `<enum_name>::<Variant>`
+pub fn handlers(source: &str, enum_name: &str) -> Result<Vec<Handler>, String> {
+    let file: File = syn::parse_file(source).map_err(|e| e.to_string())?;
+    let functions: Vec<&ItemFn> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(f) => Some(f),
+            _ => None,
+        })
+        .collect();
+    let process_instruction = functions
+        .iter()
+        .find(|f| f.sig.ident == "process_instruction")
+        .ok_or("no process_instruction")?;
+
+    let mut arms = ArmVisitor { arms: vec![] };
+    arms.visit_block(&process_instruction.block);
+
+    let mut handlers = vec![];
+    for arm in arms.arms {
+        let variant = arm.pat.to_token_stream().to_string().replace(' ', "");
+        let variant = match variant.strip_prefix(&format!("{}::", enum_name)) {
+            Some(rest) => rest
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .unwrap()
+                .to_string(),
+            None => continue,
+        };
+        let function =
+            called_function(&arm.body).and_then(|name| functions.iter().find(|f| f.sig.ident == name));
+        let handler = match function {
+            Some(f) => handler(
+                variant,
+                Some(f.sig.ident.to_string()),
+                &f.block.to_token_stream().to_string(),
+                |v| v.visit_block(&f.block),
+            ),
+            None => handler(variant, None, &arm.body.to_token_stream().to_string(), |v| {
+                v.visit_expr(&arm.body)
+            }),
+        };
+        handlers.push(handler);
+    }
+    Ok(handlers)
+}
+
+struct ArmVisitor {
+    arms: Vec<syn::Arm>,
+}
+
+impl<'ast> Visit<'ast> for ArmVisitor {
+    fn visit_expr_match(&mut self, m: &'ast syn::ExprMatch) {
+        if self.arms.is_empty() {
+            self.arms = m.arms.clone();
+        }
+    }
+}
+
+/// `deposit` for an arm `=> deposit(program_id, accounts, amount)` (or the same in a block)
+fn called_function(body: &Expr) -> Option<String> {
+    match body {
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(p) if p.path.segments.len() == 1 => Some(p.path.segments[0].ident.to_string()),
+            _ => None,
+        },
+        Expr::Block(b) => match b.block.stmts.as_slice() {
+            [syn::Stmt::Expr(e)] => called_function(e),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn handler<F: FnOnce(&mut AccountVisitor)>(
+    variant: String,
+    function: Option<String>,
+    body: &str,
+    visit: F,
+) -> Handler {
+    let mut visitor = AccountVisitor::default();
+    visit(&mut visitor);
+    let body = normalize(body);
+    // so that `lamports.borrow_mut() == ..` is not taken for an assignment
+    let assignments = body.replace("==", "~~");
+    let AccountVisitor { accounts, passed } = visitor;
+    let accounts = accounts
+        .into_iter()
+        .map(|(name, optional)| {
+            let used = |pattern: &str| uses(&body, pattern, &name);
+            let writes = name != "_"
+                && ([
+                    "&mut&mutX.data.borrow_mut()",
+                    "X.try_borrow_mut_",
+                    "X.realloc(",
+                    "X.assign(",
+                ]
+                .iter()
+                .any(|p| used(p))
+                    || ["-=", "+=", "="]
+                        .iter()
+                        .any(|op| uses(&assignments, &format!("X.lamports.borrow_mut(){}", op), &name)));
+            ProcessorAccount {
+                checks_signer: name != "_" && used("X.is_signer"),
+                writes,
+                passed_on: name != "_" && (used("X.clone()") || passed.contains(&name)),
+                optional,
+                name,
+            }
+        })
+        .collect();
+    Handler {
+        variant,
+        function,
+        accounts,
+        remaining_accounts: body.contains("accounts[") && body.contains("..]")
+            || body.contains("account_info_iter.as_slice()")
+            || body.contains("inaccount_info_iter"),
+        invokes: body.contains("invoke(") || body.contains("invoke_signed("),
+    }
+}
+
+/// The pattern with `X` replaced by the binding, not as the end of a longer name
+/// (`info.is_signer` is not in `wallet_info.is_signer`)
+fn uses(body: &str, pattern: &str, name: &str) -> bool {
+    let pattern = pattern.replace('X', name);
+    body.match_indices(&pattern).any(|(i, _)| {
+        body[..i]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+    })
+}
+
+/// The token string without whitespace, `(*info).lamports` as `info.lamports` and
+/// `(*info.data).borrow_mut()` as `info.data.borrow_mut()`
+fn normalize(tokens: &str) -> String {
+    let mut s: String = tokens.split_whitespace().collect();
+    while let Some(start) = s.find("(*") {
+        let end = match s[start..].find(')') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let inner = s[start + 2..end].to_string();
+        if !inner.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+            // not a plain place expression, leave it and look further
+            s.replace_range(start..start + 2, "( *");
+            continue;
+        }
+        s.replace_range(start..=end, &inner);
+    }
+    s.replace("( *", "(*")
+}
+
+#[derive(Default)]
+struct AccountVisitor {
+    /// (binding, optional) in order
+    accounts: Vec<(String, bool)>,
+    /// Bindings passed as an argument, `f(info)` or `f(&info)`
+    passed: HashSet<String>,
+}
+
+fn is_next_account_info(expr: &Expr) -> bool {
+    match expr {
+        Expr::Call(call) => {
+            matches!(&*call.func, Expr::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "next_account_info"))
+        }
+        _ => false,
+    }
+}
+
+impl<'ast> Visit<'ast> for AccountVisitor {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Some((_, init)) = &local.init {
+            let name = match &local.pat {
+                Pat::Ident(p) => p.ident.to_string(),
+                _ => "_".to_string(),
+            };
+            let required = matches!(&**init, Expr::Try(t) if is_next_account_info(&t.expr));
+            let optional = match &**init {
+                Expr::Match(m) => is_next_account_info(&m.expr),
+                Expr::MethodCall(m) => is_next_account_info(&m.receiver),
+                _ => false,
+            };
+            if required || optional {
+                self.accounts.push((name, optional));
+                return;
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if is_next_account_info(&Expr::Call(call.clone())) {
+            self.accounts.push(("_".to_string(), false));
+            return;
+        }
+        for arg in &call.args {
+            let arg = match arg {
+                Expr::Reference(r) => &*r.expr,
+                arg => arg,
+            };
+            if let Expr::Path(p) = arg {
+                if let Some(ident) = p.path.get_ident() {
+                    self.passed.insert(ident.to_string());
+                }
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+}
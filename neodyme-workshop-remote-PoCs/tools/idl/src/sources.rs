@@ -0,0 +1,21 @@
+//! Where the crate of each program is. Shared with build.rs
+
+/// (program, folder of its crate), relative to the root of the repo
+pub const SOURCES: &[(&str, &str)] = &[
+    ("level0", "level0-hack/level0"),
+    ("level1", "level1-hack/level1"),
+    ("level2", "level2-hack/level2"),
+    ("level3", "level3-hack/level3"),
+    ("level4", "level4-hack/program"),
+    ("level5", "level5-hack/level5"),
+    ("level6", "level6-hack/level6"),
+    ("level7", "level7-hack/level7"),
+    ("level8", "level8-hack/level8"),
+    ("level9", "level9-hack/level9"),
+    ("level10", "level10-hack/level10"),
+    ("amm", "level10-hack/amm"),
+    ("level11", "level11-hack/level11"),
+    ("level12", "level12-hack/level12"),
+    ("level13", "level13-hack/level13"),
+    ("level14", "level14-hack/level14"),
+];
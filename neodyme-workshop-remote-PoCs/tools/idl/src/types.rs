@@ -0,0 +1,86 @@
+//! The IDL, as written to JSON. Shared with build.rs, so nothing from solana here
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Idl {
+    pub name: String,
+    /// The source the IDL was generated from, relative to the root of the repo
+    pub source: String,
+    /// Name of the instruction enum
+    pub enum_name: String,
+    pub instructions: Vec<IdlInstruction>,
+    /// The borsh structs of the program, i.e. the Wallet account
+    pub layouts: Vec<IdlLayout>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdlInstruction {
+    pub name: String,
+    /// First byte of the instruction data, the index of the variant
+    pub discriminant: u8,
+    pub docs: String,
+    pub args: Vec<IdlField>,
+    pub accounts: Vec<IdlAccount>,
+    /// The builder function of the crate, if any
+    pub builder: Option<String>,
+    /// The builder adds a variable number of accounts after these ones
+    pub remaining_accounts: bool,
+}
+
+/// One passed account. `docs` comes from the "Passed accounts" list of the enum, `builder` is the
+/// pubkey expression of the AccountMeta in the builder; either can be missing
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdlAccount {
+    pub name: String,
+    /// None when the builder doesn't say, or there is no builder
+    pub writable: Option<bool>,
+    pub signer: Option<bool>,
+    pub docs: Option<String>,
+    pub builder: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdlLayout {
+    pub name: String,
+    /// None when a field has a variable size
+    pub size: Option<usize>,
+    pub fields: Vec<IdlField>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+    /// Offset in the layout, None after a variable size field (and for instruction args)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+/// Borsh types: `"u64"`, `"publicKey"`, `{ "vec": "publicKey" }`, `{ "array": ["u8", 32] }`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum IdlType {
+    Named(String),
+    Vec { vec: Box<IdlType> },
+    Array { array: (Box<IdlType>, usize) },
+}
+
+impl IdlType {
+    /// Size in bytes, None for variable size types
+    pub fn size(&self) -> Option<usize> {
+        match self {
+            IdlType::Named(name) => match name.as_str() {
+                "bool" | "u8" | "i8" => Some(1),
+                "u16" | "i16" => Some(2),
+                "u32" | "i32" => Some(4),
+                "u64" | "i64" | "f64" => Some(8),
+                "u128" | "i128" => Some(16),
+                "publicKey" => Some(32),
+                _ => None,
+            },
+            IdlType::Vec { .. } => None,
+            IdlType::Array { array: (ty, len) } => ty.size().map(|size| size * len),
+        }
+    }
+}
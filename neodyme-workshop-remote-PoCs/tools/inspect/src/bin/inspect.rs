@@ -0,0 +1,171 @@
+use std::{path::PathBuf, str::FromStr};
+
+use owo_colors::OwoColorize;
+use serde_json::Value;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, native_token::lamports_to_sol, pubkey::Pubkey};
+
+use actors::Actors;
+use inspect::{identify, load_fixture, program_ids, Identification};
+
+const USAGE: &str = "usage: inspect [--validator | --url <RPC_URL>] [--fixture <ACCOUNT.json>]... [--root <REPO_ROOT>] [PUBKEY | ROLE]...";
+
+fn show_value(actors: &Actors, value: &Value) -> String {
+    match value {
+        Value::String(s) => match Pubkey::from_str(s) {
+            Ok(pubkey) => actors.tag(&pubkey),
+            Err(_) => s.clone(),
+        },
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(|item| show_value(actors, item)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        value => value.to_string(),
+    }
+}
+
+fn show(actors: &Actors, program_ids: &[(String, Pubkey)], pubkey: &Pubkey, account: &Account) {
+    println!("{}", actors.tag(pubkey).bold());
+    println!(
+        "  owner {}, {} SOL, {} bytes",
+        actors.tag(&account.owner),
+        lamports_to_sol(account.lamports),
+        account.data.len()
+    );
+    let identification = identify(account, program_ids);
+    let (exact, prefixes) = identification.split(&account.data);
+    match &identification {
+        Identification::System => println!("  {}", "system account".green()),
+        Identification::Program => println!("  {}", "program".green()),
+        Identification::Empty => println!("  {}", "no data".green()),
+        Identification::Decoded { owner, .. } => {
+            if owner.is_none() {
+                println!(
+                    "  {}",
+                    "the owner is not a level program, trying the layouts of every level".yellow()
+                );
+            }
+            match exact.first() {
+                Some(first) => {
+                    println!("  {} {}", first.program.green(), first.layout.green().bold());
+                    for (name, value) in &first.fields {
+                        println!("    {}: {}", name, show_value(actors, value));
+                    }
+                }
+                None => println!("  {}", "no layout takes the whole data".red()),
+            }
+            for other in exact.iter().skip(1) {
+                println!(
+                    "  {} also reads as {} {}",
+                    "ambiguous:".yellow().bold(),
+                    other.program,
+                    other.layout
+                );
+            }
+            // `T::deserialize(&mut &data[..])` in the processors reads a prefix and ignores the rest,
+            // which matters to the program the data is for (any one, when no layout fits)
+            let relevant = |program: &str| exact.is_empty() || exact.iter().any(|c| c.program == program);
+            for prefix in prefixes.iter().filter(|p| relevant(&p.program)) {
+                println!(
+                    "  {} the first {} of {} bytes read as {} {}, which `deserialize` accepts",
+                    "ambiguous:".yellow().bold(),
+                    prefix.used,
+                    account.data.len(),
+                    prefix.program,
+                    prefix.layout
+                );
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut url = None;
+    let mut fixtures = vec![];
+    let mut root = PathBuf::from(".");
+    let mut names = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--validator" => url = Some("http://localhost:8899/".to_string()),
+            "--url" => url = Some(args.next().expect(USAGE)),
+            "--fixture" => fixtures.push(PathBuf::from(args.next().expect(USAGE))),
+            "--root" => root = PathBuf::from(args.next().expect(USAGE)),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => names.push(arg),
+        }
+    }
+    if names.is_empty() && fixtures.is_empty() {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    }
+
+    let mut actors = Actors::new();
+    actors.load_all();
+    let program_ids = program_ids(&root);
+    for (name, id) in &program_ids {
+        actors.label(*id, name);
+    }
+    for name in ["system_program", "spl_token", "bpf_loader", "bpf_loader_upgradeable"] {
+        actors.label(levels::well_known(name).unwrap(), name);
+    }
+
+    let mut fixture_accounts = vec![];
+    for path in &fixtures {
+        match load_fixture(path) {
+            Ok(accounts) => fixture_accounts.extend(accounts),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        }
+    }
+    // Without pubkeys, every account of the fixtures
+    let pubkeys: Vec<Pubkey> = match names.is_empty() {
+        true => fixture_accounts.iter().map(|(pubkey, _)| *pubkey).collect(),
+        false => names
+            .iter()
+            .map(|name| match Pubkey::from_str(name) {
+                Ok(pubkey) => pubkey,
+                Err(_) => actors.find(name).unwrap_or_else(|| {
+                    eprintln!("{} is neither a pubkey nor an actor of {}", name, actors.dir().display());
+                    std::process::exit(2);
+                }),
+            })
+            .collect(),
+    };
+
+    // Accounts of the fixtures first, the validator for the rest (the local one by default)
+    let client = match (&url, fixtures.is_empty()) {
+        (Some(url), _) => Some(url.clone()),
+        (None, true) => Some("http://localhost:8899/".to_string()),
+        (None, false) => None,
+    }
+    .map(|url| RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()));
+
+    let mut all_found = true;
+    for pubkey in &pubkeys {
+        let account = match fixture_accounts.iter().find(|(key, _)| key == pubkey) {
+            Some((_, account)) => Ok(account.clone()),
+            None => match &client {
+                Some(client) => client.get_account(pubkey).map_err(|e| e.to_string()),
+                None => Err("not in the fixtures".to_string()),
+            },
+        };
+        match account {
+            Ok(account) => show(&actors, &program_ids, pubkey, &account),
+            Err(e) => {
+                println!("{} {}", actors.tag(pubkey).bold(), e.red());
+                all_found = false;
+            }
+        }
+        println!();
+    }
+    if !all_found {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,224 @@
+//! Tells which structure of which level an account holds, from its owner, size and layout, and
+//! decodes it. Accounts come from a validator or from fixtures (`solana account --output json`)
+use std::{path::Path, str::FromStr};
+
+use serde_json::{Map, Value};
+use solana_sdk::{
+    account::Account,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+    system_program,
+};
+use spl_token::state::{Account as TokenAccount, Mint};
+
+/// The vendored spl-token level4 deploys next to its program, see `level4-hack/cicdh.sh`
+const MYSPL: (&str, &str) = ("myspl", "level4-hack");
+/// Token-2022, next to spl-token the token programs level4 allowlists
+pub const TOKEN_2022: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// The program ids of the levels, read from the keypairs under `root` (the root of the repo) that
+/// `cargo build-bpf` wrote. Programs that were never built are left out
+pub fn program_ids(root: &Path) -> Vec<(String, Pubkey)> {
+    let paths = levels::PROGRAMS
+        .iter()
+        .map(|program| (program.name, program.keypair_path()))
+        .chain(std::iter::once((MYSPL.0, format!("{}/target/so/{}-keypair.json", MYSPL.1, MYSPL.0))));
+    paths
+        .filter_map(|(name, path)| {
+            read_keypair_file(root.join(path))
+                .ok()
+                .map(|keypair| (name.to_string(), keypair.pubkey()))
+        })
+        .collect()
+}
+
+/// The token programs level4 accepts with `patched`: spl-token and Token-2022
+pub fn token_program_allowlist() -> [Pubkey; 2] {
+    [spl_token::id(), Pubkey::from_str(TOKEN_2022).unwrap()]
+}
+
+/// The programs that own token accounts: the allowlist and `myspl` when it is known
+pub fn token_programs(program_ids: &[(String, Pubkey)]) -> Vec<Pubkey> {
+    let mut programs = token_program_allowlist().to_vec();
+    programs.extend(program_ids.iter().filter(|(name, _)| name == MYSPL.0).map(|(_, id)| *id));
+    programs
+}
+
+/// The accounts of a fixture: the JSON of `solana account <PUBKEY> --output json`, or a list of them
+pub fn load_fixture(path: &Path) -> Result<Vec<(Pubkey, Account)>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let json: Value = serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let entries = match json {
+        Value::Array(entries) => entries,
+        entry => vec![entry],
+    };
+    entries
+        .iter()
+        .map(|entry| parse_fixture_entry(entry).map_err(|e| format!("{}: {}", path.display(), e)))
+        .collect()
+}
+
+fn parse_fixture_entry(entry: &Value) -> Result<(Pubkey, Account), String> {
+    let pubkey = |value: &Value| -> Result<Pubkey, String> {
+        let s = value.as_str().ok_or(format!("{} is not a pubkey", value))?;
+        Pubkey::from_str(s).map_err(|_| format!("{} is not a pubkey", s))
+    };
+    let address = pubkey(&entry["pubkey"])?;
+    let account = &entry["account"];
+    let data = match &account["data"] {
+        Value::Array(parts) if parts.get(1).and_then(Value::as_str) == Some("base64") => {
+            base64::decode(parts[0].as_str().unwrap_or_default()).map_err(|e| e.to_string())?
+        }
+        data => return Err(format!("data {} is not [\"<base64>\", \"base64\"]", data)),
+    };
+    Ok((
+        address,
+        Account {
+            lamports: account["lamports"].as_u64().ok_or("no lamports")?,
+            data,
+            owner: pubkey(&account["owner"])?,
+            executable: account["executable"].as_bool().unwrap_or(false),
+            rent_epoch: account["rentEpoch"].as_u64().unwrap_or(0),
+        },
+    ))
+}
+
+/// One way to read the data of an account
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    /// The level, or `spl_token`
+    pub program: String,
+    pub layout: String,
+    pub fields: Map<String, Value>,
+    /// Bytes the fields take, less than the data for a prefix read
+    pub used: usize,
+}
+
+impl Candidate {
+    /// The fields take the whole data, as `try_from_slice` wants
+    pub fn exact(&self, data: &[u8]) -> bool {
+        self.used == data.len()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Identification {
+    /// Owned by the system program
+    System,
+    /// An executable account
+    Program,
+    /// No data to decode
+    Empty,
+    /// Every layout the data reads as. `owner` is the program name when the owner is a level (or
+    /// a token program), and only its layouts are tried then
+    Decoded {
+        owner: Option<String>,
+        candidates: Vec<Candidate>,
+    },
+}
+
+impl Identification {
+    /// The candidates that take the whole data, then the ones that only read a prefix
+    pub fn split<'a>(&'a self, data: &[u8]) -> (Vec<&'a Candidate>, Vec<&'a Candidate>) {
+        match self {
+            Identification::Decoded { candidates, .. } => candidates.iter().partition(|c| c.exact(data)),
+            _ => (vec![], vec![]),
+        }
+    }
+}
+
+/// What the account holds. With an owner that is not a level (i.e. `bpf_loader`, which owns the
+/// accounts the PoCs craft), every layout of every level is tried
+pub fn identify(account: &Account, program_ids: &[(String, Pubkey)]) -> Identification {
+    if account.executable {
+        return Identification::Program;
+    }
+    if account.owner == system_program::id() {
+        return Identification::System;
+    }
+    if account.data.is_empty() {
+        return Identification::Empty;
+    }
+    if token_programs(program_ids).contains(&account.owner) {
+        return Identification::Decoded {
+            owner: Some(name_of(&account.owner, program_ids).unwrap_or_else(|| "spl_token".to_string())),
+            candidates: token_candidates(&account.data),
+        };
+    }
+    let owner = name_of(&account.owner, program_ids);
+    let idls: Vec<idl::Idl> = idl::idls()
+        .into_iter()
+        .filter(|idl| owner.as_ref().is_none_or(|owner| &idl.name == owner))
+        .collect();
+    let mut candidates = vec![];
+    for idl in &idls {
+        for layout in &idl.layouts {
+            if let Ok((fields, used)) = idl.read_account(&layout.name, &account.data) {
+                candidates.push(Candidate {
+                    program: idl.name.clone(),
+                    layout: layout.name.clone(),
+                    fields,
+                    used,
+                });
+            }
+        }
+    }
+    if owner.is_none() {
+        candidates.extend(token_candidates(&account.data));
+    }
+    Identification::Decoded { owner, candidates }
+}
+
+fn name_of(pubkey: &Pubkey, program_ids: &[(String, Pubkey)]) -> Option<String> {
+    program_ids.iter().find(|(_, id)| id == pubkey).map(|(name, _)| name.clone())
+}
+
+/// SPL `Account` (165 bytes) or `Mint` (82 bytes). Token-2022 accounts with extensions are longer,
+/// with the account type at byte 165
+fn token_candidates(data: &[u8]) -> Vec<Candidate> {
+    let candidate = |layout: &str, fields: Map<String, Value>, used: usize| Candidate {
+        program: "spl_token".to_string(),
+        layout: layout.to_string(),
+        fields,
+        used,
+    };
+    let extended = data.len() > TokenAccount::LEN;
+    let mut candidates = vec![];
+    if data.len() == TokenAccount::LEN || (extended && data[TokenAccount::LEN] == 2) {
+        if let Ok(account) = TokenAccount::unpack_unchecked(&data[..TokenAccount::LEN]) {
+            candidates.push(candidate("Account", token_account_fields(&account), data.len()));
+        }
+    }
+    if data.len() == Mint::LEN || (extended && data[TokenAccount::LEN] == 1) {
+        if let Ok(mint) = Mint::unpack_unchecked(&data[..Mint::LEN]) {
+            candidates.push(candidate("Mint", mint_fields(&mint), data.len()));
+        }
+    }
+    candidates
+}
+
+fn token_account_fields(account: &TokenAccount) -> Map<String, Value> {
+    let option = |key: Option<Pubkey>| key.map_or(Value::Null, |key| Value::from(key.to_string()));
+    let mut fields = Map::new();
+    fields.insert("mint".into(), account.mint.to_string().into());
+    fields.insert("owner".into(), account.owner.to_string().into());
+    fields.insert("amount".into(), account.amount.into());
+    fields.insert("delegate".into(), option(account.delegate.into()));
+    fields.insert("state".into(), format!("{:?}", account.state).into());
+    fields.insert("is_native".into(), Option::<u64>::from(account.is_native).into());
+    fields.insert("delegated_amount".into(), account.delegated_amount.into());
+    fields.insert("close_authority".into(), option(account.close_authority.into()));
+    fields
+}
+
+fn mint_fields(mint: &Mint) -> Map<String, Value> {
+    let option = |key: Option<Pubkey>| key.map_or(Value::Null, |key| Value::from(key.to_string()));
+    let mut fields = Map::new();
+    fields.insert("mint_authority".into(), option(mint.mint_authority.into()));
+    fields.insert("supply".into(), mint.supply.into());
+    fields.insert("decimals".into(), mint.decimals.into());
+    fields.insert("is_initialized".into(), mint.is_initialized.into());
+    fields.insert("freeze_authority".into(), option(mint.freeze_authority.into()));
+    fields
+}
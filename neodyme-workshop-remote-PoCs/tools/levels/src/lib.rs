@@ -0,0 +1,193 @@
+//! Every level program in one place: where it lives, and how to build and read its
+//! instructions by name, for the tools that work across levels
+use solana_program::pubkey::Pubkey;
+
+mod value;
+pub use value::{Args, FromValue, Value};
+
+/// Generates `INSTRUCTIONS`, `encode` and `decode` for an instruction enum,
+/// listing every variant with the names of its fields
+macro_rules! instructions {
+    ($ix:ident { $($variant:ident $({ $($field:ident),* })?),* $(,)? }) => {
+        use borsh::{BorshDeserialize, BorshSerialize};
+
+        pub const INSTRUCTIONS: &[&str] = &[$(stringify!($variant)),*];
+
+        pub fn encode(name: &str, _args: &$crate::Args) -> Result<Vec<u8>, String> {
+            let ix = match name {
+                $(stringify!($variant) => $ix::$variant $({ $($field: _args.get(stringify!($field))?),* })?,)*
+                _ => return Err(format!("unknown instruction {}", name)),
+            };
+            Ok(ix.try_to_vec().unwrap())
+        }
+
+        pub fn decode(data: &[u8]) -> Option<String> {
+            $ix::try_from_slice(data).ok().map(|ix| format!("{:?}", ix))
+        }
+    };
+}
+
+pub mod programs {
+    pub mod level0 {
+        use ::level0::WalletInstruction as Ix;
+        instructions!(Ix { Initialize, Deposit { amount }, Withdraw { amount } });
+    }
+    pub mod level1 {
+        use ::level1::WalletInstruction as Ix;
+        instructions!(Ix { Initialize, Deposit { amount }, Withdraw { amount } });
+    }
+    pub mod level2 {
+        use ::level2::WalletInstruction as Ix;
+        instructions!(Ix { Initialize, Deposit { amount }, Withdraw { amount } });
+    }
+    pub mod level3 {
+        use ::level3::TipInstruction as Ix;
+        instructions!(Ix {
+            Initialize { seed, fee, fee_recipient },
+            CreatePool,
+            Tip { amount },
+            Withdraw { amount },
+        });
+    }
+    pub mod level4 {
+        // level4 is built against solana-program 1.14 and can't be linked with the others,
+        // so we use the same Structure created in the Smart Contract
+        #[derive(Debug, borsh_derive::BorshDeserialize, borsh_derive::BorshSerialize)]
+        pub enum WalletInstruction {
+            Initialize,
+            Deposit { amount: u64 },
+            Withdraw { amount: u64 },
+        }
+        use WalletInstruction as Ix;
+        instructions!(Ix { Initialize, Deposit { amount }, Withdraw { amount } });
+    }
+    pub mod level5 {
+        use ::level5::WalletInstruction as Ix;
+        instructions!(Ix {
+            Initialize,
+            Deposit { amount },
+            Withdraw { amount },
+            Transfer { amount },
+        });
+    }
+    pub mod level6 {
+        use ::level6::RewardInstruction as Ix;
+        instructions!(Ix { Initialize { reward }, Register { bump }, Claim });
+    }
+    pub mod level7 {
+        use ::level7::VoucherInstruction as Ix;
+        instructions!(Ix { Initialize, Issue { amount }, Close });
+    }
+    pub mod level8 {
+        use ::level8::BridgeInstruction as Ix;
+        instructions!(Ix { Initialize { guardian }, Claim { amount } });
+    }
+    pub mod level9 {
+        use ::level9::VaultInstruction as Ix;
+        instructions!(Ix { Initialize, OpenPosition, Deposit { amount }, Withdraw { shares } });
+    }
+    pub mod level10 {
+        use ::level10::LendingInstruction as Ix;
+        instructions!(Ix {
+            Initialize,
+            OpenObligation,
+            DepositCollateral { lamports },
+            Borrow { amount },
+        });
+    }
+    pub mod amm {
+        use ::amm::AmmInstruction as Ix;
+        instructions!(Ix {
+            InitializePool,
+            SwapSolForToken { lamports },
+            SwapTokenForSol { amount },
+        });
+    }
+    pub mod level11 {
+        use ::level11::VaultInstruction as Ix;
+        instructions!(Ix { Initialize, Deposit { amount }, Withdraw { amount } });
+    }
+    pub mod level12 {
+        use ::level12::PayrollInstruction as Ix;
+        instructions!(Ix { Initialize { salary, recipients }, Pay });
+    }
+    pub mod level13 {
+        use ::level13::VaultInstruction as Ix;
+        instructions!(Ix { Initialize, Deposit { amount }, Withdraw { amount } });
+    }
+    pub mod level14 {
+        use ::level14::FeeInstruction as Ix;
+        instructions!(Ix { SetConfig { treasury }, Pay { amount }, Sweep });
+    }
+}
+
+pub struct Program {
+    /// Name of the crate, and of `target/so/<name>.so` / `<name>-keypair.json`
+    pub name: &'static str,
+    /// Folder of the level, relative to the root of the repo
+    pub dir: &'static str,
+    pub instructions: &'static [&'static str],
+    pub encode: fn(&str, &Args) -> Result<Vec<u8>, String>,
+    pub decode: fn(&[u8]) -> Option<String>,
+}
+
+impl Program {
+    /// The keypair `cargo build-bpf` writes next to the program, relative to the root of the repo
+    pub fn keypair_path(&self) -> String {
+        format!("{}/target/so/{}-keypair.json", self.dir, self.name)
+    }
+
+    pub fn so_path(&self) -> String {
+        format!("{}/target/so/{}.so", self.dir, self.name)
+    }
+}
+
+macro_rules! program {
+    ($name:ident, $dir:expr) => {
+        Program {
+            name: stringify!($name),
+            dir: $dir,
+            instructions: programs::$name::INSTRUCTIONS,
+            encode: programs::$name::encode,
+            decode: programs::$name::decode,
+        }
+    };
+}
+
+pub const PROGRAMS: &[Program] = &[
+    program!(level0, "level0-hack"),
+    program!(level1, "level1-hack"),
+    program!(level2, "level2-hack"),
+    program!(level3, "level3-hack"),
+    program!(level4, "level4-hack"),
+    program!(level5, "level5-hack"),
+    program!(level6, "level6-hack"),
+    program!(level7, "level7-hack"),
+    program!(level8, "level8-hack"),
+    program!(level9, "level9-hack"),
+    program!(level10, "level10-hack"),
+    program!(amm, "level10-hack"),
+    program!(level11, "level11-hack"),
+    program!(level12, "level12-hack"),
+    program!(level13, "level13-hack"),
+    program!(level14, "level14-hack"),
+];
+
+pub fn find(name: &str) -> Option<&'static Program> {
+    PROGRAMS.iter().find(|program| program.name == name)
+}
+
+/// Sysvars and native programs the levels take as accounts
+pub fn well_known(name: &str) -> Option<Pubkey> {
+    use solana_program::{bpf_loader, bpf_loader_upgradeable, system_program, sysvar};
+    match name {
+        "system_program" => Some(system_program::id()),
+        "rent" => Some(sysvar::rent::id()),
+        "clock" => Some(sysvar::clock::id()),
+        "instructions" => Some(sysvar::instructions::id()),
+        "spl_token" => Some(spl_token::id()),
+        "bpf_loader" => Some(bpf_loader::id()),
+        "bpf_loader_upgradeable" => Some(bpf_loader_upgradeable::id()),
+        _ => None,
+    }
+}
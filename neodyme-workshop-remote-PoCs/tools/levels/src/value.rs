@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use solana_program::pubkey::Pubkey;
+
+/// An instruction argument, before it is converted to the field type of the enum
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Int(i128),
+    Float(f64),
+    Pubkey(Pubkey),
+    List(Vec<Value>),
+}
+
+/// Named instruction arguments, i.e. `amount` of `Withdraw { amount }`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Args(BTreeMap<String, Value>);
+
+impl Args {
+    pub fn new() -> Self {
+        Args(BTreeMap::new())
+    }
+
+    pub fn insert(&mut self, name: &str, value: Value) {
+        self.0.insert(name.to_string(), value);
+    }
+
+    pub fn get<T: FromValue>(&self, name: &str) -> Result<T, String> {
+        match self.0.get(name) {
+            Some(value) => T::from_value(value).map_err(|e| format!("{}: {}", name, e)),
+            None => Err(format!("missing argument {}", name)),
+        }
+    }
+}
+
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Result<Self, String>;
+}
+
+impl FromValue for u64 {
+    /// Negative values wrap around, so `-n` reaches the program as `u64::MAX - n + 1`
+    /// (what the level2 PoC does with its i64 copy of `Withdraw`)
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match *value {
+            Value::Int(v) if v >= i64::MIN as i128 && v <= u64::MAX as i128 => Ok(v as u64),
+            _ => Err(format!("{:?} is not a u64", value)),
+        }
+    }
+}
+
+impl FromValue for u8 {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match *value {
+            Value::Int(v) if v >= 0 && v <= u8::MAX as i128 => Ok(v as u8),
+            _ => Err(format!("{:?} is not a u8", value)),
+        }
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match *value {
+            Value::Float(v) => Ok(v),
+            Value::Int(v) => Ok(v as f64),
+            _ => Err(format!("{:?} is not a f64", value)),
+        }
+    }
+}
+
+impl FromValue for Pubkey {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match *value {
+            Value::Pubkey(v) => Ok(v),
+            _ => Err(format!("{:?} is not a Pubkey", value)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::List(values) => values.iter().map(T::from_value).collect(),
+            _ => Err(format!("{:?} is not a list", value)),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! The conversions of the instruction arguments to the field types
+use levels::{Args, FromValue, Value};
+
+#[test]
+fn u64_wraps_negative_values() {
+    assert_eq!(u64::from_value(&Value::Int(-1)), Ok(u64::MAX));
+    assert_eq!(u64::from_value(&Value::Int(-100)), Ok(u64::MAX - 99));
+    // What the level2 PoC sends: the i64 it builds reaches the program as this u64
+    assert_eq!(u64::from_value(&Value::Int(i64::MIN as i128)), Ok(i64::MIN as u64));
+    assert!(u64::from_value(&Value::Int(i64::MIN as i128 - 1)).is_err());
+}
+
+#[test]
+fn u64_range() {
+    assert_eq!(u64::from_value(&Value::Int(0)), Ok(0));
+    assert_eq!(u64::from_value(&Value::Int(u64::MAX as i128)), Ok(u64::MAX));
+    assert!(u64::from_value(&Value::Int(u64::MAX as i128 + 1)).is_err());
+    assert!(u64::from_value(&Value::Float(1.0)).is_err());
+}
+
+#[test]
+fn args_name_the_field() {
+    let mut args = Args::new();
+    args.insert("amount", Value::Int(-1));
+    assert_eq!(args.get::<u64>("amount"), Ok(u64::MAX));
+    assert_eq!(args.get::<u8>("amount"), Err("amount: Int(-1) is not a u8".to_string()));
+    assert_eq!(args.get::<u64>("fee"), Err("missing argument fee".to_string()));
+}
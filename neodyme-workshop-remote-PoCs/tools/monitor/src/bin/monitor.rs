@@ -0,0 +1,379 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    str::FromStr,
+    sync::mpsc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{json, Map, Value};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    pubsub_client::PubsubClient,
+    rpc_client::RpcClient,
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig, RpcTransactionLogsConfig,
+        RpcTransactionLogsFilter,
+    },
+    rpc_response::RpcLogsResponse,
+};
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature,
+};
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+
+use actors::Actors;
+use inspect::{load_fixture, program_ids, token_program_allowlist};
+use monitor::{invocations, load_transactions, rules, Detector, RULES};
+
+const USAGE: &str = "usage: monitor [--validator | --url <RPC_URL>] [--ws-url <WS_URL>] [--root <REPO_ROOT>]
+               [--program-id <PUBKEY>] [--replay <TRANSACTIONS.json> [--fixture <ACCOUNTS.json>]...] [LEVEL]...";
+
+/// A level being watched
+struct Watched {
+    level: String,
+    program_id: Pubkey,
+}
+
+enum Event {
+    Logs(RpcLogsResponse),
+    Account { watched: usize, pubkey: Pubkey, account: Account, slot: u64 },
+}
+
+/// Owners and lamports of the accounts seen so far
+#[derive(Default)]
+struct Accounts {
+    owners: HashMap<Pubkey, Pubkey>,
+    lamports: HashMap<Pubkey, u64>,
+}
+
+/// The websocket of a validator, from its RPC URL: the port after the RPC one, like the CLI
+fn ws_url(url: &str) -> String {
+    let ws = url.replacen("https://", "wss://", 1).replacen("http://", "ws://", 1);
+    let (scheme, rest) = ws.split_once("://").unwrap_or(("ws", &ws));
+    let (host, path) = rest.split_once('/').map_or((rest, ""), |(host, path)| (host, path));
+    match host.rsplit_once(':').and_then(|(name, port)| Some((name, port.parse::<u16>().ok()?))) {
+        Some((name, port)) => format!("{}://{}:{}/{}", scheme, name, port + 1, path),
+        None => ws,
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// `{"<pubkey>": "hacker"}` for the accounts the actor registry knows
+fn labels(actors: &Actors, pubkeys: &[Pubkey]) -> Value {
+    let mut labels = Map::new();
+    for pubkey in pubkeys {
+        let name = actors.name(pubkey);
+        if name != pubkey.to_string() {
+            labels.insert(pubkey.to_string(), name.into());
+        }
+    }
+    Value::Object(labels)
+}
+
+/// Prints an alert for every exploit signature in the transaction
+fn check_transaction(
+    watched: &[Watched],
+    detector: &mut Detector,
+    actors: &Actors,
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<(), String> {
+    let signature = match &tx.transaction.transaction.decode() {
+        Some(transaction) => transaction.signatures.first().map(|s| s.to_string()),
+        None => None,
+    };
+    let meta = tx.transaction.meta.as_ref();
+    for invocation in invocations(tx)? {
+        for w in watched.iter().filter(|w| w.program_id == invocation.program) {
+            if let Some(finding) = detector.check(&w.level, &w.program_id, &invocation) {
+                let alert = json!({
+                    "time": now(),
+                    "severity": "high",
+                    "level": w.level,
+                    "program": w.program_id.to_string(),
+                    "rule": finding.rule,
+                    "signature": signature,
+                    "slot": tx.slot,
+                    "failed": meta.is_some_and(|meta| meta.err.is_some()),
+                    "cpi": invocation.inner,
+                    "instruction": finding.instruction,
+                    "accounts": invocation.accounts.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+                    "actors": labels(actors, &invocation.accounts),
+                    "message": finding.message,
+                    "logs": meta.and_then(|meta| meta.log_messages.clone()),
+                });
+                println!("{}", alert);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The confirmed transaction, which can take a moment to show up after its logs
+fn fetch_transaction(client: &RpcClient, signature: &str) -> Result<EncodedConfirmedTransactionWithStatusMeta, String> {
+    let signature = Signature::from_str(signature).map_err(|e| e.to_string())?;
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let mut attempts = 0;
+    loop {
+        match client.get_transaction_with_config(&signature, config) {
+            Ok(tx) => return Ok(tx),
+            Err(_) if attempts < 20 => {
+                attempts += 1;
+                std::thread::sleep(Duration::from_millis(250));
+            }
+            Err(e) => return Err(format!("{}: {}", signature, e)),
+        }
+    }
+}
+
+fn main() {
+    let mut url = "http://localhost:8899/".to_string();
+    let mut ws = None;
+    let mut root = PathBuf::from(".");
+    let mut program_id = None;
+    let mut replay = None;
+    let mut fixtures = vec![];
+    let mut levels = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--validator" => url = "http://localhost:8899/".to_string(),
+            "--url" => url = args.next().expect(USAGE),
+            "--ws-url" => ws = Some(args.next().expect(USAGE)),
+            "--root" => root = PathBuf::from(args.next().expect(USAGE)),
+            "--program-id" => program_id = Some(Pubkey::from_str(&args.next().expect(USAGE)).expect(USAGE)),
+            "--replay" => replay = Some(PathBuf::from(args.next().expect(USAGE))),
+            "--fixture" => fixtures.push(PathBuf::from(args.next().expect(USAGE))),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => levels.push(arg),
+        }
+    }
+    if program_id.is_some() && levels.len() != 1 {
+        eprintln!("--program-id goes with a single level");
+        std::process::exit(2);
+    }
+
+    let mut actors = Actors::new();
+    actors.load_all();
+    let program_ids = program_ids(&root);
+    for (name, id) in &program_ids {
+        actors.label(*id, name);
+    }
+    // Not inspect::token_programs, which has myspl when it was built: passing it is the exploit
+    let token_programs = token_program_allowlist();
+    // Every level with a rule that was built, by default
+    if levels.is_empty() {
+        levels = RULES
+            .iter()
+            .map(|(level, _, _)| level.to_string())
+            .filter(|level| program_ids.iter().any(|(name, _)| name == level))
+            .collect();
+        levels.dedup();
+    }
+    let mut watched = vec![];
+    for level in &levels {
+        if rules(level).next().is_none() {
+            eprintln!("no rule for {}, the monitor knows {:?}", level, RULES.iter().map(|r| r.0).collect::<Vec<_>>());
+            std::process::exit(2);
+        }
+        match program_id.or_else(|| program_ids.iter().find(|(name, _)| name == level).map(|(_, id)| *id)) {
+            Some(program_id) => watched.push(Watched { level: level.clone(), program_id }),
+            None => {
+                eprintln!("{} was not built, pass its --program-id", level);
+                std::process::exit(2);
+            }
+        }
+    }
+    if watched.is_empty() {
+        eprintln!("no level was built under {}, pass a LEVEL and its --program-id", root.display());
+        std::process::exit(2);
+    }
+
+    let client = RpcClient::new_with_commitment(url.clone(), CommitmentConfig::confirmed());
+    let mut accounts = Accounts::default();
+    for path in &fixtures {
+        match load_fixture(path) {
+            Ok(fixture) => accounts.owners.extend(fixture.iter().map(|(pubkey, a)| (*pubkey, a.owner))),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    // Replay: the transactions of a file, the owners from the fixtures
+    if let Some(replay) = replay {
+        let transactions = match load_transactions(&replay) {
+            Ok(transactions) => transactions,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        };
+        let owners = accounts.owners;
+        let mut owner_of = |pubkey: &Pubkey| owners.get(pubkey).copied();
+        let mut detector = Detector {
+            token_programs: &token_programs,
+            owner_of: &mut owner_of,
+        };
+        for tx in &transactions {
+            if let Err(e) = check_transaction(&watched, &mut detector, &actors, tx) {
+                eprintln!("{}", e);
+            }
+        }
+        return;
+    }
+
+    // The accounts of the programs as they are now, to compare the changes with
+    for w in &watched {
+        match client.get_program_accounts(&w.program_id) {
+            Ok(program_accounts) => {
+                for (pubkey, account) in program_accounts {
+                    accounts.owners.insert(pubkey, account.owner);
+                    accounts.lamports.insert(pubkey, account.lamports);
+                }
+            }
+            Err(e) => {
+                eprintln!("{} {}: {}", w.level, w.program_id, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let ws = ws.unwrap_or_else(|| ws_url(&url));
+    let (sender, receiver) = mpsc::channel();
+    for (i, w) in watched.iter().enumerate() {
+        let logs = PubsubClient::logs_subscribe(
+            &ws,
+            RpcTransactionLogsFilter::Mentions(vec![w.program_id.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        );
+        let program = PubsubClient::program_subscribe(
+            &ws,
+            &w.program_id,
+            Some(RpcProgramAccountsConfig {
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            }),
+        );
+        let ((logs, logs_receiver), (program, program_receiver)) = match (logs, program) {
+            (Ok(logs), Ok(program)) => (logs, program),
+            (Err(e), _) | (_, Err(e)) => {
+                eprintln!("{}: {}", ws, e);
+                std::process::exit(1);
+            }
+        };
+        let sender_logs = sender.clone();
+        std::thread::spawn(move || {
+            // The subscription lives as long as its receiver is read
+            let _logs = logs;
+            for response in logs_receiver {
+                if sender_logs.send(Event::Logs(response.value)).is_err() {
+                    break;
+                }
+            }
+        });
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            let _program = program;
+            for response in program_receiver {
+                let keyed = response.value;
+                let (pubkey, account) = match (Pubkey::from_str(&keyed.pubkey), keyed.account.decode()) {
+                    (Ok(pubkey), Some(account)) => (pubkey, account),
+                    _ => continue,
+                };
+                let event = Event::Account {
+                    watched: i,
+                    pubkey,
+                    account,
+                    slot: response.context.slot,
+                };
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    eprintln!(
+        "watching {} over {}",
+        watched.iter().map(|w| w.level.as_str()).collect::<Vec<_>>().join(", "),
+        ws
+    );
+
+    let mut seen = HashSet::new();
+    for event in receiver {
+        match event {
+            Event::Logs(logs) => {
+                // A transaction calling two watched levels comes once per level
+                if !seen.insert(logs.signature.clone()) {
+                    continue;
+                }
+                let tx = match fetch_transaction(&client, &logs.signature) {
+                    Ok(tx) => tx,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        continue;
+                    }
+                };
+                let owners = &mut accounts.owners;
+                let mut owner_of = |pubkey: &Pubkey| {
+                    if let Some(owner) = owners.get(pubkey) {
+                        return Some(*owner);
+                    }
+                    let owner = client.get_account(pubkey).ok()?.owner;
+                    owners.insert(*pubkey, owner);
+                    Some(owner)
+                };
+                let mut detector = Detector {
+                    token_programs: &token_programs,
+                    owner_of: &mut owner_of,
+                };
+                if let Err(e) = check_transaction(&watched, &mut detector, &actors, &tx) {
+                    eprintln!("{}", e);
+                }
+            }
+            Event::Account {
+                watched: i,
+                pubkey,
+                account,
+                slot,
+            } => {
+                accounts.owners.insert(pubkey, account.owner);
+                let before = accounts.lamports.insert(pubkey, account.lamports);
+                if let Some(before) = before.filter(|before| *before > account.lamports) {
+                    let alert = json!({
+                        "time": now(),
+                        "severity": "info",
+                        "level": watched[i].level,
+                        "program": watched[i].program_id.to_string(),
+                        "rule": "outflow",
+                        "slot": slot,
+                        "account": pubkey.to_string(),
+                        "actors": labels(&actors, &[pubkey]),
+                        "lamports_before": before,
+                        "lamports_after": account.lamports,
+                        "message": format!("{} lost {} lamports", pubkey, before - account.lamports),
+                    });
+                    println!("{}", alert);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Recognizes the exploits of the levels in confirmed transactions, by decoding every instruction
+//! sent to a level (CPIs included) with its instruction enum
+use std::{path::Path, str::FromStr};
+
+use borsh::BorshDeserialize;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiInstruction};
+
+/// One instruction of a transaction, top level or inner
+#[derive(Clone, Debug, PartialEq)]
+pub struct Invocation {
+    pub program: Pubkey,
+    pub accounts: Vec<Pubkey>,
+    /// The lamports of each account before the transaction, in the order of `accounts`
+    pub pre_balances: Vec<u64>,
+    pub data: Vec<u8>,
+    /// Called through a CPI
+    pub inner: bool,
+}
+
+/// Every instruction of the transaction, the inner ones right after the instruction invoking them.
+/// Needs the transaction encoded as base64 (or base58), not as JSON
+pub fn invocations(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Result<Vec<Invocation>, String> {
+    let transaction = tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or("the transaction is not binary encoded")?;
+    let meta = tx.transaction.meta.as_ref().ok_or("the transaction has no status meta")?;
+
+    // Static keys, then the ones loaded from lookup tables
+    let mut keys = transaction.message.static_account_keys().to_vec();
+    if let Some(loaded) = &meta.loaded_addresses {
+        for key in loaded.writable.iter().chain(&loaded.readonly) {
+            keys.push(Pubkey::from_str(key).map_err(|e| e.to_string())?);
+        }
+    }
+    let invocation = |program_index: u8, accounts: &[u8], data: Vec<u8>, inner: bool| -> Option<Invocation> {
+        let account = |index: &u8| keys.get(*index as usize).copied();
+        Some(Invocation {
+            program: account(&program_index)?,
+            accounts: accounts.iter().map(account).collect::<Option<_>>()?,
+            pre_balances: accounts
+                .iter()
+                .map(|index| meta.pre_balances.get(*index as usize).copied().unwrap_or(0))
+                .collect(),
+            data,
+            inner,
+        })
+    };
+
+    let mut invocations = vec![];
+    for (i, instruction) in transaction.message.instructions().iter().enumerate() {
+        invocations.extend(invocation(
+            instruction.program_id_index,
+            &instruction.accounts,
+            instruction.data.clone(),
+            false,
+        ));
+        let inner = meta.inner_instructions.iter().flatten().filter(|inner| inner.index as usize == i);
+        for instruction in inner.flat_map(|inner| &inner.instructions) {
+            if let UiInstruction::Compiled(compiled) = instruction {
+                let data = bs58::decode(&compiled.data).into_vec().map_err(|e| e.to_string())?;
+                invocations.extend(invocation(compiled.program_id_index, &compiled.accounts, data, true));
+            }
+        }
+    }
+    Ok(invocations)
+}
+
+/// getTransaction results (`{"slot": .., "transaction": ["<base64>", "base64"], "meta": ..}`), or a
+/// list of them
+pub fn load_transactions(path: &Path) -> Result<Vec<EncodedConfirmedTransactionWithStatusMeta>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let json: Value = serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let entries = match json {
+        Value::Array(entries) => entries,
+        entry => vec![entry],
+    };
+    entries
+        .into_iter()
+        .map(|entry| serde_json::from_value(entry).map_err(|e| format!("{}: {}", path.display(), e)))
+        .collect()
+}
+
+/// The exploits the monitor knows: (level, rule, what it means)
+pub const RULES: &[(&str, &str, &str)] = &[
+    (
+        "level0",
+        "forged_wallet",
+        "Withdraw with a Wallet that is not owned by level0",
+    ),
+    (
+        "level2",
+        "huge_withdraw",
+        "Withdraw of a negative amount (as i64), or of more than the Wallet holds",
+    ),
+    (
+        "level4",
+        "fake_token_program",
+        "Withdraw with a program that is not a token program in the token program slot",
+    ),
+];
+
+pub fn rules(level: &str) -> impl Iterator<Item = &'static (&'static str, &'static str, &'static str)> + '_ {
+    RULES.iter().filter(move |(name, _, _)| *name == level)
+}
+
+/// An exploit signature in an instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub rule: &'static str,
+    /// The decoded instruction, i.e. `Withdraw { amount: 1000 }`
+    pub instruction: String,
+    pub message: String,
+}
+
+/// What the detection of a level needs to know
+pub struct Detector<'a> {
+    /// The token programs a level4 Withdraw may pass, see `inspect::token_program_allowlist`
+    pub token_programs: &'a [Pubkey],
+    /// The owner of an account, None when it does not exist
+    pub owner_of: &'a mut dyn FnMut(&Pubkey) -> Option<Pubkey>,
+}
+
+impl<'a> Detector<'a> {
+    /// Checks an instruction sent to `level`, deployed at `program_id`. The data is read with
+    /// `deserialize`, the way the processors do
+    pub fn check(&mut self, level: &str, program_id: &Pubkey, invocation: &Invocation) -> Option<Finding> {
+        if invocation.program != *program_id {
+            return None;
+        }
+        let data = &mut &invocation.data[..];
+        match level {
+            "level0" => match level0::WalletInstruction::deserialize(data).ok()? {
+                ix @ level0::WalletInstruction::Withdraw { .. } => {
+                    let wallet = invocation.accounts.first()?;
+                    let owner = (self.owner_of)(wallet);
+                    if owner == Some(*program_id) {
+                        return None;
+                    }
+                    Some(Finding {
+                        rule: "forged_wallet",
+                        instruction: format!("{:?}", ix),
+                        message: match owner {
+                            Some(owner) => format!("the Wallet {} is owned by {}", wallet, owner),
+                            None => format!("the Wallet {} does not exist", wallet),
+                        },
+                    })
+                }
+                _ => None,
+            },
+            "level2" => match level2::WalletInstruction::deserialize(data).ok()? {
+                level2::WalletInstruction::Withdraw { amount } => {
+                    let wallet = invocation.accounts.first()?;
+                    let held = invocation.pre_balances.first().copied().unwrap_or(0);
+                    let message = match (amount as i64) < 0 {
+                        true => format!("the amount is {} as i64", amount as i64),
+                        false if amount > held => {
+                            format!("the Wallet {} only held {} lamports", wallet, held)
+                        }
+                        false => return None,
+                    };
+                    Some(Finding {
+                        rule: "huge_withdraw",
+                        instruction: format!("{:?}", level2::WalletInstruction::Withdraw { amount }),
+                        message,
+                    })
+                }
+                _ => None,
+            },
+            "level4" => {
+                use levels::programs::level4::WalletInstruction;
+                match WalletInstruction::deserialize(data).ok()? {
+                    ix @ WalletInstruction::Withdraw { .. } => {
+                        let token_program = invocation.accounts.get(5)?;
+                        if self.token_programs.contains(token_program) {
+                            return None;
+                        }
+                        Some(Finding {
+                            rule: "fake_token_program",
+                            instruction: format!("{:?}", ix),
+                            message: format!("{} is passed as the token program", token_program),
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
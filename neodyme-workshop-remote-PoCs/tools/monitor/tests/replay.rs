@@ -0,0 +1,83 @@
+//! The monitor rules against recorded transactions: one fixture per rule, and `benign.json` with
+//! the same instructions done right, which must not raise anything.
+//!
+//! The fixtures are getTransaction results (like `monitor --replay` takes) with made up keys: the
+//! levels are deployed at `[0x10; 32]` (level0), `[0x12; 32]` (level2) and `[0x14; 32]` (level4),
+//! `owners.json` has the owners of the level0 Wallets. The level4 Withdraw is a CPI of a program
+//! of the hacker, and passes the keypair of `[0x31; 32]` as the token program
+use std::{collections::HashMap, path::Path};
+
+use inspect::{load_fixture, program_ids, token_program_allowlist, token_programs};
+use monitor::{invocations, load_transactions, Detector};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{keypair_from_seed, write_keypair_file, Signer},
+};
+
+const WATCHED: [(&str, [u8; 32]); 3] = [("level0", [0x10; 32]), ("level2", [0x12; 32]), ("level4", [0x14; 32])];
+
+/// (level, rule) of every finding in the transactions of `fixture`, in order
+fn replay(fixture: &str) -> Vec<(&'static str, &'static str)> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let owners: HashMap<Pubkey, Pubkey> = load_fixture(&fixtures.join("owners.json"))
+        .unwrap()
+        .into_iter()
+        .map(|(pubkey, account)| (pubkey, account.owner))
+        .collect();
+    let mut owner_of = |pubkey: &Pubkey| owners.get(pubkey).copied();
+    // like the binary
+    let token_programs = token_program_allowlist();
+    let mut detector = Detector {
+        token_programs: &token_programs,
+        owner_of: &mut owner_of,
+    };
+
+    let mut findings = vec![];
+    for tx in load_transactions(&fixtures.join(fixture)).unwrap() {
+        for invocation in invocations(&tx).unwrap() {
+            for (level, program_id) in WATCHED {
+                if let Some(finding) = detector.check(level, &Pubkey::new_from_array(program_id), &invocation) {
+                    findings.push((level, finding.rule));
+                }
+            }
+        }
+    }
+    findings
+}
+
+#[test]
+fn forged_wallet() {
+    assert_eq!(replay("forged_wallet.json"), [("level0", "forged_wallet")]);
+}
+
+#[test]
+fn huge_withdraw() {
+    // a negative amount, then more than the Wallet holds (failed, still an attempt)
+    assert_eq!(replay("huge_withdraw.json"), [("level2", "huge_withdraw"), ("level2", "huge_withdraw")]);
+}
+
+#[test]
+fn fake_token_program() {
+    assert_eq!(replay("fake_token_program.json"), [("level4", "fake_token_program")]);
+}
+
+#[test]
+fn fake_token_program_with_myspl_built() {
+    // the fake token program of the fixture is myspl, built next to level4
+    let myspl = keypair_from_seed(&[0x31; 32]).unwrap();
+    let root = std::env::temp_dir().join(format!("monitor-replay-{}", std::process::id()));
+    let so = root.join("level4-hack/target/so");
+    std::fs::create_dir_all(&so).unwrap();
+    write_keypair_file(&myspl, so.join("myspl-keypair.json")).unwrap();
+    let program_ids = program_ids(&root);
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert!(token_programs(&program_ids).contains(&myspl.pubkey()));
+    assert_eq!(replay("fake_token_program.json"), [("level4", "fake_token_program")]);
+}
+
+#[test]
+fn benign() {
+    let findings = replay("benign.json");
+    assert!(findings.is_empty(), "{:?}", findings);
+}
@@ -0,0 +1,239 @@
+use std::{path::PathBuf, str::FromStr};
+
+use owo_colors::OwoColorize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, native_token::lamports_to_sol, pubkey::Pubkey};
+
+use actors::Actors;
+use inspect::{identify, program_ids, Identification};
+use pda::{level3_vaults, of_level, PDAS};
+
+const USAGE: &str = "usage: pda [--validator | --url <RPC_URL> | --offline] [--root <REPO_ROOT>] [--program-id <PUBKEY>]
+           [--actor <ROLE | PUBKEY>]... [--mint <PUBKEY>] [LEVEL]...";
+
+/// Fetches the accounts (None for the missing ones), nothing offline
+fn fetch(client: Option<&RpcClient>, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>, String> {
+    let client = match client {
+        Some(client) => client,
+        None => return Ok(vec![None; pubkeys.len()]),
+    };
+    let mut accounts = vec![];
+    // getMultipleAccounts takes up to 100 accounts
+    for chunk in pubkeys.chunks(100) {
+        accounts.extend(client.get_multiple_accounts(chunk).map_err(|e| e.to_string())?);
+    }
+    Ok(accounts)
+}
+
+/// `exists: level0 Wallet, 0.00133632 SOL`
+fn describe(account: Option<&Account>, program_ids: &[(String, Pubkey)], offline: bool) -> String {
+    let account = match (account, offline) {
+        (_, true) => return String::new(),
+        (None, false) => return "missing".dimmed().to_string(),
+        (Some(account), _) => account,
+    };
+    let what = match identify(account, program_ids) {
+        Identification::System => "system account".to_string(),
+        Identification::Program => "program".to_string(),
+        Identification::Empty => "no data".to_string(),
+        identification @ Identification::Decoded { .. } => match identification.split(&account.data).0.first() {
+            Some(candidate) => format!("{} {}", candidate.program, candidate.layout),
+            None => "unknown layout".to_string(),
+        },
+    };
+    format!("{} {}, {} SOL", "exists:".green(), what, lamports_to_sol(account.lamports))
+}
+
+/// `0-2, 4, 6-9`
+fn ranges(seeds: &[u8]) -> String {
+    let mut ranges: Vec<(u8, u8)> = vec![];
+    for &seed in seeds {
+        match ranges.last_mut() {
+            Some((_, end)) if *end as u16 + 1 == seed as u16 => *end = seed,
+            _ => ranges.push((seed, seed)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn level3(
+    program_id: &Pubkey,
+    client: Option<&RpcClient>,
+    program_ids: &[(String, Pubkey)],
+    actors: &Actors,
+) -> Result<(), String> {
+    let vaults = level3_vaults(program_id);
+    let valid: Vec<(u8, Pubkey)> = vaults.iter().filter_map(|(seed, vault)| vault.map(|v| (*seed, v))).collect();
+    let on_curve: Vec<u8> = vaults.iter().filter(|(_, vault)| vault.is_none()).map(|(seed, _)| *seed).collect();
+    println!("  vault [[seed]], create_program_address with any seed the initializer picks");
+    println!("    off curve ({} of 256): {}", valid.len(), ranges(&valid.iter().map(|(s, _)| *s).collect::<Vec<_>>()));
+    println!("    on curve, Initialize panics ({}): {}", on_curve.len(), ranges(&on_curve));
+
+    if client.is_none() {
+        return Ok(());
+    }
+    let pubkeys: Vec<Pubkey> = valid.iter().map(|(_, vault)| *vault).collect();
+    let accounts = fetch(client, &pubkeys)?;
+    let mut initialized = 0;
+    for ((seed, vault), account) in valid.iter().zip(&accounts) {
+        let account = match account {
+            Some(account) => account,
+            None => continue,
+        };
+        initialized += 1;
+        println!("    seed {:>3} {} {}", seed, vault, describe(Some(account), program_ids, false));
+        if let Some(candidate) = identify(account, program_ids).split(&account.data).0.first() {
+            for (name, value) in &candidate.fields {
+                let value = match value.as_str().and_then(|s| Pubkey::from_str(s).ok()) {
+                    Some(pubkey) => actors.tag(&pubkey),
+                    None => value.to_string(),
+                };
+                println!("             {}: {}", name, value);
+            }
+        }
+    }
+    println!("    {} initialized vaults", initialized);
+    Ok(())
+}
+
+fn main() {
+    let mut url = Some("http://localhost:8899/".to_string());
+    let mut root = PathBuf::from(".");
+    let mut program_id = None;
+    let mut actor_names = vec![];
+    let mut mint = None;
+    let mut levels = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--validator" => url = Some("http://localhost:8899/".to_string()),
+            "--url" => url = Some(args.next().expect(USAGE)),
+            "--offline" => url = None,
+            "--root" => root = PathBuf::from(args.next().expect(USAGE)),
+            "--program-id" => program_id = Some(Pubkey::from_str(&args.next().expect(USAGE)).expect(USAGE)),
+            "--actor" => actor_names.push(args.next().expect(USAGE)),
+            "--mint" => mint = Some(Pubkey::from_str(&args.next().expect(USAGE)).expect(USAGE)),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => levels.push(arg),
+        }
+    }
+    if levels.is_empty() {
+        levels = vec!["level3".to_string()];
+        for pda in PDAS {
+            if !levels.iter().any(|level| level == pda.level) {
+                levels.push(pda.level.to_string());
+            }
+        }
+        levels.sort_by_key(|level| level.trim_start_matches("level").parse::<u32>().unwrap_or(10));
+    }
+    if program_id.is_some() && levels.len() != 1 {
+        eprintln!("--program-id goes with a single level");
+        std::process::exit(2);
+    }
+    let mut actors = Actors::new();
+    actors.load_all();
+    // By default the hacker and the victim, those of them a PoC already made
+    if actor_names.is_empty() {
+        actor_names = ["hacker", "victim"]
+            .iter()
+            .filter(|role| actors.find(role).is_some())
+            .map(|role| role.to_string())
+            .collect();
+    }
+    let actor_keys: Vec<(String, Pubkey)> = actor_names
+        .iter()
+        .map(|name| match Pubkey::from_str(name) {
+            Ok(pubkey) => (actors.name(&pubkey), pubkey),
+            Err(_) => match actors.find(name) {
+                Some(pubkey) => (name.clone(), pubkey),
+                None => {
+                    eprintln!("{} is neither a pubkey nor an actor of {}", name, actors.dir().display());
+                    std::process::exit(2);
+                }
+            },
+        })
+        .collect();
+    let program_ids = program_ids(&root);
+    for (name, id) in &program_ids {
+        actors.label(*id, name);
+    }
+    let client = url
+        .as_ref()
+        .map(|url| RpcClient::new_with_commitment(url.clone(), CommitmentConfig::confirmed()));
+    let offline = client.is_none();
+
+    let mut failed = false;
+    for level in &levels {
+        let id = program_id.or_else(|| program_ids.iter().find(|(name, _)| name == level).map(|(_, id)| *id));
+        let id = match id {
+            Some(id) => id,
+            None => {
+                println!("{} {}\n", level.bold(), "not built, pass its --program-id".red());
+                failed = true;
+                continue;
+            }
+        };
+        println!("{} {}", level.bold(), id);
+        if level == "level3" {
+            if let Err(e) = level3(&id, client.as_ref(), &program_ids, &actors) {
+                println!("  {}", e.red());
+                failed = true;
+            }
+            println!();
+            continue;
+        }
+
+        // (name, seeds, for whom, address and bump)
+        let mut rows = vec![];
+        for pda in of_level(level) {
+            let whom: Vec<(String, Pubkey)> = match pda.per_actor() {
+                true => actor_keys.clone(),
+                false => vec![(String::new(), Pubkey::default())],
+            };
+            for (actor, actor_key) in whom {
+                match pda.derive(&id, &actor_key, mint.as_ref()) {
+                    Some(derived) => rows.push((pda, actor, derived)),
+                    None => println!("  {} {} {}", pda.name, pda.describe(), "needs --mint".yellow()),
+                }
+            }
+        }
+        let pubkeys: Vec<Pubkey> = rows.iter().map(|(_, _, (address, _))| *address).collect();
+        let accounts = match fetch(client.as_ref(), &pubkeys) {
+            Ok(accounts) => accounts,
+            Err(e) => {
+                println!("  {}\n", e.red());
+                failed = true;
+                continue;
+            }
+        };
+        for ((pda, actor, (address, bump)), account) in rows.iter().zip(&accounts) {
+            let name = match actor.is_empty() {
+                true => pda.name.to_string(),
+                false => format!("{} ({})", pda.name, actor),
+            };
+            println!(
+                "  {:<24} {:<22} {} bump {:>3}  {}",
+                name,
+                pda.describe(),
+                address,
+                bump,
+                describe(account.as_ref(), &program_ids, offline)
+            );
+        }
+        println!();
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,119 @@
+//! Every PDA of the levels in one table, with the seeds the programs use: `[authority]` and
+//! `[authority, "VAULT"]` in level0, `[owner, mint]` and `[]` in level4, `[[seed]]` in level3...
+use solana_sdk::{bpf_loader_upgradeable, pubkey::Pubkey};
+
+/// One seed of a PDA
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Seed {
+    Str(&'static str),
+    /// The pubkey of the actor the PDA is for (an authority, an owner...)
+    Actor,
+    /// The pubkey of a token mint, see `Inputs::mint`
+    Mint,
+    /// The program id itself, for PDAs of another program (level14 ProgramData)
+    ProgramId,
+}
+
+/// A PDA of a level
+pub struct Pda {
+    pub level: &'static str,
+    pub name: &'static str,
+    pub seeds: &'static [Seed],
+    /// Derived under this program instead of the level (`bpf_loader_upgradeable` for ProgramData)
+    pub under: Option<fn() -> Pubkey>,
+    /// The function of the level crate deriving it
+    pub helper: &'static str,
+}
+
+impl Pda {
+    pub fn per_actor(&self) -> bool {
+        self.seeds.contains(&Seed::Actor)
+    }
+
+    pub fn needs_mint(&self) -> bool {
+        self.seeds.contains(&Seed::Mint)
+    }
+
+    /// `["VAULT", actor]`
+    pub fn describe(&self) -> String {
+        let seeds: Vec<String> = self
+            .seeds
+            .iter()
+            .map(|seed| match seed {
+                Seed::Str(s) => format!("{:?}", s),
+                Seed::Actor => "actor".to_string(),
+                Seed::Mint => "mint".to_string(),
+                Seed::ProgramId => "program id".to_string(),
+            })
+            .collect();
+        format!("[{}]", seeds.join(", "))
+    }
+
+    /// The address and canonical bump, None when the PDA needs a mint and there is none
+    pub fn derive(&self, program_id: &Pubkey, actor: &Pubkey, mint: Option<&Pubkey>) -> Option<(Pubkey, u8)> {
+        let mut seeds: Vec<Vec<u8>> = vec![];
+        for seed in self.seeds {
+            seeds.push(match seed {
+                Seed::Str(s) => s.as_bytes().to_vec(),
+                Seed::Actor => actor.to_bytes().to_vec(),
+                Seed::Mint => mint?.to_bytes().to_vec(),
+                Seed::ProgramId => program_id.to_bytes().to_vec(),
+            });
+        }
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        let under = self.under.map_or(*program_id, |under| under());
+        Some(Pubkey::find_program_address(&seeds, &under))
+    }
+}
+
+macro_rules! pda {
+    ($level:literal, $name:literal, [$($seed:expr),*], $helper:literal) => {
+        Pda { level: $level, name: $name, seeds: &[$($seed),*], under: None, helper: $helper }
+    };
+}
+
+use Seed::*;
+
+pub const PDAS: &[Pda] = &[
+    pda!("level0", "wallet", [Actor], "level0::get_wallet_address"),
+    pda!("level0", "vault", [Actor, Str("VAULT")], "level0::get_vault_address"),
+    pda!("level1", "wallet", [Actor], "level1::get_wallet_address"),
+    pda!("level2", "wallet", [Actor], "level2::get_wallet_address"),
+    pda!("level4", "wallet", [Actor, Mint], "level4::get_wallet_address"),
+    pda!("level4", "authority", [], "level4::get_authority"),
+    pda!("level5", "wallet", [Actor], "level5::get_wallet_address"),
+    pda!("level5", "vault", [Str("VAULT")], "level5::get_vault_address"),
+    pda!("level6", "treasury", [Str("TREASURY")], "level6::get_treasury_address"),
+    pda!("level6", "user", [Str("USER"), Actor], "level6::find_user_address"),
+    pda!("level7", "vault", [Str("VAULT")], "level7::get_vault_address"),
+    pda!("level8", "bridge", [Str("BRIDGE")], "level8::get_bridge_address"),
+    pda!("level9", "vault", [Str("VAULT")], "level9::get_vault_address"),
+    pda!("level9", "position", [Actor], "level9::get_position_address"),
+    pda!("level10", "market", [Str("MARKET")], "level10::get_market_address"),
+    pda!("level10", "obligation", [Actor], "level10::get_obligation_address"),
+    pda!("amm", "pool", [Str("POOL"), Mint], "amm::get_pool_address"),
+    pda!("level11", "balance", [Actor], "level11::get_balance_address"),
+    pda!("level11", "vault", [Str("VAULT")], "level11::get_vault_address"),
+    pda!("level12", "payroll", [Str("PAYROLL"), Actor], "level12::get_payroll_address"),
+    pda!("level14", "config", [Str("CONFIG")], "level14::get_config_address"),
+    Pda {
+        level: "level14",
+        name: "programdata",
+        seeds: &[ProgramId],
+        under: Some(bpf_loader_upgradeable::id),
+        helper: "level14::get_programdata_address",
+    },
+];
+
+/// The PDAs of a level
+pub fn of_level(level: &str) -> Vec<&'static Pda> {
+    PDAS.iter().filter(|pda| pda.level == level).collect()
+}
+
+/// level3 derives its Vault with `create_program_address(&[&[seed]])`, for any seed the
+/// initializer picks: the address of each of the 256 seeds, None when it lands on the curve
+pub fn level3_vaults(program_id: &Pubkey) -> Vec<(u8, Option<Pubkey>)> {
+    (0..=255u8)
+        .map(|seed| (seed, Pubkey::create_program_address(&[&[seed]], program_id).ok()))
+        .collect()
+}
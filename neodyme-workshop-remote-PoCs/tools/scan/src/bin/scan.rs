@@ -0,0 +1,153 @@
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+use owo_colors::OwoColorize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, native_token::lamports_to_sol, pubkey::Pubkey};
+
+use actors::Actors;
+use inspect::{load_fixture, program_ids, token_programs};
+use scan::{bug, scan, Report, Source, BUGS};
+
+const USAGE: &str = "usage: scan [--validator | --url <RPC_URL>] [--fixture <ACCOUNTS.json>]... [--root <REPO_ROOT>]
+            [--program-id <PUBKEY>] [LEVEL]...";
+
+fn show(actors: &Actors, report: &Report) {
+    println!("{} {}", report.level.bold(), actors.tag(&report.program_id));
+    println!("  {}", bug(&report.level).unwrap_or_default().dimmed());
+    println!("  {} accounts owned by the program", report.scanned);
+    for exposure in &report.exposures {
+        let of = match exposure.of {
+            Some(of) => format!(" of {}", actors.tag(&of)),
+            None => String::new(),
+        };
+        let mut amount = format!("{} SOL", lamports_to_sol(exposure.lamports));
+        if let Some((mint, tokens)) = exposure.tokens {
+            amount = format!("{} of mint {}", tokens, actors.tag(&mint));
+        }
+        let how = exposure.how.as_ref().map_or(String::new(), |how| format!(" ({})", how));
+        println!(
+            "  {} {} {}{}: {}{}",
+            "exposed:".red().bold(),
+            exposure.what,
+            actors.tag(&exposure.address),
+            of,
+            amount.bold(),
+            how
+        );
+    }
+    for note in &report.notes {
+        println!("  {} {}", "note:".yellow(), actors.relabel(note));
+    }
+    println!("  at risk: {}", at_risk(actors, report.lamports_at_risk(), &report.tokens_at_risk()));
+}
+
+/// `12.5 SOL, 1000 of mint <pubkey>`
+fn at_risk(actors: &Actors, lamports: u64, tokens: &BTreeMap<Pubkey, u64>) -> String {
+    let mut parts = vec![format!("{} SOL", lamports_to_sol(lamports))];
+    parts.extend(tokens.iter().map(|(mint, amount)| format!("{} of mint {}", amount, actors.tag(mint))));
+    parts.join(", ")
+}
+
+fn main() {
+    let mut url = None;
+    let mut fixtures = vec![];
+    let mut root = PathBuf::from(".");
+    let mut program_id = None;
+    let mut levels = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--validator" => url = Some("http://localhost:8899/".to_string()),
+            "--url" => url = Some(args.next().expect(USAGE)),
+            "--fixture" => fixtures.push(PathBuf::from(args.next().expect(USAGE))),
+            "--root" => root = PathBuf::from(args.next().expect(USAGE)),
+            "--program-id" => program_id = Some(Pubkey::from_str(&args.next().expect(USAGE)).expect(USAGE)),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => levels.push(arg),
+        }
+    }
+    if program_id.is_some() && levels.len() != 1 {
+        eprintln!("--program-id goes with a single level");
+        std::process::exit(2);
+    }
+
+    let mut actors = Actors::new();
+    actors.load_all();
+    let program_ids = program_ids(&root);
+    for (name, id) in &program_ids {
+        actors.label(*id, name);
+    }
+    // Every level that was built, by default
+    if levels.is_empty() {
+        levels = BUGS
+            .iter()
+            .map(|(level, _)| level.to_string())
+            .filter(|level| program_ids.iter().any(|(name, _)| name == level))
+            .collect();
+        if levels.is_empty() {
+            eprintln!("no level was built under {}, pass a LEVEL and its --program-id", root.display());
+            std::process::exit(2);
+        }
+    }
+
+    let source = match (url, fixtures.is_empty()) {
+        (None, false) => {
+            let mut accounts = vec![];
+            for path in &fixtures {
+                match load_fixture(path) {
+                    Ok(fixture) => accounts.extend(fixture),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    }
+                }
+            }
+            Source::Fixtures(accounts)
+        }
+        (url, _) => Source::Validator(RpcClient::new_with_commitment(
+            url.unwrap_or_else(|| "http://localhost:8899/".to_string()),
+            CommitmentConfig::confirmed(),
+        )),
+    };
+    let token_programs = token_programs(&program_ids);
+
+    let mut lamports = 0;
+    let mut tokens = BTreeMap::new();
+    let mut failed = false;
+    for level in &levels {
+        let id = program_id.or_else(|| program_ids.iter().find(|(name, _)| name == level).map(|(_, id)| *id));
+        let id = match id {
+            Some(id) => id,
+            None => {
+                println!("{} {}\n", level.bold(), "not built, pass its --program-id".red());
+                failed = true;
+                continue;
+            }
+        };
+        match scan(level, &id, &source, &token_programs) {
+            Ok(report) => {
+                show(&actors, &report);
+                lamports += report.lamports_at_risk();
+                for (mint, amount) in report.tokens_at_risk() {
+                    *tokens.entry(mint).or_insert(0) += amount;
+                }
+            }
+            Err(e) => {
+                println!("{} {}", level.bold(), e.red());
+                failed = true;
+            }
+        }
+        println!();
+    }
+    if levels.len() > 1 {
+        println!("{} {}", "Total at risk:".bold(), at_risk(&actors, lamports, &tokens));
+    }
+    // Like `idl check`, so an audit script can tell
+    if failed || lamports > 0 || tokens.values().any(|amount| *amount > 0) {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,573 @@
+//! Goes through every account of a deployed level and finds the ones the bug of that level (the
+//! one its PoC exploits) can drain, with the lamports and tokens at risk. For audits of a local
+//! validator: the PoCs attack one fresh victim, this looks at everything that is already there
+use std::collections::BTreeMap;
+
+use borsh::BorshDeserialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey, rent::Rent};
+use spl_token::state::Account as TokenAccount;
+
+/// Where the accounts come from
+// There is a single Source per run, not worth boxing
+#[allow(clippy::large_enum_variant)]
+pub enum Source {
+    Validator(RpcClient),
+    /// `solana account --output json` dumps, see `inspect::load_fixture`
+    Fixtures(Vec<(Pubkey, Account)>),
+}
+
+impl Source {
+    /// getProgramAccounts, or the accounts of the fixtures owned by the program
+    pub fn program_accounts(&self, program_id: &Pubkey) -> Result<Vec<(Pubkey, Account)>, String> {
+        self.accounts_owned_by(program_id, vec![])
+    }
+
+    fn accounts_owned_by(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Account)>, String> {
+        match self {
+            Source::Validator(client) => client
+                .get_program_accounts_with_config(
+                    program_id,
+                    RpcProgramAccountsConfig {
+                        filters: if filters.is_empty() { None } else { Some(filters) },
+                        account_config: RpcAccountInfoConfig {
+                            encoding: Some(UiAccountEncoding::Base64),
+                            ..RpcAccountInfoConfig::default()
+                        },
+                        ..RpcProgramAccountsConfig::default()
+                    },
+                )
+                .map_err(|e| e.to_string()),
+            Source::Fixtures(accounts) => Ok(accounts
+                .iter()
+                .filter(|(_, account)| {
+                    account.owner == *program_id
+                        && filters.iter().all(|filter| filter.allows(&account.clone().into()))
+                })
+                .cloned()
+                .collect()),
+        }
+    }
+
+    /// The accounts, None for the missing ones
+    pub fn get(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>, String> {
+        match self {
+            Source::Validator(client) => {
+                let mut accounts = vec![];
+                // getMultipleAccounts takes up to 100 accounts
+                for chunk in pubkeys.chunks(100) {
+                    accounts.extend(client.get_multiple_accounts(chunk).map_err(|e| e.to_string())?);
+                }
+                Ok(accounts)
+            }
+            Source::Fixtures(accounts) => Ok(pubkeys
+                .iter()
+                .map(|pubkey| accounts.iter().find(|(key, _)| key == pubkey).map(|(_, a)| a.clone()))
+                .collect()),
+        }
+    }
+
+    fn get_one(&self, pubkey: &Pubkey) -> Result<Option<Account>, String> {
+        Ok(self.get(&[*pubkey])?.remove(0))
+    }
+
+    /// The SPL token accounts of `owner`, under every token program
+    pub fn token_accounts(
+        &self,
+        token_programs: &[Pubkey],
+        owner: &Pubkey,
+    ) -> Result<Vec<(Pubkey, TokenAccount)>, String> {
+        let mut token_accounts = vec![];
+        for token_program in token_programs {
+            // The owner is at 32 in `Account`, Token-2022 accounts with extensions are longer
+            let filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(32, owner.as_ref()))];
+            for (pubkey, account) in self.accounts_owned_by(token_program, filters)? {
+                if account.data.len() < TokenAccount::LEN {
+                    continue;
+                }
+                if let Ok(token_account) = TokenAccount::unpack_unchecked(&account.data[..TokenAccount::LEN]) {
+                    token_accounts.push((pubkey, token_account));
+                }
+            }
+        }
+        Ok(token_accounts)
+    }
+
+    /// Rent exemption of `len` bytes, the default Rent for fixtures
+    pub fn rent(&self, len: u64) -> Result<u64, String> {
+        match self {
+            Source::Validator(client) => client
+                .get_minimum_balance_for_rent_exemption(len as usize)
+                .map_err(|e| e.to_string()),
+            Source::Fixtures(_) => Ok(Rent::default().minimum_balance(len as usize)),
+        }
+    }
+}
+
+/// An account the bug can drain
+#[derive(Clone, Debug, PartialEq)]
+pub struct Exposure {
+    pub address: Pubkey,
+    /// `Vault`, `Wallet`...
+    pub what: &'static str,
+    /// Whose it is (authority, creator, admin...), when the account says
+    pub of: Option<Pubkey>,
+    pub lamports: u64,
+    /// (mint, amount) for token accounts
+    pub tokens: Option<(Pubkey, u64)>,
+    /// How the lamports or tokens can be taken, when it is not the general case of the level
+    pub how: Option<String>,
+}
+
+impl Exposure {
+    fn new(address: Pubkey, what: &'static str, of: Option<Pubkey>, lamports: u64) -> Self {
+        Exposure {
+            address,
+            what,
+            of,
+            lamports,
+            tokens: None,
+            how: None,
+        }
+    }
+}
+
+/// What the scan of one level found
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub level: String,
+    pub program_id: Pubkey,
+    /// Accounts of the program decoded
+    pub scanned: usize,
+    pub exposures: Vec<Exposure>,
+    /// Anything else worth knowing (the state that makes an exposure, or prevents it)
+    pub notes: Vec<String>,
+}
+
+impl Report {
+    pub fn lamports_at_risk(&self) -> u64 {
+        self.exposures.iter().map(|e| e.lamports).sum()
+    }
+
+    /// Per mint
+    pub fn tokens_at_risk(&self) -> BTreeMap<Pubkey, u64> {
+        let mut tokens = BTreeMap::new();
+        for (mint, amount) in self.exposures.iter().filter_map(|e| e.tokens) {
+            *tokens.entry(mint).or_insert(0) += amount;
+        }
+        tokens
+    }
+}
+
+/// The levels the scanner knows, with the bug it looks for
+pub const BUGS: &[(&str, &str)] = &[
+    ("level0", "Withdraw takes any Wallet, even one that is not owned by the program"),
+    ("level1", "Withdraw never checks that the authority signs"),
+    ("level2", "Withdraw does unchecked arithmetic, a negative amount drains the destination Wallet"),
+    ("level3", "Withdraw reads any account of the program as a TipPool, i.e. a Vault of ours"),
+    ("level4", "Withdraw signs for whichever token program owns the Wallet and the mint passed"),
+    ("level5", "Transfer from a Wallet to itself doubles its balance"),
+    ("level6", "Register takes any bump, so the reward can be claimed once per valid bump"),
+    ("level7", "Close leaves the Voucher data, refunding its rent revives it"),
+    ("level8", "Claim reads the guardian signature from whatever account is passed as the sysvar"),
+    ("level9", "Deposits round down to 0 shares after a donation to an empty Vault"),
+    ("level10", "Collateral is valued at the spot price of the amm pool"),
+    ("level11", "Withdraw hands the Vault signature to a callback program of the caller"),
+    ("level12", "Pay never checks who the recipients are"),
+    ("level13", "Initialize takes a Vault that is already initialized"),
+    ("level14", "SetConfig reads the upgrade authority from any account passed as ProgramData"),
+];
+
+/// The levels with a `patched` feature. A patched build keeps the accounts of the vulnerable one,
+/// the scan can't tell them apart
+pub const PATCHED: &[&str] = &[
+    "level4", "level5", "level6", "level7", "level8", "level9", "level11", "level12", "level13", "level14",
+];
+
+pub fn bug(level: &str) -> Option<&'static str> {
+    BUGS.iter().find(|(name, _)| *name == level).map(|(_, bug)| *bug)
+}
+
+/// What a level scan works with
+struct Scan<'a> {
+    program_id: Pubkey,
+    source: &'a Source,
+    accounts: Vec<(Pubkey, Account)>,
+    token_programs: &'a [Pubkey],
+}
+
+impl<'a> Scan<'a> {
+    /// The accounts of `len` bytes that deserialize as `T`
+    fn decode<T: BorshDeserialize>(&self, len: u64) -> Vec<(Pubkey, T, &Account)> {
+        self.accounts
+            .iter()
+            .filter(|(_, account)| account.data.len() as u64 == len)
+            .filter_map(|(pubkey, account)| T::try_from_slice(&account.data).ok().map(|t| (*pubkey, t, account)))
+            .collect()
+    }
+
+    /// The accounts that deserialize as `T`, whatever their size (for `Vec` fields)
+    fn decode_any<T: BorshDeserialize>(&self) -> Vec<(Pubkey, T, &Account)> {
+        self.accounts
+            .iter()
+            .filter_map(|(pubkey, account)| T::try_from_slice(&account.data).ok().map(|t| (*pubkey, t, account)))
+            .collect()
+    }
+
+    /// The account at a PDA of the level, with a note when it was never created
+    fn get_pda(&self, address: &Pubkey, what: &str, notes: &mut Vec<String>) -> Result<Option<Account>, String> {
+        let account = self.source.get_one(address)?;
+        if account.is_none() {
+            notes.push(format!("no {} at {}, the program was never initialized", what, address));
+        }
+        Ok(account)
+    }
+
+    /// Lamports above the rent exemption of the account
+    fn above_rent(&self, account: &Account) -> Result<u64, String> {
+        Ok(account.lamports.saturating_sub(self.source.rent(account.data.len() as u64)?))
+    }
+}
+
+/// Scans the accounts of `level`, deployed at `program_id`. `token_programs` are the programs token
+/// accounts can be owned by (see `inspect::token_programs`)
+pub fn scan(level: &str, program_id: &Pubkey, source: &Source, token_programs: &[Pubkey]) -> Result<Report, String> {
+    let scan = Scan {
+        program_id: *program_id,
+        source,
+        accounts: source.program_accounts(program_id)?,
+        token_programs,
+    };
+    let mut notes = vec![];
+    let exposures = match level {
+        "level0" => level0(&scan)?,
+        "level1" => level1(&scan)?,
+        "level2" => level2(&scan)?,
+        "level3" => level3(&scan, &mut notes)?,
+        "level4" => level4(&scan, &mut notes)?,
+        "level5" => level5(&scan, &mut notes)?,
+        "level6" => level6(&scan, &mut notes)?,
+        "level7" => level7(&scan, &mut notes)?,
+        "level8" => level8(&scan, &mut notes)?,
+        "level9" => level9(&scan, &mut notes)?,
+        "level10" => level10(&scan, &mut notes)?,
+        "level11" => level11(&scan, &mut notes)?,
+        "level12" => level12(&scan)?,
+        "level13" => level13(&scan)?,
+        "level14" => level14(&scan, &mut notes)?,
+        _ => return Err(format!("no known bug for {}", level)),
+    };
+    if PATCHED.contains(&level) {
+        notes.push("a `patched` build is reported as exposed too, its accounts look the same".to_string());
+    }
+    Ok(Report {
+        level: level.to_string(),
+        program_id: *program_id,
+        scanned: scan.accounts.len(),
+        exposures: exposures.into_iter().filter(|e| e.lamports > 0 || e.tokens.is_some_and(|(_, a)| a > 0)).collect(),
+        notes,
+    })
+}
+
+/// Every Vault: a Wallet of ours naming it as its vault is enough, Withdraw takes all its lamports
+fn level0(scan: &Scan) -> Result<Vec<Exposure>, String> {
+    let wallets = scan.decode::<level0::Wallet>(level0::WALLET_LEN);
+    Ok(scan
+        .accounts
+        .iter()
+        .filter(|(_, account)| account.data.is_empty())
+        .map(|(address, account)| {
+            let authority = wallets.iter().find(|(_, w, _)| w.vault == *address).map(|(_, w, _)| w.authority);
+            Exposure::new(*address, "Vault", authority, account.lamports)
+        })
+        .collect())
+}
+
+/// Every Wallet, all its lamports
+fn level1(scan: &Scan) -> Result<Vec<Exposure>, String> {
+    Ok(scan
+        .decode::<level1::Wallet>(level1::WALLET_LEN)
+        .into_iter()
+        .map(|(address, wallet, account)| Exposure::new(address, "Wallet", Some(wallet.authority), account.lamports))
+        .collect())
+}
+
+/// Every Wallet, down to its rent exemption
+fn level2(scan: &Scan) -> Result<Vec<Exposure>, String> {
+    let mut exposures = vec![];
+    for (address, wallet, account) in scan.decode::<level2::Wallet>(level2::WALLET_LEN) {
+        exposures.push(Exposure::new(address, "Wallet", Some(wallet.authority), scan.above_rent(account)?));
+    }
+    Ok(exposures)
+}
+
+/// A Vault read as a TipPool has `creator` as withdraw authority, the bits of `fee` as value and
+/// `fee_recipient` as vault: Initialize a Vault of ours with a free seed, the victim Vault as
+/// `fee_recipient`, and Withdraw everything. Without a free seed, only the forged pools already
+/// there can be used, up to their value
+fn level3(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    use level3::{TipPool, Vault, TIP_POOL_LEN, VAULT_LEN};
+
+    let vaults = scan.decode::<Vault>(VAULT_LEN);
+    let pools = scan.decode::<TipPool>(TIP_POOL_LEN);
+    let free_seeds = pda::level3_vaults(&scan.program_id)
+        .into_iter()
+        .filter_map(|(seed, address)| address.map(|address| (seed, address)))
+        .filter(|(_, address)| !scan.accounts.iter().any(|(key, _)| key == address))
+        .count();
+
+    let mut exposures = vec![];
+    for (address, vault, account) in &vaults {
+        let owed: u64 = pools.iter().filter(|(_, p, _)| p.vault == *address).map(|(_, p, _)| p.value).sum();
+        // Vaults whose TipPool reading points at this one
+        let forged: Vec<(Pubkey, TipPool)> = vaults
+            .iter()
+            .filter_map(|(key, _, forged)| {
+                TipPool::deserialize(&mut &forged.data[..]).ok().map(|pool| (*key, pool))
+            })
+            .filter(|(_, pool)| pool.vault == *address && pool.value > owed)
+            .collect();
+        for (key, pool) in &forged {
+            notes.push(format!(
+                "{} is a Vault that reads as a TipPool of {} worth {} lamports, withdrawable by {} (the Vault owes {} to its pools)",
+                key, address, pool.value, pool.withdraw_authority, owed
+            ));
+        }
+        let lamports = match (free_seeds, forged.iter().map(|(_, pool)| pool.value).max()) {
+            (0, None) => 0,
+            (0, Some(value)) => value.min(account.lamports),
+            _ => account.lamports,
+        };
+        let mut exposure = Exposure::new(*address, "Vault", Some(vault.creator), lamports);
+        if free_seeds == 0 && !forged.is_empty() {
+            exposure.how = Some("through the forged pools already there".to_string());
+        }
+        exposures.push(exposure);
+    }
+    for (address, pool, _) in &pools {
+        let vault_lamports = vaults.iter().find(|(key, _, _)| *key == pool.vault).map(|(_, _, a)| a.lamports);
+        if vault_lamports.is_some_and(|lamports| pool.value > lamports) {
+            notes.push(format!(
+                "the TipPool {} is worth {} lamports, more than its Vault {} holds",
+                address, pool.value, pool.vault
+            ));
+        }
+    }
+    if free_seeds == 0 {
+        notes.push("every seed off the curve has its Vault, a new Vault can't be initialized".to_string());
+    } else {
+        notes.push(format!("{} seeds off the curve are still free for a Vault of ours", free_seeds));
+    }
+    Ok(exposures)
+}
+
+/// The token Wallets of the authority PDA: Withdraw hands the authority signature to whichever token
+/// program is passed. Those of a fake token program (`myspl`, or any program of ours) are taken
+/// outright. Those of spl-token and Token-2022 go to the fake program signed, as the destination of
+/// the transfer. level4 owns no account itself, the Wallets belong to the token programs
+fn level4(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    let authority = pda::of_level("level4")
+        .into_iter()
+        .find(|pda| pda.name == "authority")
+        .and_then(|pda| pda.derive(&scan.program_id, &Pubkey::default(), None))
+        .map(|(address, _)| address)
+        .ok_or("no authority PDA for level4")?;
+    let allowlist = inspect::token_program_allowlist();
+    let (others, allowlisted): (Vec<Pubkey>, Vec<Pubkey>) =
+        scan.token_programs.iter().copied().partition(|program| !allowlist.contains(program));
+    let mut exposures: Vec<Exposure> = scan
+        .source
+        .token_accounts(&others, &authority)?
+        .into_iter()
+        .map(|(address, token_account)| Exposure {
+            tokens: Some((token_account.mint, token_account.amount)),
+            ..Exposure::new(address, "Wallet", None, 0)
+        })
+        .collect();
+    let signed = scan.source.token_accounts(&allowlisted, &authority)?;
+    if !signed.is_empty() {
+        notes.push(
+            "the fake token program gets the spl-token Wallets signed, but only 4 accounts: it still needs the \
+             token program itself among them to move the tokens"
+                .to_string(),
+        );
+    }
+    exposures.extend(signed.into_iter().map(|(address, token_account)| Exposure {
+        tokens: Some((token_account.mint, token_account.amount)),
+        how: Some("signed over to a fake token program".to_string()),
+        ..Exposure::new(address, "Wallet", None, 0)
+    }));
+    Ok(exposures)
+}
+
+/// The Vault: Withdraw pays any balance the doubled Wallet claims
+fn level5(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    let (vault, _) = level5::get_vault_address(scan.program_id);
+    let balances: u64 = scan
+        .decode::<level5::Wallet>(level5::WALLET_LEN)
+        .iter()
+        .map(|(_, wallet, _)| wallet.balance)
+        .sum();
+    let account = match scan.get_pda(&vault, "Vault", notes)? {
+        Some(account) => account,
+        None => return Ok(vec![]),
+    };
+    notes.push(format!("the Wallets hold balances of {} lamports in total", balances));
+    Ok(vec![Exposure::new(vault, "Vault", None, account.lamports)])
+}
+
+/// The Treasury, in whole rewards above its rent exemption
+fn level6(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    let (address, _) = level6::get_treasury_address(scan.program_id);
+    let account = match scan.get_pda(&address, "Treasury", notes)? {
+        Some(account) => account,
+        None => return Ok(vec![]),
+    };
+    let treasury = level6::Treasury::try_from_slice(&account.data).map_err(|e| format!("Treasury: {}", e))?;
+    let available = scan.above_rent(&account)?;
+    let lamports = match treasury.reward {
+        0 => 0,
+        reward => available / reward * reward,
+    };
+    let users = scan.decode::<level6::User>(level6::USER_LEN).len();
+    notes.push(format!("{} Users registered, reward of {} lamports", users, treasury.reward));
+    Ok(vec![Exposure::new(address, "Treasury", Some(treasury.admin), lamports)])
+}
+
+/// The Vault above its rent exemption, as long as a Voucher holder can redeem theirs again and again
+fn level7(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    let (address, _) = level7::get_vault_address(scan.program_id);
+    let vouchers = scan.decode::<level7::Voucher>(level7::VOUCHER_LEN);
+    let vouchers: Vec<_> = vouchers.iter().filter(|(_, voucher, _)| voucher.vault == address).collect();
+    let account = match scan.get_pda(&address, "Vault", notes)? {
+        Some(account) => account,
+        None => return Ok(vec![]),
+    };
+    let admin = level7::Vault::try_from_slice(&account.data).ok().map(|vault| vault.admin);
+    if vouchers.is_empty() {
+        notes.push("no Voucher is outstanding, there is nothing to revive".to_string());
+        return Ok(vec![]);
+    }
+    notes.push(format!(
+        "{} Vouchers outstanding, worth {} lamports",
+        vouchers.len(),
+        vouchers.iter().map(|(_, voucher, _)| voucher.amount).sum::<u64>()
+    ));
+    Ok(vec![Exposure::new(address, "Vault", admin, scan.above_rent(&account)?)])
+}
+
+/// The Bridge above its rent exemption
+fn level8(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    let (address, _) = level8::get_bridge_address(scan.program_id);
+    let account = match scan.get_pda(&address, "Bridge", notes)? {
+        Some(account) => account,
+        None => return Ok(vec![]),
+    };
+    let admin = level8::Bridge::try_from_slice(&account.data).ok().map(|bridge| bridge.admin);
+    Ok(vec![Exposure::new(address, "Bridge", admin, scan.above_rent(&account)?)])
+}
+
+/// Nothing is at risk until someone deposits into an empty Vault: then the whole deposit is
+fn level9(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    for (address, vault, _) in scan.decode::<level9::Vault>(level9::VAULT_LEN) {
+        if vault.total_shares == 0 {
+            notes.push(format!(
+                "the Vault {} has no shares, the next deposit can be rounded down to nothing",
+                address
+            ));
+        } else if vault.total_shares < level9::MINIMUM_LIQUIDITY {
+            notes.push(format!(
+                "the Vault {} has {} shares, a donation can still make small deposits worth 0 shares",
+                address, vault.total_shares
+            ));
+        }
+    }
+    Ok(vec![])
+}
+
+/// The liquidity of the Market, which a skewed price lets one borrow
+fn level10(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    let (address, _) = level10::get_market_address(scan.program_id);
+    let account = match scan.get_pda(&address, "Market", notes)? {
+        Some(account) => account,
+        None => return Ok(vec![]),
+    };
+    let market = level10::Market::try_from_slice(&account.data).map_err(|e| format!("Market: {}", e))?;
+    let liquidity = match scan.source.get_one(&market.liquidity)? {
+        Some(liquidity) if liquidity.data.len() >= TokenAccount::LEN => liquidity,
+        _ => return Ok(vec![]),
+    };
+    let liquidity = TokenAccount::unpack_unchecked(&liquidity.data[..TokenAccount::LEN]).map_err(|e| e.to_string())?;
+    let obligations = scan.decode::<level10::Obligation>(level10::OBLIGATION_LEN);
+    notes.push(format!(
+        "{} Obligations, {} tokens borrowed, priced by the amm pool {}",
+        obligations.len(),
+        obligations.iter().map(|(_, o, _)| o.borrowed).sum::<u64>(),
+        market.amm_pool
+    ));
+    Ok(vec![Exposure {
+        tokens: Some((liquidity.mint, liquidity.amount)),
+        ..Exposure::new(market.liquidity, "liquidity", Some(market.admin), 0)
+    }])
+}
+
+/// The Vault, a system account the callback can empty
+fn level11(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    let (vault, _) = level11::get_vault_address(scan.program_id);
+    let balances: u64 = scan
+        .decode::<level11::Balance>(level11::BALANCE_LEN)
+        .iter()
+        .map(|(_, balance, _)| balance.amount)
+        .sum();
+    let account = match scan.get_pda(&vault, "Vault", notes)? {
+        Some(account) => account,
+        None => return Ok(vec![]),
+    };
+    notes.push(format!("the Balances hold {} lamports in total", balances));
+    Ok(vec![Exposure::new(vault, "Vault", None, account.lamports)])
+}
+
+/// Every Payroll, one salary per recipient and per PAY_PERIOD: whoever cranks Pay first takes it
+fn level12(scan: &Scan) -> Result<Vec<Exposure>, String> {
+    let mut exposures = vec![];
+    for (address, payroll, account) in scan.decode_any::<level12::Payroll>() {
+        let total = payroll.salary.saturating_mul(payroll.recipients.len() as u64);
+        let available = scan.above_rent(account)?;
+        let mut exposure = Exposure::new(address, "Payroll", Some(payroll.admin), total.min(available));
+        exposure.how = Some(format!(
+            "every payday, next one at unix time {}",
+            payroll.last_paid + level12::PAY_PERIOD
+        ));
+        exposures.push(exposure);
+    }
+    Ok(exposures)
+}
+
+/// Every Vault, down to the rent exemption Withdraw keeps
+fn level13(scan: &Scan) -> Result<Vec<Exposure>, String> {
+    let mut exposures = vec![];
+    for (address, vault, account) in scan.decode::<level13::Vault>(level13::VAULT_LEN) {
+        let of = Some(vault.authority).filter(|_| vault.is_initialized);
+        exposures.push(Exposure::new(address, "Vault", of, scan.above_rent(account)?));
+    }
+    Ok(exposures)
+}
+
+/// The fees of the Config, which Sweep sends to the treasury SetConfig can redirect
+fn level14(scan: &Scan, notes: &mut Vec<String>) -> Result<Vec<Exposure>, String> {
+    let (address, _) = level14::get_config_address(scan.program_id);
+    let account = match scan.get_pda(&address, "Config", notes)? {
+        Some(account) => account,
+        None => return Ok(vec![]),
+    };
+    Ok(vec![Exposure::new(address, "Config", None, scan.above_rent(&account)?)])
+}
@@ -0,0 +1,200 @@
+//! The scan of every level against `tests/fixtures/<level>.json` (what `scan --fixture` takes).
+//!
+//! The fixtures have made up keys: level N is deployed at `[0x10 + N; 32]`, the accounts of the
+//! levels are `[0x40; 32]`, `[0x41; 32]`... or their PDAs, the admin or authority is `[0x20; 32]`,
+//! the hacker `[0x21; 32]`, someone else `[0x22; 32]` and the mint `[0x50; 32]`. The fake token
+//! program of level4 is `[0x31; 32]`, like in the monitor fixtures
+use std::path::Path;
+
+use inspect::{load_fixture, token_program_allowlist};
+use scan::{scan, Exposure, Report, Source};
+use solana_sdk::{account::Account, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, rent::Rent};
+
+const ADMIN: Pubkey = Pubkey::new_from_array([0x20; 32]);
+const HACKER: Pubkey = Pubkey::new_from_array([0x21; 32]);
+const MINT: Pubkey = Pubkey::new_from_array([0x50; 32]);
+const FAKE_TOKEN_PROGRAM: Pubkey = Pubkey::new_from_array([0x31; 32]);
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn program_id(level: &str) -> Pubkey {
+    key(0x10 + level["level".len()..].parse::<u8>().unwrap())
+}
+
+fn rent(len: usize) -> u64 {
+    Rent::default().minimum_balance(len)
+}
+
+fn run(level: &str, accounts: Vec<(Pubkey, Account)>) -> Report {
+    let [spl_token, token_2022] = token_program_allowlist();
+    let token_programs = [spl_token, token_2022, FAKE_TOKEN_PROGRAM];
+    scan(level, &program_id(level), &Source::Fixtures(accounts), &token_programs).unwrap()
+}
+
+fn scan_fixture(level: &str) -> Report {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(format!("{}.json", level));
+    run(level, load_fixture(&fixture).unwrap())
+}
+
+fn exposure(address: Pubkey, what: &'static str, of: Option<Pubkey>, lamports: u64) -> Exposure {
+    Exposure { address, what, of, lamports, tokens: None, how: None }
+}
+
+fn has_note(report: &Report, note: &str) -> bool {
+    report.notes.iter().any(|n| n.contains(note))
+}
+
+#[test]
+fn level0() {
+    let report = scan_fixture("level0");
+    // the Wallet has data, only the Vault it names is taken
+    assert_eq!(report.exposures, [exposure(key(0x40), "Vault", Some(ADMIN), 5 * LAMPORTS_PER_SOL)]);
+}
+
+#[test]
+fn level1() {
+    let report = scan_fixture("level1");
+    assert_eq!(report.exposures, [exposure(key(0x41), "Wallet", Some(ADMIN), 2 * LAMPORTS_PER_SOL)]);
+}
+
+#[test]
+fn level2() {
+    let report = scan_fixture("level2");
+    // the Wallet of [0x22; 32] only has its rent exemption
+    assert_eq!(report.exposures, [exposure(key(0x41), "Wallet", Some(ADMIN), LAMPORTS_PER_SOL)]);
+}
+
+#[test]
+fn level3() {
+    let report = scan_fixture("level3");
+    // with free seeds every Vault goes, the forged one too
+    assert_eq!(
+        report.exposures,
+        [
+            exposure(key(0x40), "Vault", Some(ADMIN), 3 * LAMPORTS_PER_SOL),
+            exposure(key(0x42), "Vault", Some(HACKER), rent(73)),
+        ]
+    );
+    assert!(has_note(&report, &format!("{} is a Vault that reads as a TipPool of {}", key(0x42), key(0x40))));
+    assert!(has_note(&report, "seeds off the curve are still free"));
+}
+
+#[test]
+fn level4() {
+    let report = scan_fixture("level4");
+    // the token account of the hacker is not one of the authority
+    assert_eq!(
+        report.exposures,
+        [
+            Exposure { tokens: Some((MINT, 1000)), ..exposure(key(0x40), "Wallet", None, 0) },
+            Exposure {
+                tokens: Some((MINT, 2000)),
+                how: Some("signed over to a fake token program".to_string()),
+                ..exposure(key(0x41), "Wallet", None, 0)
+            },
+        ]
+    );
+    assert!(has_note(&report, "it still needs the token program itself"));
+}
+
+#[test]
+fn level5() {
+    let report = scan_fixture("level5");
+    let (vault, _) = level5::get_vault_address(program_id("level5"));
+    assert_eq!(report.exposures, [exposure(vault, "Vault", None, 6 * LAMPORTS_PER_SOL)]);
+    assert!(has_note(&report, "balances of 3000000000 lamports in total"));
+}
+
+#[test]
+fn never_initialized() {
+    let report = run("level5", vec![]);
+    assert!(report.exposures.is_empty());
+    let (vault, _) = level5::get_vault_address(program_id("level5"));
+    assert!(has_note(&report, &format!("no Vault at {}, the program was never initialized", vault)));
+}
+
+#[test]
+fn level6() {
+    let report = scan_fixture("level6");
+    let (treasury, _) = level6::get_treasury_address(program_id("level6"));
+    // 2.5 SOL above the rent, in rewards of 1 SOL
+    assert_eq!(report.exposures, [exposure(treasury, "Treasury", Some(ADMIN), 2 * LAMPORTS_PER_SOL)]);
+    assert!(has_note(&report, "1 Users registered"));
+}
+
+#[test]
+fn level7() {
+    let report = scan_fixture("level7");
+    let (vault, _) = level7::get_vault_address(program_id("level7"));
+    assert_eq!(report.exposures, [exposure(vault, "Vault", Some(ADMIN), 3 * LAMPORTS_PER_SOL)]);
+    assert!(has_note(&report, "1 Vouchers outstanding"));
+}
+
+#[test]
+fn level8() {
+    let report = scan_fixture("level8");
+    let (bridge, _) = level8::get_bridge_address(program_id("level8"));
+    assert_eq!(report.exposures, [exposure(bridge, "Bridge", Some(ADMIN), LAMPORTS_PER_SOL)]);
+}
+
+#[test]
+fn level9() {
+    let report = scan_fixture("level9");
+    assert!(report.exposures.is_empty());
+    assert!(has_note(&report, &format!("the Vault {} has no shares", key(0x40))));
+    assert!(has_note(&report, &format!("the Vault {} has 500 shares", key(0x41))));
+    // above MINIMUM_LIQUIDITY
+    assert!(!has_note(&report, &key(0x42).to_string()));
+}
+
+#[test]
+fn level10() {
+    let report = scan_fixture("level10");
+    assert_eq!(
+        report.exposures,
+        [Exposure { tokens: Some((MINT, 5000)), ..exposure(key(0x41), "liquidity", Some(ADMIN), 0) }]
+    );
+    assert!(has_note(&report, "1 Obligations, 100 tokens borrowed"));
+}
+
+#[test]
+fn level11() {
+    let report = scan_fixture("level11");
+    let (vault, _) = level11::get_vault_address(program_id("level11"));
+    assert_eq!(report.exposures, [exposure(vault, "Vault", None, 4 * LAMPORTS_PER_SOL)]);
+}
+
+#[test]
+fn level12() {
+    let report = scan_fixture("level12");
+    // a salary for each of the 2 recipients, out of 5 SOL
+    assert_eq!(
+        report.exposures,
+        [Exposure {
+            how: Some(format!("every payday, next one at unix time {}", level12::PAY_PERIOD)),
+            ..exposure(key(0x40), "Payroll", Some(ADMIN), 2 * LAMPORTS_PER_SOL)
+        }]
+    );
+}
+
+#[test]
+fn level13() {
+    let report = scan_fixture("level13");
+    assert_eq!(
+        report.exposures,
+        [
+            exposure(key(0x40), "Vault", Some(ADMIN), LAMPORTS_PER_SOL),
+            // not initialized, nobody's
+            exposure(key(0x41), "Vault", None, LAMPORTS_PER_SOL / 2),
+        ]
+    );
+}
+
+#[test]
+fn level14() {
+    let report = scan_fixture("level14");
+    let (config, _) = level14::get_config_address(program_id("level14"));
+    assert_eq!(report.exposures, [exposure(config, "Config", None, LAMPORTS_PER_SOL / 2)]);
+}
@@ -0,0 +1,160 @@
+//! Where a scenario runs: an in-process bank, or a validator over RPC
+use poc_framework::{
+    solana_client::{
+        client_error::ClientErrorKind,
+        rpc_client::RpcClient,
+        rpc_config::RpcTransactionConfig,
+        rpc_request::{RpcError, RpcResponseErrorData},
+    },
+    solana_sdk::{
+        account::Account,
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_instruction,
+    },
+    solana_transaction_status::UiTransactionEncoding,
+    Environment, LocalEnvironment, PrintableTransaction, RemoteEnvironment,
+};
+
+/// What happened to a transaction
+pub struct Outcome {
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+}
+
+// There is a single Backend per scenario, not worth boxing
+#[allow(clippy::large_enum_variant)]
+pub enum Backend {
+    Local(LocalEnvironment),
+    /// The RemoteEnvironment panics on a failed transaction, so they are sent through our own client
+    Remote { env: RemoteEnvironment, client: RpcClient },
+}
+
+impl Backend {
+    /// A bank with the given programs (`pubkey`, path of the `.so`) and accounts already in it
+    pub fn local(programs: &[(Pubkey, String)], accounts: Vec<(Pubkey, Account)>) -> Self {
+        let mut builder = LocalEnvironment::builder();
+        for (program_id, path) in programs {
+            builder.add_program(*program_id, path);
+        }
+        for (pubkey, account) in accounts {
+            builder.add_account(pubkey, account);
+        }
+        Backend::Local(builder.build())
+    }
+
+    /// A validator where the programs are already deployed, fees are paid by the `payer` actor
+    pub fn remote(url: &str, payer: Keypair) -> Self {
+        let client = || RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+        let env = RemoteEnvironment::new_with_airdrop(client(), payer, 10000000000);
+        Backend::Remote {
+            env,
+            client: client(),
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self, Backend::Local(_))
+    }
+
+    pub fn get_account(&self, pubkey: Pubkey) -> Option<Account> {
+        match self {
+            Backend::Local(env) => env.get_account(pubkey),
+            Backend::Remote { env, .. } => env.get_account(pubkey),
+        }
+    }
+
+    pub fn lamports(&self, pubkey: Pubkey) -> u64 {
+        self.get_account(pubkey).map_or(0, |account| account.lamports)
+    }
+
+    pub fn rent(&self, space: usize) -> u64 {
+        match self {
+            Backend::Local(env) => env.get_rent_excemption(space),
+            Backend::Remote { env, .. } => env.get_rent_excemption(space),
+        }
+    }
+
+    /// Tops the account up to `lamports`, like `RemoteEnvironment::airdrop`
+    pub fn airdrop(&mut self, pubkey: Pubkey, lamports: u64) {
+        let balance = self.lamports(pubkey);
+        if balance >= lamports {
+            return;
+        }
+        match self {
+            Backend::Local(env) => {
+                let payer = env.payer();
+                env.execute_as_transaction(
+                    &[system_instruction::transfer(&payer.pubkey(), &pubkey, lamports - balance)],
+                    &[],
+                )
+                .assert_success();
+            }
+            Backend::Remote { env, .. } => env.airdrop(pubkey, lamports),
+        }
+    }
+
+    /// A `bpf_loader` owned account holding `data`, the way a crafted account is made on a validator
+    pub fn create_account_with_data(&mut self, account: &Keypair, data: Vec<u8>) {
+        match self {
+            Backend::Local(env) => env.create_account_with_data(account, data),
+            Backend::Remote { env, .. } => env.create_account_with_data(account, data),
+        }
+    }
+
+    pub fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Outcome {
+        match self {
+            Backend::Local(env) => {
+                let tx = env.tx_with_instructions(instructions, signers);
+                let meta = env.execute_transaction(tx).transaction.meta;
+                Outcome {
+                    error: meta.as_ref().and_then(|m| m.err.as_ref()).map(|e| e.to_string()),
+                    logs: meta.and_then(|m| m.log_messages).unwrap_or_default(),
+                }
+            }
+            Backend::Remote { env, client } => {
+                let tx = env.tx_with_instructions(instructions, signers);
+                match client.send_and_confirm_transaction(&tx) {
+                    Ok(signature) => {
+                        let logs = client
+                            .get_transaction_with_config(
+                                &signature,
+                                RpcTransactionConfig {
+                                    encoding: Some(UiTransactionEncoding::Json),
+                                    commitment: Some(CommitmentConfig::confirmed()),
+                                    ..RpcTransactionConfig::default()
+                                },
+                            )
+                            .ok()
+                            .and_then(|tx| tx.transaction.meta)
+                            .and_then(|meta| meta.log_messages)
+                            .unwrap_or_default();
+                        Outcome { error: None, logs }
+                    }
+                    Err(e) => {
+                        let logs = match e.kind() {
+                            ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                                data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+                                ..
+                            }) => result.logs.clone().unwrap_or_default(),
+                            _ => vec![],
+                        };
+                        Outcome {
+                            error: Some(e.to_string()),
+                            logs,
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn payer(&self) -> Keypair {
+        match self {
+            Backend::Local(env) => env.payer(),
+            Backend::Remote { env, .. } => env.payer(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use owo_colors::OwoColorize;
+
+use scenario::{file::Scenario, Runner, Target};
+
+const USAGE: &str = "usage: scenario [--local | --validator | --url <RPC_URL>] [--root <REPO_ROOT>] <SCENARIO.toml>...";
+
+pub fn main() {
+    let mut target = Target::Local;
+    let mut root = PathBuf::from(".");
+    let mut files = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--local" => target = Target::Local,
+            "--validator" => target = Target::Remote("http://localhost:8899/".to_string()),
+            "--url" => target = Target::Remote(args.next().expect(USAGE)),
+            "--root" => root = PathBuf::from(args.next().expect(USAGE)),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    }
+
+    let mut all_as_expected = true;
+    for file in files {
+        let result = std::fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|s| toml::from_str::<Scenario>(&s).map_err(|e| e.to_string()))
+            .and_then(|scenario| Runner::new(scenario, &root, &target))
+            .and_then(|mut runner| runner.run());
+        match result {
+            Ok(true) => {}
+            Ok(false) => {
+                println!("{} {}", file.red().bold(), "did not go as expected".red().bold());
+                all_as_expected = false;
+            }
+            Err(e) => {
+                println!("{} {}", file.red().bold(), e.red());
+                all_as_expected = false;
+            }
+        }
+    }
+    if !all_as_expected {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,153 @@
+//! Integer expressions for amounts: `5 sol`, `lamports(vault) - rent(33)`, `-(lamports(wallet) - 1)`
+use poc_framework::solana_program::native_token::LAMPORTS_PER_SOL;
+
+/// What an expression can ask about the chain, when it is evaluated
+pub trait Context {
+    fn lamports(&self, name: &str) -> Result<u64, String>;
+    fn rent(&self, space: usize) -> u64;
+}
+
+pub fn eval(expr: &str, context: &dyn Context) -> Result<i128, String> {
+    let mut parser = Parser {
+        tokens: tokenize(expr)?,
+        position: 0,
+        context,
+    };
+    let value = parser.sum()?;
+    match parser.tokens.get(parser.position) {
+        None => Ok(value),
+        Some(token) => Err(format!("unexpected {:?} in {:?}", token, expr)),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(String),
+    Word(String),
+    Symbol(char),
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = expr.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut number = String::new();
+            while let Some(&c) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '.' || c == '_') {
+                    break;
+                }
+                if c != '_' {
+                    number.push(c);
+                }
+                chars.next();
+            }
+            tokens.push(Token::Number(number));
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        } else if "+-()".contains(c) {
+            tokens.push(Token::Symbol(c));
+            chars.next();
+        } else {
+            return Err(format!("unexpected {:?} in {:?}", c, expr));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    context: &'a dyn Context,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Symbol(c)) if c == symbol => Ok(()),
+            other => Err(format!("expected {:?}, found {:?}", symbol, other)),
+        }
+    }
+
+    fn sum(&mut self) -> Result<i128, String> {
+        let mut value = self.term()?;
+        while let Some(Token::Symbol(c)) = self.peek().cloned() {
+            match c {
+                '+' => {
+                    self.next();
+                    value += self.term()?;
+                }
+                '-' => {
+                    self.next();
+                    value -= self.term()?;
+                }
+                _ => break,
+            }
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<i128, String> {
+        match self.next() {
+            Some(Token::Symbol('-')) => Ok(-self.term()?),
+            Some(Token::Symbol('(')) => {
+                let value = self.sum()?;
+                self.expect(')')?;
+                Ok(value)
+            }
+            Some(Token::Number(number)) => {
+                if let Some(Token::Word(unit)) = self.peek() {
+                    if unit == "sol" {
+                        self.next();
+                        let sol: f64 = number
+                            .parse()
+                            .map_err(|_| format!("bad number {:?}", number))?;
+                        return Ok((sol * LAMPORTS_PER_SOL as f64) as i128);
+                    }
+                }
+                number
+                    .parse()
+                    .map_err(|_| format!("bad integer {:?}", number))
+            }
+            Some(Token::Word(function)) => {
+                self.expect('(')?;
+                let argument = match self.next() {
+                    Some(Token::Word(argument)) | Some(Token::Number(argument)) => argument,
+                    other => return Err(format!("expected an argument, found {:?}", other)),
+                };
+                self.expect(')')?;
+                match function.as_str() {
+                    "lamports" => Ok(self.context.lamports(&argument)? as i128),
+                    "rent" => {
+                        let space = argument
+                            .parse()
+                            .map_err(|_| format!("bad size {:?}", argument))?;
+                        Ok(self.context.rent(space) as i128)
+                    }
+                    _ => Err(format!("unknown function {}", function)),
+                }
+            }
+            other => Err(format!("unexpected {:?}", other)),
+        }
+    }
+}
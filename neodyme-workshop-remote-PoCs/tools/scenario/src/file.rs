@@ -0,0 +1,142 @@
+//! The scenario format, read from TOML
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Level programs used by the scenario, by crate name (`level0`, `amm`, ...)
+    pub programs: Vec<String>,
+    #[serde(default)]
+    pub actors: BTreeMap<String, Actor>,
+    #[serde(default)]
+    pub pdas: BTreeMap<String, Pda>,
+    #[serde(default)]
+    pub accounts: BTreeMap<String, Crafted>,
+    #[serde(default)]
+    pub steps: Vec<Step>,
+}
+
+/// A named signer, whose keypair comes from the actor registry
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Actor {
+    /// Role in the registry, defaults to the name of the actor
+    #[serde(default)]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub airdrop: Option<Amount>,
+}
+
+/// `find_program_address(seeds, program)`, or `create_program_address(seeds + [bump], program)`
+/// when the bump is given
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pda {
+    pub program: String,
+    pub seeds: Vec<Seed>,
+    #[serde(default)]
+    pub bump: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Seed {
+    /// The bytes of a named account
+    Pubkey(String),
+    Str(String),
+    U8(u8),
+}
+
+/// An account written as-is, i.e. a fake Wallet or a forged ProgramData
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Crafted {
+    /// Role in the actor registry, a new keypair every run by default
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Defaults to `bpf_loader`, the only owner a crafted account can have on a validator
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub data: Vec<Field>,
+    /// Zeroed data, instead of `data`
+    #[serde(default)]
+    pub space: Option<usize>,
+    /// Defaults to the rent exemption
+    #[serde(default)]
+    pub lamports: Option<Amount>,
+}
+
+/// One borsh field of a crafted account
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Pubkey(String),
+    Bool(bool),
+    U8(u8),
+    U64(Amount),
+    I64(Amount),
+    F64(f64),
+    Bytes(Vec<u8>),
+}
+
+/// Either a number or an expression, i.e. `"5 sol"` or `"lamports(vault) - rent(33)"`
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Amount {
+    Int(i64),
+    Expr(String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    pub name: String,
+    /// Actors and crafted accounts signing the transaction, besides the payer
+    #[serde(default)]
+    pub signers: Vec<String>,
+    #[serde(default)]
+    pub expect: Expect,
+    /// Send the transaction this many times, the arguments are evaluated again each time
+    #[serde(default = "one")]
+    pub repeat: u32,
+    /// Accounts whose lamports are printed after the step
+    #[serde(default)]
+    pub show: Vec<String>,
+    pub instructions: Vec<StepInstruction>,
+}
+
+fn one() -> u32 {
+    1
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Expect {
+    #[default]
+    Success,
+    Failure,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StepInstruction {
+    /// A level program, or `system`
+    pub program: String,
+    /// Variant of the instruction enum of the program
+    #[serde(default)]
+    pub instruction: Option<String>,
+    /// Raw instruction data, instead of `instruction`
+    #[serde(default)]
+    pub data: Option<Vec<u8>>,
+    #[serde(default)]
+    pub args: BTreeMap<String, toml::Value>,
+    /// `"name"` (readonly), `"w:name"` (writable), `"s:name"` (signer) or `"ws:name"`
+    #[serde(default)]
+    pub accounts: Vec<String>,
+}
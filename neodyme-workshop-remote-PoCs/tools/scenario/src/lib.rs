@@ -0,0 +1,425 @@
+//! Runs the declarative scenarios in `tools/scenarios`: actors, PDAs and crafted accounts are
+//! named once, and the steps build the instructions of the levels by variant name
+use std::{collections::BTreeMap, path::Path};
+
+use owo_colors::OwoColorize;
+use poc_framework::{
+    clone_keypair, random_keypair,
+    solana_sdk::{
+        account::Account,
+        bpf_loader,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::{read_keypair_file, Keypair, Signer},
+        system_instruction::SystemInstruction,
+    },
+};
+
+pub mod backend;
+pub mod expr;
+pub mod file;
+
+use actors::Actors;
+use backend::Backend;
+use expr::Context;
+use file::{Amount, Crafted, Expect, Field, Pda, Scenario, Seed, StepInstruction};
+
+/// Where to run a scenario
+pub enum Target {
+    /// In-process bank, loading the programs from `<level>/target/so`
+    Local,
+    /// Validator at this RPC url, with the programs already deployed
+    Remote(String),
+}
+
+/// Every named account of a scenario
+pub struct Names {
+    pub programs: BTreeMap<String, Pubkey>,
+    pub pubkeys: BTreeMap<String, Pubkey>,
+    pub keypairs: BTreeMap<String, Keypair>,
+    /// Where the keypairs of the actors come from, and the labels of the logs
+    pub actors: Actors,
+}
+
+impl Names {
+    /// Actors, PDAs, crafted accounts, programs, sysvars or a base58 pubkey
+    pub fn resolve(&self, name: &str) -> Result<Pubkey, String> {
+        self.pubkeys
+            .get(name)
+            .or_else(|| self.programs.get(name))
+            .copied()
+            .or_else(|| levels::well_known(name))
+            .or_else(|| name.parse().ok())
+            .ok_or(format!("unknown account {}", name))
+    }
+
+    /// PDAs can use each other as seeds, so they are resolved until nothing is left
+    fn resolve_pdas(&mut self, pdas: &BTreeMap<String, Pda>) -> Result<(), String> {
+        let mut pending: Vec<&String> = pdas.keys().collect();
+        while !pending.is_empty() {
+            let before = pending.len();
+            let mut unresolved = vec![];
+            for name in pending {
+                let pda = &pdas[name];
+                let program_id = self.resolve(&pda.program)?;
+                let mut seeds: Vec<Vec<u8>> = vec![];
+                let mut ready = true;
+                for seed in &pda.seeds {
+                    match seed {
+                        Seed::Pubkey(account) => match self.resolve(account) {
+                            Ok(pubkey) => seeds.push(pubkey.to_bytes().to_vec()),
+                            Err(_) => ready = false,
+                        },
+                        Seed::Str(s) => seeds.push(s.as_bytes().to_vec()),
+                        Seed::U8(byte) => seeds.push(vec![*byte]),
+                    }
+                }
+                if !ready {
+                    unresolved.push(name);
+                    continue;
+                }
+                let mut seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+                let address = match pda.bump {
+                    Some(ref bump) => {
+                        seeds.push(std::slice::from_ref(bump));
+                        Pubkey::create_program_address(&seeds, &program_id)
+                            .map_err(|e| format!("pda {}: {}", name, e))?
+                    }
+                    None => Pubkey::find_program_address(&seeds, &program_id).0,
+                };
+                self.pubkeys.insert(name.clone(), address);
+            }
+            if unresolved.len() == before {
+                return Err(format!("can't resolve the seeds of {:?}", unresolved));
+            }
+            pending = unresolved;
+        }
+        Ok(())
+    }
+
+    /// Crafted accounts exist before the scenario starts, so their amounts can't use `lamports()`
+    fn craft(&self, crafted: &Crafted) -> Result<Account, String> {
+        let amount = |amount: &Amount| match amount {
+            Amount::Int(v) => Ok(*v as i128),
+            Amount::Expr(e) => expr::eval(e, &BeforeStart),
+        };
+        let mut data = vec![0; crafted.space.unwrap_or(0)];
+        for field in &crafted.data {
+            match field {
+                Field::Pubkey(name) => data.extend_from_slice(&self.resolve(name)?.to_bytes()),
+                Field::Bool(v) => data.push(*v as u8),
+                Field::U8(v) => data.push(*v),
+                Field::U64(v) => data.extend_from_slice(&(amount(v)? as u64).to_le_bytes()),
+                Field::I64(v) => data.extend_from_slice(&(amount(v)? as i64).to_le_bytes()),
+                Field::F64(v) => data.extend_from_slice(&v.to_le_bytes()),
+                Field::Bytes(v) => data.extend_from_slice(v),
+            }
+        }
+        let owner = match &crafted.owner {
+            Some(owner) => self.resolve(owner)?,
+            None => bpf_loader::id(),
+        };
+        let lamports = match &crafted.lamports {
+            Some(lamports) => amount(lamports)? as u64,
+            None => Rent::default().minimum_balance(data.len()),
+        };
+        Ok(Account {
+            lamports,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        })
+    }
+}
+
+struct BeforeStart;
+
+impl Context for BeforeStart {
+    fn lamports(&self, name: &str) -> Result<u64, String> {
+        Err(format!("lamports({}) can't be used in a crafted account", name))
+    }
+
+    fn rent(&self, space: usize) -> u64 {
+        Rent::default().minimum_balance(space)
+    }
+}
+
+pub struct Runner {
+    scenario: Scenario,
+    backend: Backend,
+    names: Names,
+}
+
+impl Runner {
+    /// Resolves every name of the scenario and sets up the backend, `root` being the root of the repo
+    pub fn new(scenario: Scenario, root: &Path, target: &Target) -> Result<Self, String> {
+        let mut names = Names {
+            programs: BTreeMap::new(),
+            pubkeys: BTreeMap::new(),
+            keypairs: BTreeMap::new(),
+            actors: Actors::new(),
+        };
+        let mut so_files = vec![];
+        for name in &scenario.programs {
+            let program = levels::find(name).ok_or(format!("unknown program {}", name))?;
+            // `cargo build-bpf` writes the keypair next to the program, `./cicd.sh` deploys it there
+            let keypair_path = root.join(program.keypair_path());
+            let program_id = read_keypair_file(&keypair_path)
+                .map_err(|_| {
+                    format!(
+                        "{} not found, build {} first (./cicd.sh in {})",
+                        keypair_path.display(),
+                        name,
+                        program.dir
+                    )
+                })?
+                .pubkey();
+            so_files.push((program_id, root.join(program.so_path()).display().to_string()));
+            names.programs.insert(name.clone(), program_id);
+        }
+
+        for (name, actor) in &scenario.actors {
+            let keypair = names.actors.get(actor.role.as_deref().unwrap_or(name));
+            names.keypairs.insert(name.clone(), keypair);
+        }
+        for (name, crafted) in &scenario.accounts {
+            let keypair = match &crafted.role {
+                Some(role) => names.actors.get(role),
+                None => random_keypair(),
+            };
+            names.keypairs.insert(name.clone(), keypair);
+        }
+        for (name, keypair) in &names.keypairs {
+            names.pubkeys.insert(name.clone(), keypair.pubkey());
+        }
+        names.resolve_pdas(&scenario.pdas)?;
+        // The logs name the accounts the way the scenario does
+        for (name, pubkey) in names.pubkeys.iter().chain(names.programs.iter()) {
+            names.actors.label(*pubkey, name);
+        }
+
+        let mut crafted = vec![];
+        for (name, account) in &scenario.accounts {
+            crafted.push((name, names.craft(account)?));
+        }
+
+        let backend = match target {
+            Target::Local => {
+                let accounts = crafted
+                    .into_iter()
+                    .map(|(name, account)| (names.pubkeys[name], account))
+                    .collect();
+                Backend::local(&so_files, accounts)
+            }
+            Target::Remote(url) => {
+                let mut backend = Backend::remote(url, names.actors.get("payer"));
+                for (name, account) in crafted {
+                    if account.owner != bpf_loader::id() || scenario.accounts[name].lamports.is_some() {
+                        return Err(format!(
+                            "{}: only bpf_loader owned accounts with the default lamports can be crafted on a validator",
+                            name
+                        ));
+                    }
+                    backend.create_account_with_data(&names.keypairs[name], account.data);
+                }
+                backend
+            }
+        };
+        Ok(Runner {
+            scenario,
+            backend,
+            names,
+        })
+    }
+
+    pub fn resolve(&self, name: &str) -> Result<Pubkey, String> {
+        self.names.resolve(name)
+    }
+
+    fn amount(&self, amount: &Amount) -> Result<i128, String> {
+        match amount {
+            Amount::Int(v) => Ok(*v as i128),
+            Amount::Expr(e) => expr::eval(e, self),
+        }
+    }
+
+    fn arg(&self, value: &toml::Value) -> Result<levels::Value, String> {
+        Ok(match value {
+            toml::Value::Integer(v) => levels::Value::Int(*v as i128),
+            toml::Value::Float(v) => levels::Value::Float(*v),
+            toml::Value::String(s) => match self.resolve(s) {
+                Ok(pubkey) => levels::Value::Pubkey(pubkey),
+                Err(_) => levels::Value::Int(expr::eval(s, self)?),
+            },
+            toml::Value::Array(values) => {
+                levels::Value::List(values.iter().map(|v| self.arg(v)).collect::<Result<_, _>>()?)
+            }
+            _ => return Err(format!("unsupported argument {}", value)),
+        })
+    }
+
+    fn instruction(&self, ix: &StepInstruction) -> Result<(Instruction, String), String> {
+        let mut args = levels::Args::new();
+        for (name, value) in &ix.args {
+            args.insert(name, self.arg(value)?);
+        }
+        let (program_id, data, label) = if ix.program == "system" {
+            let name = ix.instruction.as_deref().unwrap_or_default();
+            let system_ix = match name {
+                "Transfer" => SystemInstruction::Transfer {
+                    lamports: args.get("lamports")?,
+                },
+                "CreateAccount" => SystemInstruction::CreateAccount {
+                    lamports: args.get("lamports")?,
+                    space: args.get("space")?,
+                    owner: args.get("owner")?,
+                },
+                _ => return Err(format!("unknown system instruction {:?}", name)),
+            };
+            let label = format!("system::{:?}", system_ix);
+            (levels::well_known("system_program").unwrap(), bincode::serialize(&system_ix).unwrap(), label)
+        } else {
+            let program = levels::find(&ix.program).ok_or(format!("unknown program {}", ix.program))?;
+            let program_id = *self
+                .names
+                .programs
+                .get(&ix.program)
+                .ok_or(format!("{} is not in the programs of the scenario", ix.program))?;
+            let data = match (&ix.instruction, &ix.data) {
+                (Some(name), None) => (program.encode)(name, &args)?,
+                (None, Some(data)) => data.clone(),
+                _ => return Err("set either instruction or data".to_string()),
+            };
+            let decoded = (program.decode)(&data).unwrap_or(format!("{:?}", data));
+            (program_id, data, format!("{}::{}", ix.program, decoded))
+        };
+
+        let mut accounts = vec![];
+        for account in &ix.accounts {
+            let (flags, name) = match account.split_once(':') {
+                Some((flags, name)) if flags.chars().all(|c| c == 'w' || c == 's') => (flags, name),
+                _ => ("", account.as_str()),
+            };
+            let pubkey = self.resolve(name)?;
+            let is_signer = flags.contains('s');
+            accounts.push(if flags.contains('w') {
+                AccountMeta::new(pubkey, is_signer)
+            } else {
+                AccountMeta::new_readonly(pubkey, is_signer)
+            });
+        }
+        Ok((
+            Instruction {
+                program_id,
+                accounts,
+                data,
+            },
+            label,
+        ))
+    }
+
+    /// Runs every step, returns whether they all ended as expected
+    pub fn run(&mut self) -> Result<bool, String> {
+        println!();
+        println!("{}", "********************************************".bright_blue().bold());
+        println!("{} {}", "*".bright_blue().bold(), self.scenario.name.bright_blue().bold());
+        println!("{}", "********************************************".bright_blue().bold());
+        if !self.scenario.description.is_empty() {
+            println!("{}", self.scenario.description.trim());
+        }
+        println!();
+        for (name, program_id) in &self.names.programs {
+            println!("{} {} {}", "Program: ".bold().blue(), name, program_id.blue());
+        }
+        for name in self.scenario.actors.keys() {
+            println!("{} {} {}", "Actor: ".bold().blue(), name, self.names.pubkeys[name].blue());
+        }
+
+        for (name, actor) in &self.scenario.actors {
+            if let Some(airdrop) = &actor.airdrop {
+                let lamports = self.amount(airdrop)? as u64;
+                let pubkey = self.names.pubkeys[name];
+                self.backend.airdrop(pubkey, lamports);
+            }
+        }
+        let balances_before: Vec<(String, u64)> = self
+            .scenario
+            .actors
+            .keys()
+            .map(|name| (name.clone(), self.backend.lamports(self.names.pubkeys[name])))
+            .collect();
+
+        let mut as_expected = true;
+        for step in &self.scenario.steps {
+            for round in 0..step.repeat {
+                let mut instructions = vec![];
+                println!();
+                if step.repeat > 1 {
+                    println!("{} ({}/{})", step.name.bold(), round + 1, step.repeat);
+                } else {
+                    println!("{}", step.name.bold());
+                }
+                for ix in &step.instructions {
+                    let (instruction, label) = self.instruction(ix)?;
+                    println!("    {}", label);
+                    instructions.push(instruction);
+                }
+                let signers = step
+                    .signers
+                    .iter()
+                    .map(|name| match self.names.keypairs.get(name) {
+                        Some(keypair) => Ok(clone_keypair(keypair)),
+                        None => Err(format!("{} has no keypair", name)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let outcome = self.backend.send(&instructions, &signers.iter().collect::<Vec<_>>());
+                let expected = match step.expect {
+                    Expect::Success => outcome.error.is_none(),
+                    Expect::Failure => outcome.error.is_some(),
+                };
+                match (&outcome.error, expected) {
+                    (None, true) => println!("{}", "  => succeeded".green()),
+                    (Some(e), true) => println!("{} {}", "  => failed as expected:".green(), e),
+                    (None, false) => println!("{}", "  => succeeded, but was expected to fail".red().bold()),
+                    (Some(e), false) => println!("{} {}", "  => failed:".red().bold(), self.names.actors.relabel(e)),
+                }
+                if !expected {
+                    as_expected = false;
+                    for log in &outcome.logs {
+                        println!("     {}", self.names.actors.relabel(log));
+                    }
+                }
+            }
+            for name in &step.show {
+                let lamports = self.backend.lamports(self.resolve(name)?);
+                println!("  {} {} {}", name.bold().blue(), "lamports:".blue(), lamports.blue());
+            }
+        }
+
+        println!();
+        for (name, before) in balances_before {
+            let after = self.backend.lamports(self.names.pubkeys[&name]);
+            let change = after as i128 - before as i128;
+            if change >= 0 {
+                println!("{} {} {}", name.bold(), "earned:".bold().green(), change.green());
+            } else {
+                println!("{} {} {}", name.bold(), "lost:".bold().red(), (-change).red());
+            }
+        }
+        println!();
+        Ok(as_expected)
+    }
+}
+
+impl Context for Runner {
+    fn lamports(&self, name: &str) -> Result<u64, String> {
+        Ok(self.backend.lamports(self.resolve(name)?))
+    }
+
+    fn rent(&self, space: usize) -> u64 {
+        self.backend.rent(space)
+    }
+}
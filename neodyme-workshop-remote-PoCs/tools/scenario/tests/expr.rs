@@ -0,0 +1,75 @@
+//! The amount expressions of the scenarios, against a made up chain: `vault` holds 5 SOL, `wallet`
+//! 1000 lamports, and the rent of `n` bytes is `1000 + n`
+use poc_framework::solana_program::native_token::LAMPORTS_PER_SOL;
+use scenario::expr::{eval, Context};
+
+struct Chain;
+
+impl Context for Chain {
+    fn lamports(&self, name: &str) -> Result<u64, String> {
+        match name {
+            "vault" => Ok(5 * LAMPORTS_PER_SOL),
+            "wallet" => Ok(1000),
+            _ => Err(format!("no account {}", name)),
+        }
+    }
+
+    fn rent(&self, space: usize) -> u64 {
+        1000 + space as u64
+    }
+}
+
+fn value(expr: &str) -> Result<i128, String> {
+    eval(expr, &Chain)
+}
+
+#[test]
+fn integers() {
+    assert_eq!(value("42"), Ok(42));
+    assert_eq!(value("1_000_000"), Ok(1_000_000));
+    assert_eq!(value(" 7  "), Ok(7));
+}
+
+#[test]
+fn sol() {
+    assert_eq!(value("5 sol"), Ok(5 * LAMPORTS_PER_SOL as i128));
+    assert_eq!(value("0.5 sol"), Ok(LAMPORTS_PER_SOL as i128 / 2));
+    assert_eq!(value("1sol"), Ok(LAMPORTS_PER_SOL as i128));
+    // Only with the unit
+    assert!(value("0.5").is_err());
+}
+
+#[test]
+fn sums() {
+    assert_eq!(value("1 + 2 - 4"), Ok(-1));
+    assert_eq!(value("1 sol - 1"), Ok(LAMPORTS_PER_SOL as i128 - 1));
+    assert_eq!(value("10 - (3 - 1)"), Ok(8));
+}
+
+#[test]
+fn unary_minus() {
+    assert_eq!(value("-5"), Ok(-5));
+    assert_eq!(value("--5"), Ok(5));
+    assert_eq!(value("-(lamports(wallet) - 1)"), Ok(-999));
+    // It binds to the term, not to the sum after it
+    assert_eq!(value("-1 + 3"), Ok(2));
+}
+
+#[test]
+fn functions() {
+    assert_eq!(value("lamports(vault)"), Ok(5 * LAMPORTS_PER_SOL as i128));
+    assert_eq!(value("rent(33)"), Ok(1033));
+    assert_eq!(value("lamports(vault) - rent(33)"), Ok(5 * LAMPORTS_PER_SOL as i128 - 1033));
+}
+
+#[test]
+fn errors() {
+    assert_eq!(value("lamports(nobody)"), Err("no account nobody".to_string()));
+    assert!(value("rent(wallet)").unwrap_err().contains("bad size"));
+    assert!(value("balance(vault)").unwrap_err().contains("unknown function balance"));
+    assert!(value("lamports vault").is_err());
+    assert!(value("(1 + 2").is_err());
+    assert!(value("1 2").unwrap_err().contains("unexpected"));
+    assert!(value("2 * 3").unwrap_err().contains("unexpected '*'"));
+    assert!(value("").is_err());
+}
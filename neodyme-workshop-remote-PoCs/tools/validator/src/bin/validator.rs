@@ -0,0 +1,166 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    time::Duration,
+};
+
+use owo_colors::OwoColorize;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+};
+
+use validator::{build, level_dir, preloads, Options, Validator};
+
+const USAGE: &str = "usage: validator [--root <REPO_ROOT>] [--build] [--native] [--ledger <DIR>]
+                 [--upgrade-authority <PUBKEY | KEYPAIR | none>] [--timeout <SECONDS>] LEVEL [-- COMMAND...]";
+
+/// What runs against the validator by default: the PoC (or the native) of the level, like
+/// `./cicd.sh` and `./native.sh`, and the `tok` client for level4
+fn default_command(workspace: &Path, native: bool) -> Option<Vec<String>> {
+    let client = if native { "native" } else { "pocs" };
+    if workspace.join(client).join("Cargo.toml").exists() {
+        return Some(vec![
+            "cargo".to_string(),
+            "run".to_string(),
+            format!("--manifest-path=./{}/Cargo.toml", client),
+            "--target-dir=./target/".to_string(),
+        ]);
+    }
+    if workspace.join("client/Cargo.toml").exists() {
+        return Some(["cargo", "run", "--bin", "tok"].iter().map(|s| s.to_string()).collect());
+    }
+    None
+}
+
+/// The pubkey of a keypair file, or the pubkey itself
+fn authority(arg: &str) -> Result<Option<Pubkey>, String> {
+    if arg == "none" {
+        return Ok(None);
+    }
+    if let Ok(pubkey) = Pubkey::from_str(arg) {
+        return Ok(Some(pubkey));
+    }
+    read_keypair_file(arg)
+        .map(|keypair| Some(keypair.pubkey()))
+        .map_err(|e| format!("{}: {}", arg, e))
+}
+
+fn main() {
+    let mut root = PathBuf::from(".");
+    let mut build_first = false;
+    let mut native = false;
+    let mut ledger = None;
+    let mut upgrade_authority = None;
+    let mut timeout = Duration::from_secs(60);
+    let mut level = None;
+    let mut command = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--root" => root = PathBuf::from(args.next().expect(USAGE)),
+            "--build" => build_first = true,
+            "--native" => native = true,
+            "--ledger" => ledger = Some(PathBuf::from(args.next().expect(USAGE))),
+            "--upgrade-authority" => upgrade_authority = Some(args.next().expect(USAGE)),
+            "--timeout" => timeout = Duration::from_secs(args.next().expect(USAGE).parse().expect(USAGE)),
+            "--" => command.extend(args.by_ref()),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if level.is_none() => level = Some(arg),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+    let level = level.expect(USAGE);
+    let dir = match level_dir(&level) {
+        Some(dir) => dir,
+        None => {
+            eprintln!("unknown level {}", level);
+            std::process::exit(2);
+        }
+    };
+    let workspace = root.join(dir);
+    if command.is_empty() {
+        command = match default_command(&workspace, native) {
+            Some(command) => command,
+            None => {
+                eprintln!("{} has nothing to run, pass a -- COMMAND", dir);
+                std::process::exit(2);
+            }
+        };
+    }
+    let upgrade_authority = match upgrade_authority {
+        Some(arg) => match authority(&arg) {
+            Ok(authority) => authority,
+            Err(e) => {
+                eprintln!("--upgrade-authority {}", e);
+                std::process::exit(2);
+            }
+        },
+        // The CLI wallet, the upgrade authority after `solana program deploy`. Without one, the
+        // programs are loaded with --bpf-program
+        None => {
+            let wallet = PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".config/solana/id.json");
+            read_keypair_file(wallet).ok().map(|keypair| keypair.pubkey())
+        }
+    };
+
+    if build_first {
+        if let Err(e) = build(&root, dir) {
+            eprintln!("{}", e.red());
+            std::process::exit(1);
+        }
+    }
+    let programs = match preloads(&root, dir) {
+        Ok(programs) => programs,
+        Err(e) => {
+            eprintln!("{}", e.red());
+            std::process::exit(1);
+        }
+    };
+
+    let options = Options {
+        ledger,
+        upgrade_authority,
+        timeout,
+    };
+    let validator = match Validator::start(&programs, &options) {
+        Ok(validator) => validator,
+        Err(e) => {
+            eprintln!("{}", e.red());
+            std::process::exit(1);
+        }
+    };
+    println!("{} {} (ledger {})", "solana-test-validator".bold(), validator.url(), validator.ledger().display());
+    for program in &programs {
+        let loaded = match &options.upgrade_authority {
+            Some(authority) => format!("upgradeable, authority {}", authority),
+            None => "not upgradeable".to_string(),
+        };
+        println!("  {} {} ({})", program.name, program.program_id, loaded.dimmed());
+    }
+    println!("{} {}\n", "running".bold(), command.join(" "));
+
+    let status = Command::new(&command[0])
+        .args(&command[1..])
+        .current_dir(&workspace)
+        .env("RPC_URL", validator.url())
+        .env("RUST_BACKTRACE", "1")
+        .status();
+    // Before exiting, which doesn't run the destructors
+    drop(validator);
+    match status {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("{}: {}", command[0], e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! A `solana-test-validator` of its own for every run: a child process on free ports, with a
+//! fresh ledger and the programs of the level preloaded from `target/so`, killed (and its ledger
+//! removed) when dropped. Nothing needs to be deployed, and nothing needs to be closed afterwards.
+use std::{
+    io::{Read, Seek, SeekFrom},
+    net::{TcpListener, UdpSocket},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+};
+
+/// Programs deployed next to a level that are not levels themselves: (name, folder)
+const COMPANIONS: &[(&str, &str)] = &[("myspl", "level4-hack"), ("mycallback", "level11-hack")];
+
+/// A program loaded at genesis
+#[derive(Clone, Debug)]
+pub struct Preload {
+    pub name: String,
+    pub program_id: Pubkey,
+    pub so: PathBuf,
+}
+
+/// The folder of a level, i.e. `level0-hack`
+pub fn level_dir(level: &str) -> Option<&'static str> {
+    levels::find(level).map(|program| program.dir)
+}
+
+/// `cargo build-bpf` of the workspace of a level, into its `target/so`, like `./cicd.sh`
+pub fn build(root: &Path, dir: &str) -> Result<(), String> {
+    let workspace = root.join(dir);
+    let status = Command::new("cargo")
+        .arg("build-bpf")
+        .arg(format!("--manifest-path={}", workspace.join("Cargo.toml").display()))
+        .arg(format!("--bpf-out-dir={}", workspace.join("target/so").display()))
+        .status()
+        .map_err(|e| format!("cargo build-bpf: {}", e))?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("cargo build-bpf failed for {}", dir)),
+    }
+}
+
+/// Every program built in the folder of a level: the level, and the ones it works with (`amm`,
+/// `mycallback`, `myspl`), under the program ids of their keypairs
+pub fn preloads(root: &Path, dir: &str) -> Result<Vec<Preload>, String> {
+    let names = levels::PROGRAMS
+        .iter()
+        .filter(|program| program.dir == dir)
+        .map(|program| program.name)
+        .chain(COMPANIONS.iter().filter(|(_, folder)| *folder == dir).map(|(name, _)| *name));
+    let so_dir = root.join(dir).join("target/so");
+    names
+        .map(|name| {
+            let so = so_dir.join(format!("{}.so", name));
+            let keypair = so_dir.join(format!("{}-keypair.json", name));
+            if !so.exists() {
+                return Err(format!("{} is not built, {} is missing (--build)", name, so.display()));
+            }
+            let program_id = read_keypair_file(&keypair)
+                .map_err(|e| format!("{}: {}", keypair.display(), e))?
+                .pubkey();
+            Ok(Preload {
+                name: name.to_string(),
+                program_id,
+                so,
+            })
+        })
+        .collect()
+}
+
+/// A port such that it and the `count - 1` next ones are free, over TCP and UDP. They stay bound to
+/// the returned sockets, so that nothing else takes them until these are dropped
+pub fn free_ports(count: u16) -> Result<(u16, Vec<(TcpListener, UdpSocket)>), String> {
+    for _ in 0..100 {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .map_err(|e| e.to_string())?
+            .port();
+        if port.checked_add(count).is_none() {
+            continue;
+        }
+        let sockets: Option<Vec<_>> = (port..port + count)
+            .map(|port| Some((TcpListener::bind(("127.0.0.1", port)).ok()?, UdpSocket::bind(("127.0.0.1", port)).ok()?)))
+            .collect();
+        if let Some(sockets) = sockets {
+            return Ok((port, sockets));
+        }
+    }
+    Err(format!("no {} free ports in a row", count))
+}
+
+/// Ports for `--dynamic-port-range`, where the validator binds its TPU, TVU, repair.. ports. Wider
+/// than the minimum of the validator (14 in 1.18)
+const DYNAMIC_PORTS: u16 = 32;
+
+/// The ports of a validator, one block of free ports: the RPC one, the websocket on the next one,
+/// the faucet, gossip, then the dynamic range. Held until `release`, right before the spawn
+struct Ports {
+    rpc: u16,
+    faucet: u16,
+    gossip: u16,
+    dynamic: (u16, u16),
+    held: Vec<(TcpListener, UdpSocket)>,
+}
+
+impl Ports {
+    fn reserve() -> Result<Self, String> {
+        let (port, held) = free_ports(4 + DYNAMIC_PORTS)?;
+        Ok(Ports {
+            rpc: port,
+            faucet: port + 2,
+            gossip: port + 3,
+            dynamic: (port + 4, port + 4 + DYNAMIC_PORTS - 1),
+            held,
+        })
+    }
+
+    /// Frees the ports for the validator
+    fn release(&mut self) {
+        self.held.clear();
+    }
+}
+
+pub struct Options {
+    /// Kept after the run when given, a temporary folder removed with the validator otherwise
+    pub ledger: Option<PathBuf>,
+    /// Programs are loaded upgradeable (like `solana program deploy` does) under this authority,
+    /// or with `--bpf-program` (not upgradeable, no ProgramData) without one
+    pub upgrade_authority: Option<Pubkey>,
+    /// How long to wait for the validator to be healthy
+    pub timeout: Duration,
+}
+
+/// A running `solana-test-validator`
+pub struct Validator {
+    child: Child,
+    ledger: PathBuf,
+    keep_ledger: bool,
+    rpc_port: u16,
+}
+
+impl Validator {
+    /// Spawns the validator and waits until it answers with a blockhash
+    pub fn start(programs: &[Preload], options: &Options) -> Result<Self, String> {
+        let mut ports = Ports::reserve()?;
+        let rpc_port = ports.rpc;
+        let (ledger, keep_ledger) = match &options.ledger {
+            Some(ledger) => (ledger.clone(), true),
+            None => (
+                std::env::temp_dir().join(format!("test-validator-{}-{}", std::process::id(), rpc_port)),
+                false,
+            ),
+        };
+
+        let mut command = Command::new("solana-test-validator");
+        command
+            .arg("--ledger")
+            .arg(&ledger)
+            .arg("--reset")
+            .arg("--quiet")
+            .args(["--rpc-port", &rpc_port.to_string()])
+            .args(["--faucet-port", &ports.faucet.to_string()])
+            .args(["--gossip-port", &ports.gossip.to_string()])
+            .args(["--dynamic-port-range", &format!("{}-{}", ports.dynamic.0, ports.dynamic.1)]);
+        for program in programs {
+            match &options.upgrade_authority {
+                Some(authority) => command
+                    .arg("--upgradeable-program")
+                    .arg(program.program_id.to_string())
+                    .arg(&program.so)
+                    .arg(authority.to_string()),
+                None => command
+                    .arg("--bpf-program")
+                    .arg(program.program_id.to_string())
+                    .arg(&program.so),
+            };
+        }
+        ports.release();
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("couldn't run solana-test-validator (is the Solana CLI in the PATH?): {}", e))?;
+
+        let mut validator = Validator {
+            child,
+            ledger,
+            keep_ledger,
+            rpc_port,
+        };
+        validator.wait_until_ready(options.timeout)?;
+        Ok(validator)
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}/", self.rpc_port)
+    }
+
+    pub fn ws_url(&self) -> String {
+        format!("ws://127.0.0.1:{}/", self.rpc_port + 1)
+    }
+
+    pub fn ledger(&self) -> &Path {
+        &self.ledger
+    }
+
+    pub fn client(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.url(), CommitmentConfig::confirmed())
+    }
+
+    fn wait_until_ready(&mut self, timeout: Duration) -> Result<(), String> {
+        let client = self.client();
+        let start = Instant::now();
+        loop {
+            if let Ok(Some(status)) = self.child.try_wait() {
+                return Err(format!("solana-test-validator exited ({}){}", status, self.log_tail()));
+            }
+            if client.get_health().is_ok() && client.get_latest_blockhash().is_ok() {
+                return Ok(());
+            }
+            if start.elapsed() > timeout {
+                return Err(format!(
+                    "solana-test-validator wasn't healthy after {}s{}",
+                    timeout.as_secs(),
+                    self.log_tail()
+                ));
+            }
+            thread::sleep(Duration::from_millis(250));
+        }
+    }
+
+    /// The end of `validator.log`, to explain why it didn't start
+    fn log_tail(&self) -> String {
+        let mut tail = vec![];
+        if let Ok(mut file) = std::fs::File::open(self.ledger.join("validator.log")) {
+            let length = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+            let _ = file.seek(SeekFrom::Start(length.saturating_sub(2000)));
+            let _ = file.read_to_end(&mut tail);
+        }
+        let tail = String::from_utf8_lossy(&tail);
+        match tail.is_empty() {
+            true => String::new(),
+            false => format!(", end of {}:\n{}", self.ledger.join("validator.log").display(), tail),
+        }
+    }
+}
+
+impl Drop for Validator {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if !self.keep_ledger {
+            let _ = std::fs::remove_dir_all(&self.ledger);
+        }
+    }
+}